@@ -0,0 +1,212 @@
+//! Renders the linker script of the selected chip
+//!
+//! `link/link.x.in` is filled in with the memory map below and written to
+//! OUT_DIR as `link.x`, together with `handlers.x`. The firmware is linked
+//! with `-Tlink.x`, see `.cargo/config.toml`.
+//!
+//! The stack and heap sizes default to `STACK_SIZE` and `HEAP_SIZE` and can
+//! be overridden with the environment variables of the same name, e.g.
+//! `STACK_SIZE=0x1000 cargo firmware`. The `heap` feature raises the default
+//! heap size to `ALLOC_HEAP_SIZE`, `heap-in-ccmram` moves the heap to CCM
+//! RAM.
+//!
+//! `VECT_TAB_OFFSET` moves the image, and with it the vector table, up in
+//! flash for applications started by a bootloader, e.g.
+//! `VECT_TAB_OFFSET=0x8000 cargo firmware`. The offset is also written to
+//! `config.rs` in OUT_DIR for `system_init`. With the `vect-tab-sram`
+//! feature room for a copy of the vector table is kept at the start of RAM.
+//! With `data-in-ext-sram` the external SRAM on FSMC bank 1, NE2, of
+//! `EXT_SRAM_SIZE` bytes holds the `.extsram` section.
+//!
+//! The main stack goes to the top of RAM, or of CCM RAM with the
+//! `stack-in-ccmram` feature. The `stack-guard` feature reserves a guard
+//! region below it and a separate stack for the fault handlers.
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Memory description of a chip family
+struct Memory {
+    flash_origin    : u32,
+    flash_length    : u32,
+    ram_origin      : u32,
+    ram_length      : u32,
+    ccmram_origin   : u32,
+    ccmram_length   : u32,
+    bkpsram_origin  : u32,
+    bkpsram_length  : u32,
+}
+
+/// STM32F405/407/415/417, xG parts: 1 MB flash, SRAM1 and SRAM2 form 128 KB
+/// of contiguous RAM
+const STM32F40X : Memory = Memory {
+    flash_origin    : 0x08000000,
+    flash_length    : 1024 * 1024,
+    ram_origin      : 0x20000000,
+    ram_length      : 128 * 1024,
+    ccmram_origin   : 0x10000000,
+    ccmram_length   : 64 * 1024,
+    bkpsram_origin  : 0x40024000,
+    bkpsram_length  : 4 * 1024,
+};
+
+/// STM32F427/429, xI parts: 2 MB flash, SRAM1, SRAM2 and SRAM3 form 192 KB
+/// of contiguous RAM
+const STM32F42X : Memory = Memory {
+    flash_origin    : 0x08000000,
+    flash_length    : 2048 * 1024,
+    ram_origin      : 0x20000000,
+    ram_length      : 192 * 1024,
+    ccmram_origin   : 0x10000000,
+    ccmram_length   : 64 * 1024,
+    bkpsram_origin  : 0x40024000,
+    bkpsram_length  : 4 * 1024,
+};
+
+const STACK_SIZE : u32 = 0x400;
+const HEAP_SIZE : u32 = 0;
+/// Heap size with the `heap` feature, unless HEAP_SIZE is set
+const ALLOC_HEAP_SIZE : u32 = 16 * 1024;
+
+/// MPU region below the stack with the `stack-guard` feature
+const STACK_GUARD_SIZE : u32 = 256;
+/// Stack of the fault handlers with the `stack-guard` feature
+const FAULT_STACK_SIZE : u32 = 0x400;
+
+/// Room for the vector table in RAM, VTOR needs it 512 byte aligned
+const VTOR_RAM_SIZE : u32 = 0x200;
+
+/// FSMC bank 1, NE2, as set up by `system_init_ext_mem_ctl`
+const EXT_SRAM_ORIGIN : u32 = 0x64000000;
+/// IS61WV102416BLL on the STM324xG-EVAL, 1M x 16 bit
+const EXT_SRAM_SIZE : u32 = 2 * 1024 * 1024;
+
+/// Reads a size from the environment, decimal or `0x` hex
+fn env_size(name: &str, default: u32) -> u32 {
+    println!("cargo:rerun-if-env-changed={}", name);
+    match env::var(name) {
+        Ok(value) => {
+            let value = value.trim();
+            let parsed = if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+                u32::from_str_radix(hex, 16)
+            } else {
+                value.parse()
+            };
+            parsed.unwrap_or_else(|_| panic!("{}={} is not a size", name, value))
+        },
+        Err(_) => default,
+    }
+}
+
+fn hex(value: u32) -> String {
+    format!("0x{:08X}", value)
+}
+
+fn render(template: &str, vars: &[(&str, String)]) -> String {
+    let mut script = template.to_string();
+    for &(name, ref value) in vars {
+        script = script.replace(&format!("${{{}}}", name), value);
+    }
+    if let Some(start) = script.find("${") {
+        let end = script[start..].find('}').map(|e| start + e + 1).unwrap_or(script.len());
+        panic!("link.x.in: unknown placeholder {}", &script[start..end]);
+    }
+    script
+}
+
+fn main() {
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    // Chip selection errors are reported by the crate itself
+    let memory = if env::var_os("CARGO_FEATURE_STM32F42X").is_some() {
+        &STM32F42X
+    } else {
+        &STM32F40X
+    };
+
+    let vect_tab_offset = env_size("VECT_TAB_OFFSET", 0);
+    if !vect_tab_offset.is_multiple_of(VTOR_RAM_SIZE) || vect_tab_offset >= memory.flash_length {
+        panic!("VECT_TAB_OFFSET=0x{:X} must be a multiple of 0x{:X} inside flash",
+               vect_tab_offset, VTOR_RAM_SIZE);
+    }
+
+    let vtor_ram_size = if env::var_os("CARGO_FEATURE_VECT_TAB_SRAM").is_some() {
+        VTOR_RAM_SIZE
+    } else {
+        0
+    };
+
+    let ext_sram_size = if env::var_os("CARGO_FEATURE_DATA_IN_EXT_SRAM").is_some() {
+        env_size("EXT_SRAM_SIZE", EXT_SRAM_SIZE)
+    } else {
+        0
+    };
+
+    let stack_size = env_size("STACK_SIZE", STACK_SIZE);
+    let (stack_guard_size, fault_stack_size) = if env::var_os("CARGO_FEATURE_STACK_GUARD").is_some() {
+        // The guard ends at the bottom of the stack, the MPU needs it
+        // aligned to its size
+        if !stack_size.is_multiple_of(STACK_GUARD_SIZE) {
+            panic!("STACK_SIZE=0x{:X} must be a multiple of 0x{:X} with the stack-guard feature",
+                   stack_size, STACK_GUARD_SIZE);
+        }
+        (STACK_GUARD_SIZE, FAULT_STACK_SIZE)
+    } else {
+        (0, 0)
+    };
+
+    let heap_size = if env::var_os("CARGO_FEATURE_HEAP").is_some() {
+        env_size("HEAP_SIZE", ALLOC_HEAP_SIZE)
+    } else {
+        env_size("HEAP_SIZE", HEAP_SIZE)
+    };
+
+    let heap_region = if env::var_os("CARGO_FEATURE_HEAP_IN_CCMRAM").is_some() {
+        "CCMRAM"
+    } else {
+        "RAM"
+    };
+
+    let stack_region = if env::var_os("CARGO_FEATURE_STACK_IN_CCMRAM").is_some() {
+        "CCMRAM"
+    } else {
+        "RAM"
+    };
+
+    let script = render(include_str!("link/link.x.in"), &[
+        ("FLASH_ORIGIN", hex(memory.flash_origin + vect_tab_offset)),
+        ("FLASH_LENGTH", hex(memory.flash_length - vect_tab_offset)),
+        ("RAM_ORIGIN", hex(memory.ram_origin)),
+        ("RAM_LENGTH", hex(memory.ram_length)),
+        ("CCMRAM_ORIGIN", hex(memory.ccmram_origin)),
+        ("CCMRAM_LENGTH", hex(memory.ccmram_length)),
+        ("BKPSRAM_ORIGIN", hex(memory.bkpsram_origin)),
+        ("BKPSRAM_LENGTH", hex(memory.bkpsram_length)),
+        ("STACK_REGION", stack_region.to_string()),
+        ("HEAP_REGION", heap_region.to_string()),
+        ("STACK_SIZE", hex(stack_size)),
+        ("STACK_GUARD_SIZE", hex(stack_guard_size)),
+        ("FAULT_STACK_SIZE", hex(fault_stack_size)),
+        ("HEAP_SIZE", hex(heap_size)),
+        ("VTOR_RAM_SIZE", hex(vtor_ram_size)),
+        ("EXTSRAM_ORIGIN", hex(EXT_SRAM_ORIGIN)),
+        ("EXTSRAM_LENGTH", hex(ext_sram_size)),
+    ]);
+
+    let config = format!("pub const VECT_TAB_OFFSET : u32 = 0x{:08X};\n", vect_tab_offset);
+    File::create(out.join("config.rs")).and_then(|mut f| f.write_all(config.as_bytes()))
+                                       .expect("config.rs");
+
+    File::create(out.join("link.x")).and_then(|mut f| f.write_all(script.as_bytes()))
+                                    .expect("link.x");
+    File::create(out.join("handlers.x"))
+        .and_then(|mut f| f.write_all(include_bytes!("link/handlers.x")))
+        .expect("handlers.x");
+
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=link/link.x.in");
+    println!("cargo:rerun-if-changed=link/handlers.x");
+}
@@ -0,0 +1,5 @@
+#![allow(dead_code)]
+
+//! Build options set through the environment, written by build.rs
+
+include!(concat!(env!("OUT_DIR"), "/config.rs"));
@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+//! Memory barrier instructions, no-ops on the host
+
+/// Data Synchronization Barrier, completes all outstanding memory accesses
+#[cfg(target_arch = "arm")]
+pub fn dsb() {
+    unsafe { asm!("dsb" ::: "memory" : "volatile"); }
+}
+
+#[cfg(not(target_arch = "arm"))]
+pub fn dsb() {}
+
+/// Instruction Synchronization Barrier, flushes the pipeline so following
+/// instructions see the effect of system control register writes
+#[cfg(target_arch = "arm")]
+pub fn isb() {
+    unsafe { asm!("isb" ::: "memory" : "volatile"); }
+}
+
+#[cfg(not(target_arch = "arm"))]
+pub fn isb() {}
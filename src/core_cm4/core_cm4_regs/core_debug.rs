@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+//! Type definitions for the Cortex-M4 Core Debug registers
+
+use ::volatile_reg32::*;
+use super::super::constants::CORE_DEBUG_BASE;
+
+/// Structure type to access the Core Debug Register (CoreDebug).
+pub struct CoreDebugRegs {
+    /// Offset: 0x000 (R/W)  Debug Halting Control and Status Register
+    pub dhcsr   : VolatileReg32,
+    /// Offset: 0x004 ( /W)  Debug Core Register Selector Register
+    pub dcrsr   : WriteOnlyReg32,
+    /// Offset: 0x008 (R/W)  Debug Core Register Data Register
+    pub dcrdr   : VolatileReg32,
+    /// Offset: 0x00C (R/W)  Debug Exception and Monitor Control Register
+    pub demcr   : VolatileReg32,
+}
+
+impl CoreDebugRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> CoreDebugRegs {
+        let core_debug_base: *mut u32 = CORE_DEBUG_BASE as *mut u32;
+
+        CoreDebugRegs {
+            dhcsr   : VolatileReg32::new(core_debug_base),
+            dcrsr   : WriteOnlyReg32::new_offset(core_debug_base, 1),
+            dcrdr   : VolatileReg32::new_offset(core_debug_base, 2),
+            demcr   : VolatileReg32::new_offset(core_debug_base, 3),
+        }
+    }
+}
+
+/// Debug Halting Control and Status Register Definitions
+pub const CORE_DEBUG_DHCSR_DBGKEY_POS       : u32   = 16;
+pub const CORE_DEBUG_DHCSR_DBGKEY_MSK       : u32   = 0xFFFF << CORE_DEBUG_DHCSR_DBGKEY_POS;
+
+pub const CORE_DEBUG_DHCSR_S_RESET_ST_POS   : u32   = 25;
+pub const CORE_DEBUG_DHCSR_S_RESET_ST_MSK   : u32   = 1 << CORE_DEBUG_DHCSR_S_RESET_ST_POS;
+
+pub const CORE_DEBUG_DHCSR_S_RETIRE_ST_POS  : u32   = 24;
+pub const CORE_DEBUG_DHCSR_S_RETIRE_ST_MSK  : u32   = 1 << CORE_DEBUG_DHCSR_S_RETIRE_ST_POS;
+
+pub const CORE_DEBUG_DHCSR_S_LOCKUP_POS     : u32   = 19;
+pub const CORE_DEBUG_DHCSR_S_LOCKUP_MSK     : u32   = 1 << CORE_DEBUG_DHCSR_S_LOCKUP_POS;
+
+pub const CORE_DEBUG_DHCSR_S_SLEEP_POS      : u32   = 18;
+pub const CORE_DEBUG_DHCSR_S_SLEEP_MSK      : u32   = 1 << CORE_DEBUG_DHCSR_S_SLEEP_POS;
+
+pub const CORE_DEBUG_DHCSR_S_HALT_POS       : u32   = 17;
+pub const CORE_DEBUG_DHCSR_S_HALT_MSK       : u32   = 1 << CORE_DEBUG_DHCSR_S_HALT_POS;
+
+pub const CORE_DEBUG_DHCSR_S_REGRDY_POS     : u32   = 16;
+pub const CORE_DEBUG_DHCSR_S_REGRDY_MSK     : u32   = 1 << CORE_DEBUG_DHCSR_S_REGRDY_POS;
+
+pub const CORE_DEBUG_DHCSR_C_SNAPSTALL_POS  : u32   = 5;
+pub const CORE_DEBUG_DHCSR_C_SNAPSTALL_MSK  : u32   = 1 << CORE_DEBUG_DHCSR_C_SNAPSTALL_POS;
+
+pub const CORE_DEBUG_DHCSR_C_MASKINTS_POS   : u32   = 3;
+pub const CORE_DEBUG_DHCSR_C_MASKINTS_MSK   : u32   = 1 << CORE_DEBUG_DHCSR_C_MASKINTS_POS;
+
+pub const CORE_DEBUG_DHCSR_C_STEP_POS       : u32   = 2;
+pub const CORE_DEBUG_DHCSR_C_STEP_MSK       : u32   = 1 << CORE_DEBUG_DHCSR_C_STEP_POS;
+
+pub const CORE_DEBUG_DHCSR_C_HALT_POS       : u32   = 1;
+pub const CORE_DEBUG_DHCSR_C_HALT_MSK       : u32   = 1 << CORE_DEBUG_DHCSR_C_HALT_POS;
+
+pub const CORE_DEBUG_DHCSR_C_DEBUGEN_POS    : u32   = 0;
+pub const CORE_DEBUG_DHCSR_C_DEBUGEN_MSK    : u32   = 1 << CORE_DEBUG_DHCSR_C_DEBUGEN_POS;
+
+/// Debug Exception and Monitor Control Register Definitions
+pub const CORE_DEBUG_DEMCR_TRCENA_POS       : u32   = 24;
+pub const CORE_DEBUG_DEMCR_TRCENA_MSK       : u32   = 1 << CORE_DEBUG_DEMCR_TRCENA_POS;
+
+pub const CORE_DEBUG_DEMCR_MON_REQ_POS      : u32   = 19;
+pub const CORE_DEBUG_DEMCR_MON_REQ_MSK      : u32   = 1 << CORE_DEBUG_DEMCR_MON_REQ_POS;
+
+pub const CORE_DEBUG_DEMCR_MON_STEP_POS     : u32   = 18;
+pub const CORE_DEBUG_DEMCR_MON_STEP_MSK     : u32   = 1 << CORE_DEBUG_DEMCR_MON_STEP_POS;
+
+pub const CORE_DEBUG_DEMCR_MON_PEND_POS     : u32   = 17;
+pub const CORE_DEBUG_DEMCR_MON_PEND_MSK     : u32   = 1 << CORE_DEBUG_DEMCR_MON_PEND_POS;
+
+pub const CORE_DEBUG_DEMCR_MON_EN_POS       : u32   = 16;
+pub const CORE_DEBUG_DEMCR_MON_EN_MSK       : u32   = 1 << CORE_DEBUG_DEMCR_MON_EN_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_HARDERR_POS   : u32   = 10;
+pub const CORE_DEBUG_DEMCR_VC_HARDERR_MSK   : u32   = 1 << CORE_DEBUG_DEMCR_VC_HARDERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_INTERR_POS    : u32   = 9;
+pub const CORE_DEBUG_DEMCR_VC_INTERR_MSK    : u32   = 1 << CORE_DEBUG_DEMCR_VC_INTERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_BUSERR_POS    : u32   = 8;
+pub const CORE_DEBUG_DEMCR_VC_BUSERR_MSK    : u32   = 1 << CORE_DEBUG_DEMCR_VC_BUSERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_STATERR_POS   : u32   = 7;
+pub const CORE_DEBUG_DEMCR_VC_STATERR_MSK   : u32   = 1 << CORE_DEBUG_DEMCR_VC_STATERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_CHKERR_POS    : u32   = 6;
+pub const CORE_DEBUG_DEMCR_VC_CHKERR_MSK    : u32   = 1 << CORE_DEBUG_DEMCR_VC_CHKERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_NOCPERR_POS   : u32   = 5;
+pub const CORE_DEBUG_DEMCR_VC_NOCPERR_MSK   : u32   = 1 << CORE_DEBUG_DEMCR_VC_NOCPERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_MMERR_POS     : u32   = 4;
+pub const CORE_DEBUG_DEMCR_VC_MMERR_MSK     : u32   = 1 << CORE_DEBUG_DEMCR_VC_MMERR_POS;
+
+pub const CORE_DEBUG_DEMCR_VC_CORERESET_POS : u32   = 0;
+pub const CORE_DEBUG_DEMCR_VC_CORERESET_MSK : u32   = 1 << CORE_DEBUG_DEMCR_VC_CORERESET_POS;
@@ -6,7 +6,7 @@ use ::volatile_reg32::*;
 use super::super::constants::FPU_BASE;
 
 /// Structure type to access the Floating Point Unit (FPU).
-struct FpuRegs
+pub struct FpuRegs
 {
     /// Floating-Point Context Control Register, Offset: 0x004 (R/W)
     pub fpccr   : VolatileReg32,
@@ -19,8 +19,9 @@ struct FpuRegs
 }
 
 impl FpuRegs {
-    /// Use this constructor method to initialize FPU structure before using it
-    pub fn init() -> FpuRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> FpuRegs {
         let fpu_base: *mut u32 = FPU_BASE as *mut u32;
 
         let fpu = FpuRegs {
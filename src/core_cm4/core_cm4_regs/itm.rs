@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+
+//! Type definitions for the Cortex-M4 Instrumentation Trace Macrocell registers
+
+use ::volatile_reg8::*;
+use ::volatile_reg32::*;
+use super::super::constants::ITM_BASE;
+
+/// Structure type to access the Instrumentation Trace Macrocell Register (ITM).
+pub struct ItmRegs {
+    /// Offset: 0xE00 (R/W)  ITM Trace Enable Register
+    pub ter     : VolatileReg32,
+    /// Offset: 0xE40 (R/W)  ITM Trace Privilege Register
+    pub tpr     : VolatileReg32,
+    /// Offset: 0xE80 (R/W)  ITM Trace Control Register
+    pub tcr     : VolatileReg32,
+    /// Offset: 0xFB0 ( /W)  ITM Lock Access Register
+    pub lar     : WriteOnlyReg32,
+    /// Offset: 0xFB4 (R/ )  ITM Lock Status Register
+    pub lsr     : ReadOnlyReg32,
+}
+
+impl ItmRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> ItmRegs {
+        let itm_base: *mut u32 = ITM_BASE as *mut u32;
+
+        ItmRegs {
+            ter     : VolatileReg32::new_offset(itm_base, 0xE00 / 4),
+            tpr     : VolatileReg32::new_offset(itm_base, 0xE40 / 4),
+            tcr     : VolatileReg32::new_offset(itm_base, 0xE80 / 4),
+            lar     : WriteOnlyReg32::new_offset(itm_base, 0xFB0 / 4),
+            lsr     : ReadOnlyReg32::new_offset(itm_base, 0xFB4 / 4),
+        }
+    }
+
+    /// Offset: 0x000 (R/W)  ITM Stimulus Port Register of `port` (0-31);
+    /// reads 1 while the port can accept data
+    pub fn stim(&self, port: u32) -> VolatileReg32 {
+        VolatileReg32::new((ITM_BASE + 4 * port) as *mut u32)
+    }
+
+    /// Stimulus port register accessed as a byte, sends a single character
+    pub fn stim8(&self, port: u32) -> VolatileReg8 {
+        VolatileReg8::new((ITM_BASE + 4 * port) as *mut u8)
+    }
+}
+
+/// ITM Trace Privilege Register Definitions
+pub const ITM_TPR_PRIVMASK_POS      : u32   = 0;
+pub const ITM_TPR_PRIVMASK_MSK      : u32   = 0xF << ITM_TPR_PRIVMASK_POS;
+
+/// ITM Trace Control Register Definitions
+pub const ITM_TCR_BUSY_POS          : u32   = 23;
+pub const ITM_TCR_BUSY_MSK          : u32   = 1 << ITM_TCR_BUSY_POS;
+
+pub const ITM_TCR_TRACEBUSID_POS    : u32   = 16;
+pub const ITM_TCR_TRACEBUSID_MSK    : u32   = 0x7F << ITM_TCR_TRACEBUSID_POS;
+
+pub const ITM_TCR_GTSFREQ_POS       : u32   = 10;
+pub const ITM_TCR_GTSFREQ_MSK       : u32   = 3 << ITM_TCR_GTSFREQ_POS;
+
+pub const ITM_TCR_TSPRESCALE_POS    : u32   = 8;
+pub const ITM_TCR_TSPRESCALE_MSK    : u32   = 3 << ITM_TCR_TSPRESCALE_POS;
+
+pub const ITM_TCR_SWOENA_POS        : u32   = 4;
+pub const ITM_TCR_SWOENA_MSK        : u32   = 1 << ITM_TCR_SWOENA_POS;
+
+pub const ITM_TCR_DWTENA_POS        : u32   = 3;
+pub const ITM_TCR_DWTENA_MSK        : u32   = 1 << ITM_TCR_DWTENA_POS;
+
+pub const ITM_TCR_SYNCENA_POS       : u32   = 2;
+pub const ITM_TCR_SYNCENA_MSK       : u32   = 1 << ITM_TCR_SYNCENA_POS;
+
+pub const ITM_TCR_TSENA_POS         : u32   = 1;
+pub const ITM_TCR_TSENA_MSK         : u32   = 1 << ITM_TCR_TSENA_POS;
+
+pub const ITM_TCR_ITMENA_POS        : u32   = 0;
+pub const ITM_TCR_ITMENA_MSK        : u32   = 1 << ITM_TCR_ITMENA_POS;
+
+/// ITM Lock Status Register Definitions
+pub const ITM_LSR_BYTEACC_POS       : u32   = 2;
+pub const ITM_LSR_BYTEACC_MSK       : u32   = 1 << ITM_LSR_BYTEACC_POS;
+
+pub const ITM_LSR_ACCESS_POS        : u32   = 1;
+pub const ITM_LSR_ACCESS_MSK        : u32   = 1 << ITM_LSR_ACCESS_POS;
+
+pub const ITM_LSR_PRESENT_POS       : u32   = 0;
+pub const ITM_LSR_PRESENT_MSK       : u32   = 1 << ITM_LSR_PRESENT_POS;
@@ -1,5 +1,6 @@
-
+pub mod core_debug;
 pub mod fpu;
+pub mod itm;
 pub mod mpu;
 pub mod nvic;
 pub mod scb;
@@ -6,9 +6,9 @@ use ::volatile_reg32::*;
 use super::super::constants::MPU_BASE;
 
 /// Structure type to access the Memory Protection Unit (MPU).
-struct MpuRegs {
+pub struct MpuRegs {
     /// MPU Type Register
-    pub mpu_type    : VolatileReg32,
+    pub mpu_type    : ReadOnlyReg32,
     /// MPU Control Register
     pub ctrl        : VolatileReg32,
     /// MPU Region RNRber Register
@@ -32,11 +32,13 @@ struct MpuRegs {
 }
 
 impl MpuRegs {
-    fn init() -> MpuRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> MpuRegs {
         let mpu_base: *mut u32 = MPU_BASE as *mut u32;
 
         let mpu_regs = MpuRegs {
-            mpu_type    : VolatileReg32::new(mpu_base),
+            mpu_type    : ReadOnlyReg32::new(mpu_base),
             ctrl        : VolatileReg32::new_offset(mpu_base, 1),
             rnr         : VolatileReg32::new_offset(mpu_base, 2),
             rbar        : VolatileReg32::new_offset(mpu_base, 3),
@@ -91,6 +93,24 @@ pub const MPU_RBAR_REGION_MSK       : u32   = 0xF << MPU_RBAR_REGION_POS;
 pub const MPU_RASR_ATTRS_POS        : u32   = 16;
 pub const MPU_RASR_ATTRS_MSK        : u32   = 0xFFFF << MPU_RASR_ATTRS_POS;
 
+pub const MPU_RASR_XN_POS           : u32   = 28;
+pub const MPU_RASR_XN_MSK           : u32   = 1 << MPU_RASR_XN_POS;
+
+pub const MPU_RASR_AP_POS           : u32   = 24;
+pub const MPU_RASR_AP_MSK           : u32   = 0x7 << MPU_RASR_AP_POS;
+
+pub const MPU_RASR_TEX_POS          : u32   = 19;
+pub const MPU_RASR_TEX_MSK          : u32   = 0x7 << MPU_RASR_TEX_POS;
+
+pub const MPU_RASR_S_POS            : u32   = 18;
+pub const MPU_RASR_S_MSK            : u32   = 1 << MPU_RASR_S_POS;
+
+pub const MPU_RASR_C_POS            : u32   = 17;
+pub const MPU_RASR_C_MSK            : u32   = 1 << MPU_RASR_C_POS;
+
+pub const MPU_RASR_B_POS            : u32   = 16;
+pub const MPU_RASR_B_MSK            : u32   = 1 << MPU_RASR_B_POS;
+
 pub const MPU_RASR_SRD_POS          : u32   = 8;
 pub const MPU_RASR_SRD_MSK          : u32   = 0xFF << MPU_RASR_SRD_POS;
 
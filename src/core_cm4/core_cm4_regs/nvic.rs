@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
+use ::volatile_reg8::*;
 use ::volatile_reg32::*;
 use super::super::constants::NVIC_BASE;
 
 /// Structure type to access the Nested Vectored Interrupt Controller (NVIC).
-struct NvicRegs {
+pub struct NvicRegs {
     /// Interrupt Set Enable Register
     pub iser    : [VolatileReg32; 3],
     /// Interrupt Clear Enable Register
@@ -15,21 +16,24 @@ struct NvicRegs {
     pub icpr    : [VolatileReg32; 3],
     /// Interrupt Active bit Register
     pub iabr    : [VolatileReg32; 3],
-    /// Interrupt Priority Register (8Bit wide)
+    /// Interrupt Priority Register, four priorities per word (see `ip`)
     pub ipr     : [VolatileReg32; 21],
     /// Software Trigger Interrupt Register
-    pub stir    : VolatileReg32,
+    pub stir    : WriteOnlyReg32,
 }
 
 impl NvicRegs {
-    pub fn init() -> NvicRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> NvicRegs {
+        // Offsets from NVIC_BASE, 0xE000E100, as in the ARMv7-M manual
         let iser_base:  *mut u32 = NVIC_BASE as *mut u32;
-        let icer_base:  *mut u32 = (NVIC_BASE + 0x20) as *mut u32;
-        let ispr_base:  *mut u32 = (NVIC_BASE + 0x40) as *mut u32;
-        let icpr_base:  *mut u32 = (NVIC_BASE + 0x60) as *mut u32;
-        let iabr_base:  *mut u32 = (NVIC_BASE + 0x80) as *mut u32;
-        let ipr_base:   *mut u32 = (NVIC_BASE + 0xC0) as *mut u32;
-        let stir_base:  *mut u32 = (NVIC_BASE + 0x0380) as *mut u32;
+        let icer_base:  *mut u32 = (NVIC_BASE + 0x0080) as *mut u32;
+        let ispr_base:  *mut u32 = (NVIC_BASE + 0x0100) as *mut u32;
+        let icpr_base:  *mut u32 = (NVIC_BASE + 0x0180) as *mut u32;
+        let iabr_base:  *mut u32 = (NVIC_BASE + 0x0200) as *mut u32;
+        let ipr_base:   *mut u32 = (NVIC_BASE + 0x0300) as *mut u32;
+        let stir_base:  *mut u32 = (NVIC_BASE + 0x0E00) as *mut u32;
 
         let nvic_regs = NvicRegs {
             iser:   [
@@ -80,14 +84,58 @@ impl NvicRegs {
                         VolatileReg32::new_offset(ipr_base, 19),
                         VolatileReg32::new_offset(ipr_base, 20),
                     ],
-            stir:   VolatileReg32::new(stir_base),
+            stir:   WriteOnlyReg32::new(stir_base),
         };
 
         nvic_regs
     }
+
+    /// Priority byte of interrupt `irq`; the priority registers are byte
+    /// accessible, so one priority can be set without touching the others
+    pub fn ip(&self, irq: u32) -> VolatileReg8 {
+        VolatileReg8::new((NVIC_BASE + 0x0300 + irq) as *mut u8)
+    }
 }
 
 /// Software Triggered Interrupt Register Definitions
 pub const NVIC_STIR_INTID_POS   : u32   = 0;
 pub const NVIC_STIR_INTID_MSK   : u32   = 0x1FF << NVIC_STIR_INTID_POS;
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+
+    #[test]
+    fn ip_writes_one_priority_byte() {
+        mock_regs::reset();
+        let nvic = unsafe { NvicRegs::init() };
+
+        nvic.ipr[1].set(0x11223344);
+        nvic.ip(6).set(0xA0);
+
+        assert_eq!(nvic.ipr[1].get(), 0x11A03344);
+        assert_eq!(nvic.ip(6).get(), 0xA0);
+    }
+
+    #[test]
+    fn registers_are_at_their_offsets() {
+        mock_regs::reset();
+        let nvic = unsafe { NvicRegs::init() };
+
+        nvic.iser[1].set(1);
+        nvic.icer[1].set(2);
+        nvic.ispr[1].set(3);
+        nvic.icpr[1].set(4);
+        nvic.iabr[1].set(5);
+        nvic.stir.set(6);
+
+        assert_eq!(mock_regs::read(0xE000_E104), 1);
+        assert_eq!(mock_regs::read(0xE000_E184), 2);
+        assert_eq!(mock_regs::read(0xE000_E204), 3);
+        assert_eq!(mock_regs::read(0xE000_E284), 4);
+        assert_eq!(mock_regs::read(0xE000_E304), 5);
+        assert_eq!(mock_regs::read(0xE000_EF00), 6);
+    }
+}
@@ -2,6 +2,7 @@
 
 //! Type definitions for the Cortex-M4 System Control Block registers
 
+use ::volatile_reg8::*;
 use ::volatile_reg32::*;
 use super::super::constants::SCB_BASE;
 
@@ -9,7 +10,7 @@ use super::super::constants::SCB_BASE;
 
 pub struct ScbRegs {
     /// Offset: 0x000 (R/ )  CPUID Base Register
-    pub cpuid   : VolatileReg32,
+    pub cpuid   : ReadOnlyReg32,
     /// Offset: 0x004 (R/W)  Interrupt Control and State Register
     pub icsr    : VolatileReg32,
     /// Offset: 0x008 (R/W)  Vector Table Offset Register
@@ -41,42 +42,44 @@ pub struct ScbRegs {
     /// Offset: 0x03C (R/W)  Auxiliary Fault Status Register
     pub afsr    : VolatileReg32,
     /// Offset: 0x040 (R/ )  Processor Feature Register
-    pub pfr0    : VolatileReg32,
+    pub pfr0    : ReadOnlyReg32,
     /// Offset: 0x040 (R/ )  Processor Feature Register
-    pub pfr1    : VolatileReg32,
+    pub pfr1    : ReadOnlyReg32,
     /// Offset: 0x048 (R/ )  Debug Feature Register
-    pub dfr     : VolatileReg32,
+    pub dfr     : ReadOnlyReg32,
     /// Offset: 0x04C (R/ )  Auxiliary Feature Register
-    pub adr     : VolatileReg32,
+    pub adr     : ReadOnlyReg32,
     /// Offset: 0x050 (R/ )  Memory Model Feature Register
-    pub mmfr0   : VolatileReg32,
+    pub mmfr0   : ReadOnlyReg32,
     /// Offset: 0x050 (R/ )  Memory Model Feature Register
-    pub mmfr1   : VolatileReg32,
+    pub mmfr1   : ReadOnlyReg32,
     /// Offset: 0x050 (R/ )  Memory Model Feature Register
-    pub mmfr2   : VolatileReg32,
+    pub mmfr2   : ReadOnlyReg32,
     /// Offset: 0x050 (R/ )  Memory Model Feature Register
-    pub mmfr3   : VolatileReg32,
+    pub mmfr3   : ReadOnlyReg32,
     /// Offset: 0x060 (R/ )  Instruction Set Attributes Register
-    pub isar0   : VolatileReg32,
+    pub isar0   : ReadOnlyReg32,
     /// Offset: 0x060 (R/ )  Instruction Set Attributes Register
-    pub isar1   : VolatileReg32,
+    pub isar1   : ReadOnlyReg32,
     /// Offset: 0x060 (R/ )  Instruction Set Attributes Register
-    pub isar2   : VolatileReg32,
+    pub isar2   : ReadOnlyReg32,
     /// Offset: 0x060 (R/ )  Instruction Set Attributes Register
-    pub isar3   : VolatileReg32,
+    pub isar3   : ReadOnlyReg32,
     /// Offset: 0x060 (R/ )  Instruction Set Attributes Register
-    pub isar4   : VolatileReg32,
+    pub isar4   : ReadOnlyReg32,
 
     /// Offset: 0x088 (R/W)  Coprocessor Access Control Register
     pub cpacr   : VolatileReg32,
 }
 
 impl ScbRegs {
-    pub fn init() -> ScbRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> ScbRegs {
         let scb_base: *mut u32 = SCB_BASE as *mut u32;
 
         let scb = ScbRegs {
-            cpuid   : VolatileReg32::new(scb_base),
+            cpuid   : ReadOnlyReg32::new(scb_base),
             icsr    : VolatileReg32::new_offset(scb_base, 1),
             vtor    : VolatileReg32::new_offset(scb_base, 2),
             aircr   : VolatileReg32::new_offset(scb_base, 3),
@@ -92,25 +95,32 @@ impl ScbRegs {
             mmfar   : VolatileReg32::new_offset(scb_base, 13),
             bfar    : VolatileReg32::new_offset(scb_base, 14),
             afsr    : VolatileReg32::new_offset(scb_base, 15),
-            pfr0    : VolatileReg32::new_offset(scb_base, 16),
-            pfr1    : VolatileReg32::new_offset(scb_base, 17),
-            dfr     : VolatileReg32::new_offset(scb_base, 18),
-            adr     : VolatileReg32::new_offset(scb_base, 19),
-            mmfr0   : VolatileReg32::new_offset(scb_base, 20),
-            mmfr1   : VolatileReg32::new_offset(scb_base, 21),
-            mmfr2   : VolatileReg32::new_offset(scb_base, 22),
-            mmfr3   : VolatileReg32::new_offset(scb_base, 23),
-            isar0   : VolatileReg32::new_offset(scb_base, 24),
-            isar1   : VolatileReg32::new_offset(scb_base, 25),
-            isar2   : VolatileReg32::new_offset(scb_base, 26),
-            isar3   : VolatileReg32::new_offset(scb_base, 27),
-            isar4   : VolatileReg32::new_offset(scb_base, 28),
+            pfr0    : ReadOnlyReg32::new_offset(scb_base, 16),
+            pfr1    : ReadOnlyReg32::new_offset(scb_base, 17),
+            dfr     : ReadOnlyReg32::new_offset(scb_base, 18),
+            adr     : ReadOnlyReg32::new_offset(scb_base, 19),
+            mmfr0   : ReadOnlyReg32::new_offset(scb_base, 20),
+            mmfr1   : ReadOnlyReg32::new_offset(scb_base, 21),
+            mmfr2   : ReadOnlyReg32::new_offset(scb_base, 22),
+            mmfr3   : ReadOnlyReg32::new_offset(scb_base, 23),
+            isar0   : ReadOnlyReg32::new_offset(scb_base, 24),
+            isar1   : ReadOnlyReg32::new_offset(scb_base, 25),
+            isar2   : ReadOnlyReg32::new_offset(scb_base, 26),
+            isar3   : ReadOnlyReg32::new_offset(scb_base, 27),
+            isar4   : ReadOnlyReg32::new_offset(scb_base, 28),
 
             cpacr   : VolatileReg32::new_offset(scb_base, 34),
         };
 
         scb
     }
+
+    /// Priority byte of system handler `exception` (4 = MemManage .. 15 = SysTick),
+    /// inside the SHPR1..3 registers
+    pub fn shp(&self, exception: u32) -> VolatileReg8 {
+        debug_assert!(exception >= 4 && exception <= 15);
+        VolatileReg8::new((SCB_BASE + 0x18 + exception - 4) as *mut u8)
+    }
 }
 
 /// SCB CPUID Register Definitions
@@ -268,6 +278,66 @@ pub const SCB_CFSR_BUSFAULTSR_MSK       : u32   = 0xFF << SCB_CFSR_BUSFAULTSR_PO
 pub const SCB_CFSR_MEMFAULTSR_POS       : u32   = 0;
 pub const SCB_CFSR_MEMFAULTSR_MSK       : u32   = 0xFF << SCB_CFSR_MEMFAULTSR_POS;
 
+/// MemManage Fault Status Register (part of SCB Configurable Fault Status Register)
+pub const SCB_CFSR_MMARVALID_POS        : u32   = 7;
+pub const SCB_CFSR_MMARVALID_MSK        : u32   = 1 << SCB_CFSR_MMARVALID_POS;
+
+pub const SCB_CFSR_MLSPERR_POS          : u32   = 5;
+pub const SCB_CFSR_MLSPERR_MSK          : u32   = 1 << SCB_CFSR_MLSPERR_POS;
+
+pub const SCB_CFSR_MSTKERR_POS          : u32   = 4;
+pub const SCB_CFSR_MSTKERR_MSK          : u32   = 1 << SCB_CFSR_MSTKERR_POS;
+
+pub const SCB_CFSR_MUNSTKERR_POS        : u32   = 3;
+pub const SCB_CFSR_MUNSTKERR_MSK        : u32   = 1 << SCB_CFSR_MUNSTKERR_POS;
+
+pub const SCB_CFSR_DACCVIOL_POS         : u32   = 1;
+pub const SCB_CFSR_DACCVIOL_MSK         : u32   = 1 << SCB_CFSR_DACCVIOL_POS;
+
+pub const SCB_CFSR_IACCVIOL_POS         : u32   = 0;
+pub const SCB_CFSR_IACCVIOL_MSK         : u32   = 1 << SCB_CFSR_IACCVIOL_POS;
+
+/// BusFault Status Register (part of SCB Configurable Fault Status Register)
+pub const SCB_CFSR_BFARVALID_POS        : u32   = 15;
+pub const SCB_CFSR_BFARVALID_MSK        : u32   = 1 << SCB_CFSR_BFARVALID_POS;
+
+pub const SCB_CFSR_LSPERR_POS           : u32   = 13;
+pub const SCB_CFSR_LSPERR_MSK           : u32   = 1 << SCB_CFSR_LSPERR_POS;
+
+pub const SCB_CFSR_STKERR_POS           : u32   = 12;
+pub const SCB_CFSR_STKERR_MSK           : u32   = 1 << SCB_CFSR_STKERR_POS;
+
+pub const SCB_CFSR_UNSTKERR_POS         : u32   = 11;
+pub const SCB_CFSR_UNSTKERR_MSK         : u32   = 1 << SCB_CFSR_UNSTKERR_POS;
+
+pub const SCB_CFSR_IMPRECISERR_POS      : u32   = 10;
+pub const SCB_CFSR_IMPRECISERR_MSK      : u32   = 1 << SCB_CFSR_IMPRECISERR_POS;
+
+pub const SCB_CFSR_PRECISERR_POS        : u32   = 9;
+pub const SCB_CFSR_PRECISERR_MSK        : u32   = 1 << SCB_CFSR_PRECISERR_POS;
+
+pub const SCB_CFSR_IBUSERR_POS          : u32   = 8;
+pub const SCB_CFSR_IBUSERR_MSK          : u32   = 1 << SCB_CFSR_IBUSERR_POS;
+
+/// UsageFault Status Register (part of SCB Configurable Fault Status Register)
+pub const SCB_CFSR_DIVBYZERO_POS        : u32   = 25;
+pub const SCB_CFSR_DIVBYZERO_MSK        : u32   = 1 << SCB_CFSR_DIVBYZERO_POS;
+
+pub const SCB_CFSR_UNALIGNED_POS        : u32   = 24;
+pub const SCB_CFSR_UNALIGNED_MSK        : u32   = 1 << SCB_CFSR_UNALIGNED_POS;
+
+pub const SCB_CFSR_NOCP_POS             : u32   = 19;
+pub const SCB_CFSR_NOCP_MSK             : u32   = 1 << SCB_CFSR_NOCP_POS;
+
+pub const SCB_CFSR_INVPC_POS            : u32   = 18;
+pub const SCB_CFSR_INVPC_MSK            : u32   = 1 << SCB_CFSR_INVPC_POS;
+
+pub const SCB_CFSR_INVSTATE_POS         : u32   = 17;
+pub const SCB_CFSR_INVSTATE_MSK         : u32   = 1 << SCB_CFSR_INVSTATE_POS;
+
+pub const SCB_CFSR_UNDEFINSTR_POS       : u32   = 16;
+pub const SCB_CFSR_UNDEFINSTR_MSK       : u32   = 1 << SCB_CFSR_UNDEFINSTR_POS;
+
 /// SCB Hard Fault Status Registers Definitions
 pub const SCB_HFSR_DEBUGEVT_POS         : u32   = 31;
 pub const SCB_HFSR_DEBUGEVT_MSK         : u32   = 1 << SCB_HFSR_DEBUGEVT_POS;
@@ -294,3 +364,129 @@ pub const SCB_DFSR_BKPT_MSK             : u32   = 1 << SCB_DFSR_BKPT_POS;
 pub const SCB_DFSR_HALTED_POS           : u32   = 0;
 pub const SCB_DFSR_HALTED_MSK           : u32   = 1 << SCB_DFSR_HALTED_POS;
 
+
+/// Typed fields of the SCB CPUID register
+pub mod cpuid {
+    field_bits! { /// Implementer code
+                  pub struct Implementer [24, 8]; }
+    field_bits! { /// Variant number
+                  pub struct Variant [20, 4]; }
+    field_bits! { /// Architecture
+                  pub struct Architecture [16, 4]; }
+    field_bits! { /// Part number of the processor
+                  pub struct PartNo [4, 12]; }
+    field_bits! { /// Revision number
+                  pub struct Revision [0, 4]; }
+}
+
+/// Typed fields of the SCB Interrupt Control State Register
+pub mod icsr {
+    field_flag! { /// NMI set-pending
+                  pub struct NmiPendSet [31]; }
+    field_flag! { /// PendSV set-pending
+                  pub struct PendSvSet [28]; }
+    field_flag! { /// PendSV clear-pending
+                  pub struct PendSvClr [27]; }
+    field_flag! { /// SysTick exception set-pending
+                  pub struct PendStSet [26]; }
+    field_flag! { /// SysTick exception clear-pending
+                  pub struct PendStClr [25]; }
+    field_bits! { /// Active exception number
+                  pub struct VectActive [0, 9]; }
+}
+
+/// Typed fields of the SCB Vector Table Offset Register
+pub mod vtor {
+    field_bits! { /// Vector table base offset, in units of 128 bytes
+                  pub struct TblOff [7, 25]; }
+}
+
+/// Typed fields of the SCB Application Interrupt and Reset Control Register
+pub mod aircr {
+    field_bits! { /// Register key, 0x05FA on write
+                  pub struct VectKey [16, 16]; }
+    field_bits! { /// Interrupt priority grouping
+                  pub struct PriGroup [8, 3]; }
+    field_flag! { /// System reset request
+                  pub struct SysResetReq [2]; }
+    field_flag! { /// Clear active vector bit
+                  pub struct VectClrActive [1]; }
+    field_flag! { /// Local system reset
+                  pub struct VectReset [0]; }
+}
+
+/// Typed fields of the SCB System Control Register
+pub mod scr {
+    field_flag! { /// Send event on pending bit
+                  pub struct SevOnPend [4]; }
+    field_flag! { /// Deep sleep as low power mode
+                  pub struct SleepDeep [2]; }
+    field_flag! { /// Sleep on return to Thread mode
+                  pub struct SleepOnExit [1]; }
+}
+
+/// Typed fields of the SCB Configuration Control Register
+pub mod ccr {
+    field_flag! { /// 8-byte stack frame alignment on exception entry
+                  pub struct StkAlign [9]; }
+    field_flag! { /// Ignore precise data access faults in NMI/HardFault handlers
+                  pub struct BfhfNmIgn [8]; }
+    field_flag! { /// Trap on divide by zero
+                  pub struct Div0Trp [4]; }
+    field_flag! { /// Trap on unaligned access
+                  pub struct UnalignTrp [3]; }
+    field_flag! { /// Allow unprivileged access to STIR
+                  pub struct UserSetMPend [1]; }
+    field_flag! { /// Allow entering Thread mode with active exceptions
+                  pub struct NonBaseThrdEna [0]; }
+}
+
+/// Typed fields of the SCB System Handler Control and State Register
+pub mod shcsr {
+    field_flag! { /// UsageFault enable
+                  pub struct UsgFaultEna [18]; }
+    field_flag! { /// BusFault enable
+                  pub struct BusFaultEna [17]; }
+    field_flag! { /// MemManage enable
+                  pub struct MemFaultEna [16]; }
+}
+
+/// Typed fields of the SCB Configurable Fault Status Register
+pub mod cfsr {
+    field_bits! { /// UsageFault Status Register
+                  pub struct UsgFaultSr [16, 16]; }
+    field_bits! { /// BusFault Status Register
+                  pub struct BusFaultSr [8, 8]; }
+    field_bits! { /// MemManage Fault Status Register
+                  pub struct MemFaultSr [0, 8]; }
+}
+
+/// Typed fields of the SCB HardFault Status Register
+pub mod hfsr {
+    field_flag! { /// Debug event
+                  pub struct DebugEvt [31]; }
+    field_flag! { /// Forced HardFault
+                  pub struct Forced [30]; }
+    field_flag! { /// Vector table read fault
+                  pub struct VectTbl [1]; }
+}
+
+/// Typed fields of the SCB Coprocessor Access Control Register
+pub mod cpacr {
+    field_enum! {
+        /// Access privileges for coprocessor 10
+        pub enum Cp10 [20, 2] {
+            Denied      = 0,
+            Privileged  = 1,
+            Full        = 3,
+        }
+    }
+    field_enum! {
+        /// Access privileges for coprocessor 11
+        pub enum Cp11 [22, 2] {
+            Denied      = 0,
+            Privileged  = 1,
+            Full        = 3,
+        }
+    }
+}
@@ -6,17 +6,19 @@ use super::super::constants::SCS_BASE;
 /// Structure type to access the System Control and ID Register not in the SCB.
 pub struct ScNScbRegs {
     /// Offset: 0x004 (R/ )  Interrupt Controller Type Register
-    pub ictr    : VolatileReg32,
+    pub ictr    : ReadOnlyReg32,
     /// Offset: 0x008 (R/W)  Auxiliary Control Register
     pub actlr   : VolatileReg32,
 }
 
 impl ScNScbRegs {
-    pub fn init() -> ScNScbRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> ScNScbRegs {
         let scnscb_base: *mut u32 = SCS_BASE as *mut u32;
 
         let scnscb = ScNScbRegs {
-            ictr    : VolatileReg32::new_offset(scnscb_base, 1),
+            ictr    : ReadOnlyReg32::new_offset(scnscb_base, 1),
             actlr   : VolatileReg32::new_offset(scnscb_base, 2),
         };
 
@@ -4,7 +4,7 @@ use ::volatile_reg32::*;
 use super::super::constants::SYS_TICK_BASE;
 
 /// Structure type to access the System Timer (SysTick).
-struct SysTickRegs {
+pub struct SysTickRegs {
     /// SysTick Control and Status Register
     pub ctrl    : VolatileReg32,
     /// SysTick Reload Value Register
@@ -12,18 +12,20 @@ struct SysTickRegs {
     /// SysTick Current Value Register
     pub val     : VolatileReg32,
     /// SysTick Calibration Register
-    pub calib   : VolatileReg32,
+    pub calib   : ReadOnlyReg32,
 }
 
 impl SysTickRegs {
-    pub fn init() -> SysTickRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> SysTickRegs {
         let sys_tick_base: *mut u32 = SYS_TICK_BASE as *mut u32;
 
         let sys_tick_regs = SysTickRegs {
             ctrl    : VolatileReg32::new(sys_tick_base),
             load    : VolatileReg32::new_offset(sys_tick_base, 1),
             val     : VolatileReg32::new_offset(sys_tick_base, 2),
-            calib   : VolatileReg32::new_offset(sys_tick_base, 3),
+            calib   : ReadOnlyReg32::new_offset(sys_tick_base, 3),
         };
 
         sys_tick_regs
@@ -0,0 +1,111 @@
+#![allow(dead_code)]
+
+//! Cortex-M4 system exceptions and the core part of the vector table
+//!
+//! Like the device interrupts, every exception is routed to a symbol of its
+//! own name (`SysTick`, `PendSV`, ...) which defaults to `DefaultHandler`
+//! in the linker script and can be defined with `exception!`. HardFault,
+//! MemManage, BusFault and UsageFault are defined by `faults.rs`.
+
+/// Vector table entry, `None` for reserved slots
+pub type Vector = Option<unsafe extern "C" fn()>;
+
+/// System exceptions, numbered as in the vector table
+// Named like the handler symbols of the vector table
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Exception {
+    NMI             = 2,
+    HardFault       = 3,
+    MemManage       = 4,
+    BusFault        = 5,
+    UsageFault      = 6,
+    SVCall          = 11,
+    DebugMonitor    = 12,
+    PendSV          = 14,
+    SysTick         = 15,
+}
+
+impl Exception {
+    /// The exception with vector table number `number`, as read from IPSR
+    pub fn from_number(number: u32) -> Option<Exception> {
+        match number {
+            2   => Some(Exception::NMI),
+            3   => Some(Exception::HardFault),
+            4   => Some(Exception::MemManage),
+            5   => Some(Exception::BusFault),
+            6   => Some(Exception::UsageFault),
+            11  => Some(Exception::SVCall),
+            12  => Some(Exception::DebugMonitor),
+            14  => Some(Exception::PendSV),
+            15  => Some(Exception::SysTick),
+            _   => None,
+        }
+    }
+}
+
+/// Number of the exception being handled (IPSR), 0 in thread mode and
+/// 16 + n for device interrupt n
+#[cfg(target_arch = "arm")]
+pub fn active() -> u32 {
+    let ipsr: u32;
+    unsafe { asm!("mrs $0, IPSR" : "=r"(ipsr) ::: "volatile"); }
+    ipsr & 0x1FF
+}
+
+#[cfg(not(target_arch = "arm"))]
+pub fn active() -> u32 {
+    0
+}
+
+#[cfg(not(test))]
+extern "C" {
+    fn NMI();
+    fn HardFault();
+    fn MemManage();
+    fn BusFault();
+    fn UsageFault();
+    fn SVCall();
+    fn DebugMonitor();
+    fn PendSV();
+    fn SysTick();
+}
+
+/// Exception vectors 2..15, placed after the initial stack pointer and the
+/// reset vector
+#[cfg(not(test))]
+#[link_section = ".isr_vector.exceptions"]
+#[no_mangle]
+pub static EXCEPTIONS: [Vector; 14] = [
+    Some(NMI),
+    Some(HardFault),
+    Some(MemManage),
+    Some(BusFault),
+    Some(UsageFault),
+    None,
+    None,
+    None,
+    None,
+    Some(SVCall),
+    Some(DebugMonitor),
+    None,
+    Some(PendSV),
+    Some(SysTick),
+];
+
+/// Binds a function to a system exception; an unknown exception name is a
+/// compile error
+///
+/// exception!(SysTick, on_tick);
+#[allow(unused_macros)]
+macro_rules! exception {
+    ($name:ident, $handler:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub extern "C" fn $name() {
+            let _ = $crate::core_cm4::exceptions::Exception::$name;
+            let handler: fn() = $handler;
+            handler();
+        }
+    }
+}
@@ -0,0 +1,291 @@
+#![allow(dead_code)]
+
+//! Decoding of HardFault, MemManage, BusFault and UsageFault
+//!
+//! `FaultReport` combines the registers the core stacked on exception entry
+//! with the fault status registers of the SCB.
+
+use core::fmt;
+
+use ::core_cm4::core_cm4_regs::scb::*;
+use ::core_cm4::exceptions::Exception;
+
+/// Registers pushed on the stack by the core on exception entry
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct ExceptionFrame {
+    pub r0      : u32,
+    pub r1      : u32,
+    pub r2      : u32,
+    pub r3      : u32,
+    pub r12     : u32,
+    pub lr      : u32,
+    pub pc      : u32,
+    pub xpsr    : u32,
+}
+
+/// One fault status bit of HFSR or CFSR
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FaultCause {
+    // HardFault Status Register
+    VectorTableRead,
+    Forced,
+    DebugEvent,
+
+    // MemManage Fault Status Register
+    InstructionAccessViolation,
+    DataAccessViolation,
+    MemManageUnstacking,
+    MemManageStacking,
+    MemManageLazyFpState,
+
+    // BusFault Status Register
+    InstructionBusError,
+    PreciseDataBusError,
+    ImpreciseDataBusError,
+    BusUnstacking,
+    BusStacking,
+    BusLazyFpState,
+
+    // UsageFault Status Register
+    UndefinedInstruction,
+    InvalidState,
+    InvalidPc,
+    NoCoprocessor,
+    Unaligned,
+    DivideByZero,
+}
+
+const HFSR_CAUSES: [(u32, FaultCause); 3] = [
+    (SCB_HFSR_VECTTBL_MSK,      FaultCause::VectorTableRead),
+    (SCB_HFSR_FORCED_MSK,       FaultCause::Forced),
+    (SCB_HFSR_DEBUGEVT_MSK,     FaultCause::DebugEvent),
+];
+
+const CFSR_CAUSES: [(u32, FaultCause); 17] = [
+    (SCB_CFSR_IACCVIOL_MSK,     FaultCause::InstructionAccessViolation),
+    (SCB_CFSR_DACCVIOL_MSK,     FaultCause::DataAccessViolation),
+    (SCB_CFSR_MUNSTKERR_MSK,    FaultCause::MemManageUnstacking),
+    (SCB_CFSR_MSTKERR_MSK,      FaultCause::MemManageStacking),
+    (SCB_CFSR_MLSPERR_MSK,      FaultCause::MemManageLazyFpState),
+    (SCB_CFSR_IBUSERR_MSK,      FaultCause::InstructionBusError),
+    (SCB_CFSR_PRECISERR_MSK,    FaultCause::PreciseDataBusError),
+    (SCB_CFSR_IMPRECISERR_MSK,  FaultCause::ImpreciseDataBusError),
+    (SCB_CFSR_UNSTKERR_MSK,     FaultCause::BusUnstacking),
+    (SCB_CFSR_STKERR_MSK,       FaultCause::BusStacking),
+    (SCB_CFSR_LSPERR_MSK,       FaultCause::BusLazyFpState),
+    (SCB_CFSR_UNDEFINSTR_MSK,   FaultCause::UndefinedInstruction),
+    (SCB_CFSR_INVSTATE_MSK,     FaultCause::InvalidState),
+    (SCB_CFSR_INVPC_MSK,        FaultCause::InvalidPc),
+    (SCB_CFSR_NOCP_MSK,         FaultCause::NoCoprocessor),
+    (SCB_CFSR_UNALIGNED_MSK,    FaultCause::Unaligned),
+    (SCB_CFSR_DIVBYZERO_MSK,    FaultCause::DivideByZero),
+];
+
+impl FaultCause {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            FaultCause::VectorTableRead             => "bus fault on vector table read",
+            FaultCause::Forced                      => "escalated to HardFault",
+            FaultCause::DebugEvent                  => "debug event",
+            FaultCause::InstructionAccessViolation  => "instruction access violation",
+            FaultCause::DataAccessViolation         => "data access violation",
+            FaultCause::MemManageUnstacking         => "MemManage fault on unstacking",
+            FaultCause::MemManageStacking           => "MemManage fault on stacking",
+            FaultCause::MemManageLazyFpState        => "MemManage fault on lazy FP state preservation",
+            FaultCause::InstructionBusError         => "instruction bus error",
+            FaultCause::PreciseDataBusError         => "precise data bus error",
+            FaultCause::ImpreciseDataBusError       => "imprecise data bus error",
+            FaultCause::BusUnstacking               => "bus fault on unstacking",
+            FaultCause::BusStacking                 => "bus fault on stacking",
+            FaultCause::BusLazyFpState              => "bus fault on lazy FP state preservation",
+            FaultCause::UndefinedInstruction        => "undefined instruction",
+            FaultCause::InvalidState                => "invalid EPSR state",
+            FaultCause::InvalidPc                   => "invalid EXC_RETURN on PC load",
+            FaultCause::NoCoprocessor               => "coprocessor not available",
+            FaultCause::Unaligned                   => "unaligned access",
+            FaultCause::DivideByZero                => "divide by zero",
+        }
+    }
+}
+
+/// Everything known about a fault
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FaultReport {
+    pub exception   : Exception,
+    pub frame       : ExceptionFrame,
+    /// Configurable Fault Status Register
+    pub cfsr        : u32,
+    /// HardFault Status Register
+    pub hfsr        : u32,
+    /// Faulting address of a data access violation, if MMARVALID
+    pub mmfar       : Option<u32>,
+    /// Faulting address of a precise data bus error, if BFARVALID
+    pub bfar        : Option<u32>,
+}
+
+impl FaultReport {
+    /// Builds a report from raw register values; the fault addresses are
+    /// kept only if CFSR marks them valid
+    pub fn new(exception: Exception, frame: ExceptionFrame,
+               cfsr: u32, hfsr: u32, mmfar: u32, bfar: u32) -> FaultReport {
+        FaultReport {
+            exception,
+            frame,
+            cfsr,
+            hfsr,
+            mmfar       : if cfsr & SCB_CFSR_MMARVALID_MSK != 0 { Some(mmfar) } else { None },
+            bfar        : if cfsr & SCB_CFSR_BFARVALID_MSK != 0 { Some(bfar) } else { None },
+        }
+    }
+
+    /// Reads the fault status registers for the fault being handled
+    pub fn capture(exception: Exception, frame: &ExceptionFrame) -> FaultReport {
+        // Only read, the fault handler is the last code to run
+        let scb = unsafe { ScbRegs::init() };
+
+        FaultReport::new(exception, *frame,
+                         scb.cfsr.get(), scb.hfsr.get(), scb.mmfar.get(), scb.bfar.get())
+    }
+
+    /// The status bits set in HFSR and CFSR
+    pub fn causes(&self) -> Causes {
+        Causes {
+            hfsr    : self.hfsr,
+            cfsr    : self.cfsr,
+            index   : 0,
+        }
+    }
+
+    pub fn has(&self, cause: FaultCause) -> bool {
+        self.causes().any(|c| c == cause)
+    }
+
+    pub fn summary(&self) -> Summary<'_> {
+        Summary(self)
+    }
+
+    /// True if the fault was raised while pushing or popping the exception
+    /// frame, in which case the frame may be garbage
+    pub fn stacking_error(&self) -> bool {
+        self.causes().any(|c| matches!(c,
+            FaultCause::MemManageStacking | FaultCause::MemManageUnstacking |
+            FaultCause::BusStacking | FaultCause::BusUnstacking))
+    }
+}
+
+/// Iterator over the causes of a fault, HFSR first
+pub struct Causes {
+    hfsr    : u32,
+    cfsr    : u32,
+    index   : usize,
+}
+
+impl Iterator for Causes {
+    type Item = FaultCause;
+
+    fn next(&mut self) -> Option<FaultCause> {
+        while self.index < HFSR_CAUSES.len() + CFSR_CAUSES.len() {
+            let (value, (mask, cause)) = if self.index < HFSR_CAUSES.len() {
+                (self.hfsr, HFSR_CAUSES[self.index])
+            } else {
+                (self.cfsr, CFSR_CAUSES[self.index - HFSR_CAUSES.len()])
+            };
+            self.index += 1;
+
+            if value & mask != 0 {
+                return Some(cause);
+            }
+        }
+        None
+    }
+}
+
+/// First line of a fault report: the exception and its causes
+pub struct Summary<'a>(&'a FaultReport);
+
+impl<'a> fmt::Display for Summary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let report = self.0;
+        write!(f, "{:?}:", report.exception)?;
+        for (i, cause) in report.causes().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { "," }, cause.description())?;
+            match (cause, report.mmfar, report.bfar) {
+                (FaultCause::DataAccessViolation, Some(address), _) |
+                (FaultCause::PreciseDataBusError, _, Some(address)) =>
+                    write!(f, " at 0x{:08X}", address)?,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FaultReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.summary())?;
+
+        let r = &self.frame;
+        write!(f, "\r\n  r0 = 0x{:08X}  r1 = 0x{:08X}  r2 = 0x{:08X}  r3 = 0x{:08X}",
+               r.r0, r.r1, r.r2, r.r3)?;
+        write!(f, "\r\n r12 = 0x{:08X}  lr = 0x{:08X}  pc = 0x{:08X} xpsr = 0x{:08X}",
+               r.r12, r.lr, r.pc, r.xpsr)?;
+        write!(f, "\r\ncfsr = 0x{:08X} hfsr = 0x{:08X}", self.cfsr, self.hfsr)
+    }
+}
+
+/// Routes MemManage, BusFault and UsageFault to their own handlers instead
+/// of HardFault and traps integer division by zero
+pub fn enable_fault_handlers(scb: &ScbRegs) {
+    scb.shcsr.bit_or(SCB_SHCSR_MEMFAULTENA_MSK | SCB_SHCSR_BUSFAULTENA_MSK | SCB_SHCSR_USGFAULTENA_MSK);
+    scb.ccr.bit_or(SCB_CCR_DIV_0_TRP_MSK);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(exception: Exception, cfsr: u32, hfsr: u32) -> FaultReport {
+        let frame = ExceptionFrame { pc: 0x08000123, lr: 0x08000101, ..Default::default() };
+        FaultReport::new(exception, frame, cfsr, hfsr, 0x20001000, 0x40023C00)
+    }
+
+    #[test]
+    fn precise_bus_fault_keeps_address() {
+        let r = report(Exception::BusFault, SCB_CFSR_PRECISERR_MSK | SCB_CFSR_BFARVALID_MSK, 0);
+
+        assert_eq!(r.causes().collect::<Vec<_>>(), [FaultCause::PreciseDataBusError]);
+        assert_eq!(r.bfar, Some(0x40023C00));
+        assert_eq!(r.mmfar, None);
+        assert!(!r.stacking_error());
+    }
+
+    #[test]
+    fn imprecise_bus_fault_has_no_address() {
+        let r = report(Exception::BusFault, SCB_CFSR_IMPRECISERR_MSK | SCB_CFSR_STKERR_MSK, 0);
+
+        assert!(r.has(FaultCause::ImpreciseDataBusError));
+        assert!(!r.has(FaultCause::PreciseDataBusError));
+        assert_eq!(r.bfar, None);
+        assert!(r.stacking_error());
+    }
+
+    #[test]
+    fn forced_hard_fault_lists_escalated_cause() {
+        let r = report(Exception::HardFault, SCB_CFSR_DIVBYZERO_MSK | SCB_CFSR_UNDEFINSTR_MSK,
+                       SCB_HFSR_FORCED_MSK);
+
+        assert_eq!(r.causes().collect::<Vec<_>>(),
+                   [FaultCause::Forced, FaultCause::UndefinedInstruction, FaultCause::DivideByZero]);
+    }
+
+    #[test]
+    fn display_names_causes_and_registers() {
+        let r = report(Exception::MemManage, SCB_CFSR_DACCVIOL_MSK | SCB_CFSR_MMARVALID_MSK, 0);
+        let text = format!("{}", r);
+
+        assert!(text.starts_with("MemManage: data access violation at 0x20001000\r\n"));
+        assert!(text.contains("pc = 0x08000123"));
+        assert!(text.ends_with("cfsr = 0x00000082 hfsr = 0x00000000"));
+    }
+}
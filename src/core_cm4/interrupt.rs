@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+//! Global interrupt masking through PRIMASK
+
+/// Runs `f` with all configurable interrupts masked and restores the previous
+/// mask afterwards, so critical sections can nest
+pub fn free<F, R>(f: F) -> R
+    where F: FnOnce() -> R
+{
+    let primask = primask();
+    disable();
+
+    let result = f();
+
+    // Only unmask if interrupts were enabled on entry
+    if primask & 1 == 0 {
+        unsafe { enable(); }
+    }
+
+    result
+}
+
+/// Masks all configurable interrupts
+#[cfg(target_arch = "arm")]
+pub fn disable() {
+    unsafe { asm!("cpsid i" :::: "volatile"); }
+}
+
+/// Unmasks interrupts; unsafe because it ends any critical section the
+/// caller may be in
+#[cfg(target_arch = "arm")]
+pub unsafe fn enable() {
+    asm!("cpsie i" :::: "volatile");
+}
+
+/// Current PRIMASK value, bit 0 set while interrupts are masked
+#[cfg(target_arch = "arm")]
+pub fn primask() -> u32 {
+    let primask: u32;
+    unsafe { asm!("mrs $0, PRIMASK" : "=r"(primask) ::: "volatile"); }
+    primask
+}
+
+// Host builds (unit tests) have no interrupts to mask
+#[cfg(not(target_arch = "arm"))]
+pub fn disable() {}
+
+#[cfg(not(target_arch = "arm"))]
+pub unsafe fn enable() {}
+
+#[cfg(not(target_arch = "arm"))]
+pub fn primask() -> u32 {
+    0
+}
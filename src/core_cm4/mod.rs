@@ -1,3 +1,8 @@
-
+pub mod barrier;
 pub mod constants;
 pub mod core_cm4_regs;
+#[macro_use]
+pub mod exceptions;
+pub mod fault;
+pub mod interrupt;
+pub mod reset;
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+//! System reset through the Application Interrupt and Reset Control Register
+
+use ::core_cm4::barrier::dsb;
+use ::core_cm4::core_cm4_regs::scb::*;
+
+/// Key that has to accompany every write to AIRCR
+pub const AIRCR_VECTKEY : u32   = 0x05FA;
+
+/// Resets the whole chip like CMSIS `NVIC_SystemReset`, the priority
+/// grouping is kept
+pub fn system_reset() -> ! {
+    request_reset();
+
+    // Wait until the reset takes effect
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+fn request_reset() {
+    // Whoever owns the SCB is about to be reset with the rest of the chip
+    let scb = unsafe { ScbRegs::init() };
+
+    // Let outstanding memory accesses complete first
+    dsb();
+    let prigroup = scb.aircr.get() & SCB_AIRCR_PRIGROUP_MSK;
+    scb.aircr.set((AIRCR_VECTKEY << SCB_AIRCR_VECTKEY_POS) | prigroup | SCB_AIRCR_SYSRESETREQ_MSK);
+    dsb();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::core_cm4::constants::SCB_BASE;
+
+    const AIRCR     : u32   = SCB_BASE + 0x0C;
+
+    #[test]
+    fn reset_request_keeps_priority_grouping() {
+        // Reads return VECTKEYSTAT in the upper half word
+        mock_regs::write(AIRCR, 0xFA05_0000 | (5 << SCB_AIRCR_PRIGROUP_POS));
+
+        request_reset();
+
+        assert_eq!(mock_regs::read(AIRCR), 0x05FA_0504);
+    }
+}
@@ -0,0 +1,519 @@
+#![allow(dead_code)]
+
+//! Post-mortem crash dump kept in backup SRAM across resets
+//!
+//! With the `crash-dump` feature the panic and fault handlers record a short
+//! reason, the stacked registers, the fault status and the top of the stack
+//! in backup SRAM before taking their action. Backup SRAM survives resets,
+//! and power loss too while VBAT is supplied.
+//!
+//! On the next boot `BackupSram::enable` gives access to the dump, which can
+//! be printed as a hex dump, exported as an ELF core file for GDB and
+//! cleared:
+//!
+//! let mut sram = unsafe { BackupSram::enable(&peripherals.rcc, &peripherals.pwr) };
+//! if let Some(dump) = sram.crash_dump() {
+//!     dump.write_hex(&mut panic::Uart).unwrap();
+//! }
+//! sram.clear_crash_dump();
+
+use core::{ cmp, fmt, mem, ptr, slice, str };
+
+use ::core_cm4::exceptions::Exception;
+use ::core_cm4::fault::{ ExceptionFrame, FaultReport };
+use ::hal::rcc::wait_for;
+use ::stm32f4xx::regs::pwr::*;
+use ::stm32f4xx::regs::rcc::*;
+
+/// Marks backup SRAM as holding a dump
+pub const CRASH_DUMP_MAGIC  : u32   = 0xC4A5_D0B7;
+
+/// Bytes of the reason kept, longer reasons are truncated
+pub const REASON_SIZE       : usize = 128;
+
+/// Stack words kept, starting at the stack pointer of the crashed code
+pub const STACK_WORDS       : usize = 256;
+
+/// What stopped the program
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Cause {
+    Panic,
+    Fault(Exception),
+}
+
+/// Layout of the dump in backup SRAM (about 1.2 KB of the 4 KB), placed
+/// in `.bkpsram`
+#[repr(C)]
+pub struct CrashDump {
+    magic           : u32,
+    /// 0 for a panic, the exception number for a fault
+    cause           : u32,
+    /// Registers stacked by the fault, all zero for a panic
+    pub frame       : ExceptionFrame,
+    /// Stack pointer of the crashed code, where the stack slice starts
+    pub sp          : u32,
+    /// EXC_RETURN of the fault handler, 0 for a panic
+    pub exc_return  : u32,
+    pub cfsr        : u32,
+    pub hfsr        : u32,
+    pub mmfar       : u32,
+    pub bfar        : u32,
+    reason_len      : u32,
+    reason          : [u8; REASON_SIZE],
+    stack_len       : u32,
+    stack           : [u32; STACK_WORDS],
+    checksum        : u32,
+}
+
+const DUMP_WORDS    : usize = mem::size_of::<CrashDump>() / 4;
+
+bkpsram! {
+    static mut CRASH_DUMP: CrashDump = CrashDump {
+        magic       : 0,
+        cause       : 0,
+        frame       : ExceptionFrame { r0: 0, r1: 0, r2: 0, r3: 0,
+                                       r12: 0, lr: 0, pc: 0, xpsr: 0 },
+        sp          : 0,
+        exc_return  : 0,
+        cfsr        : 0,
+        hfsr        : 0,
+        mmfar       : 0,
+        bfar        : 0,
+        reason_len  : 0,
+        reason      : [0; REASON_SIZE],
+        stack_len   : 0,
+        stack       : [0; STACK_WORDS],
+        checksum    : 0,
+    };
+}
+
+impl CrashDump {
+    fn words(&self) -> &[u32] {
+        unsafe { slice::from_raw_parts(self as *const CrashDump as *const u32, DUMP_WORDS - 1) }
+    }
+
+    fn compute_checksum(&self) -> u32 {
+        self.words().iter().fold(0xFFFF_FFFF, |sum, &word| sum.rotate_left(5) ^ word)
+    }
+
+    /// True if a complete dump is stored
+    pub fn is_valid(&self) -> bool {
+        self.magic == CRASH_DUMP_MAGIC
+            && self.reason_len as usize <= REASON_SIZE
+            && self.stack_len as usize <= STACK_WORDS
+            && self.checksum == self.compute_checksum()
+    }
+
+    pub fn cause(&self) -> Cause {
+        match Exception::from_number(self.cause) {
+            Some(exception) => Cause::Fault(exception),
+            None => Cause::Panic,
+        }
+    }
+
+    /// Panic message and location, or the decoded fault causes
+    pub fn reason(&self) -> &str {
+        let bytes = &self.reason[..cmp::min(self.reason_len as usize, REASON_SIZE)];
+        match str::from_utf8(bytes) {
+            Ok(reason) => reason,
+            Err(e) => str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or(""),
+        }
+    }
+
+    /// Stack contents from `sp` upwards
+    pub fn stack(&self) -> &[u32] {
+        &self.stack[..cmp::min(self.stack_len as usize, STACK_WORDS)]
+    }
+
+    /// The fault report rebuilt from the stored registers, `None` for a panic
+    pub fn fault_report(&self) -> Option<FaultReport> {
+        match self.cause() {
+            Cause::Fault(exception) =>
+                Some(FaultReport::new(exception, self.frame,
+                                      self.cfsr, self.hfsr, self.mmfar, self.bfar)),
+            Cause::Panic => None,
+        }
+    }
+
+    /// Invalidates the dump
+    pub fn clear(&mut self) {
+        self.magic = 0;
+    }
+
+    /// Overwrites the dump with a new record
+    // One argument per part of the record, both callers fill them all
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(&mut self, cause: Cause, frame: &ExceptionFrame, exc_return: u32, sp: u32,
+                  status: [u32; 4], stack: &[u32], reason: fmt::Arguments) {
+        self.magic = 0;
+        self.cause = match cause {
+            Cause::Panic => 0,
+            Cause::Fault(exception) => exception as u32,
+        };
+        self.frame = *frame;
+        self.sp = sp;
+        self.exc_return = exc_return;
+        self.cfsr = status[0];
+        self.hfsr = status[1];
+        self.mmfar = status[2];
+        self.bfar = status[3];
+
+        self.reason_len = 0;
+        let _ = fmt::write(&mut ReasonWriter(self), reason);
+
+        let len = cmp::min(stack.len(), STACK_WORDS);
+        self.stack[..len].copy_from_slice(&stack[..len]);
+        self.stack_len = len as u32;
+
+        self.magic = CRASH_DUMP_MAGIC;
+        self.checksum = self.compute_checksum();
+    }
+
+    /// Writes the dump as text, e.g. to the UART
+    pub fn write_hex<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self.fault_report() {
+            Some(report) => write!(w, "crash dump: {}\r\n", report)?,
+            None => write!(w, "crash dump: {}\r\n", self.reason())?,
+        }
+        write!(w, "sp = 0x{:08X} exc_return = 0x{:08X} mmfar = 0x{:08X} bfar = 0x{:08X}\r\n",
+               self.sp, self.exc_return, self.mmfar, self.bfar)?;
+
+        for (i, line) in self.stack().chunks(4).enumerate() {
+            write!(w, "{:08X}:", self.sp.wrapping_add(16 * i as u32))?;
+            for word in line {
+                write!(w, " {:08X}", word)?;
+            }
+            write!(w, "\r\n")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the dump as an ELF core file: the registers as an NT_PRSTATUS
+    /// note and the stack slice as a memory segment. r4-r11 are not saved
+    /// and read as zero.
+    ///
+    /// The note has the ARM Linux `elf_prstatus` layout, so loading it with
+    /// `gdb-multiarch <elf> <core>` needs a GDB built with ARM Linux
+    /// support; bare-metal `arm-none-eabi-gdb` builds may lack it.
+    ///
+    /// Sent as hex over a serial line it is turned back into a file with
+    /// `xxd -r -p`.
+    pub fn write_core<F: FnMut(&[u8])>(&self, mut out: F) {
+        const EHDR_SIZE     : u32   = 52;
+        const PHDR_SIZE     : u32   = 32;
+        const PRSTATUS_SIZE : usize = 148;
+        const NOTE_SIZE     : u32   = 12 + 8 + PRSTATUS_SIZE as u32;
+
+        let stack_bytes = 4 * self.stack().len() as u32;
+
+        let mut ehdr = [0u8; EHDR_SIZE as usize];
+        ehdr[..7].copy_from_slice(&[0x7F, b'E', b'L', b'F', 1, 1, 1]);
+        put16(&mut ehdr, 16, 4);                    // ET_CORE
+        put16(&mut ehdr, 18, 40);                   // EM_ARM
+        put32(&mut ehdr, 20, 1);                    // EV_CURRENT
+        put32(&mut ehdr, 28, EHDR_SIZE);            // e_phoff
+        put16(&mut ehdr, 40, EHDR_SIZE as u16);     // e_ehsize
+        put16(&mut ehdr, 42, PHDR_SIZE as u16);     // e_phentsize
+        put16(&mut ehdr, 44, 2);                    // e_phnum
+        out(&ehdr);
+
+        let note_offset = EHDR_SIZE + 2 * PHDR_SIZE;
+        out(&phdr(4, note_offset, 0, NOTE_SIZE, 0, 4));                         // PT_NOTE, PF_R
+        out(&phdr(1, note_offset + NOTE_SIZE, self.sp, stack_bytes, stack_bytes, 6)); // PT_LOAD, PF_RW
+
+        let mut note = [0u8; 20];
+        put32(&mut note, 0, 5);                     // namesz
+        put32(&mut note, 4, PRSTATUS_SIZE as u32);  // descsz
+        put32(&mut note, 8, 1);                     // NT_PRSTATUS
+        note[12..17].copy_from_slice(b"CORE\0");
+        out(&note);
+
+        let f = &self.frame;
+        let regs = [f.r0, f.r1, f.r2, f.r3, 0, 0, 0, 0, 0, 0, 0, 0,
+                    f.r12, self.sp, f.lr, f.pc, f.xpsr, f.r0];
+        let mut prstatus = [0u8; PRSTATUS_SIZE];
+        // pr_cursig: SIGABRT for a panic, SIGSEGV for a fault
+        put16(&mut prstatus, 12, if self.cause == 0 { 6 } else { 11 });
+        for (i, reg) in regs.iter().enumerate() {
+            put32(&mut prstatus, 72 + 4 * i, *reg);
+        }
+        out(&prstatus);
+
+        for word in self.stack() {
+            let mut bytes = [0u8; 4];
+            put32(&mut bytes, 0, *word);
+            out(&bytes);
+        }
+    }
+}
+
+fn put16(buf: &mut [u8], at: usize, value: u16) {
+    buf[at] = value as u8;
+    buf[at + 1] = (value >> 8) as u8;
+}
+
+fn put32(buf: &mut [u8], at: usize, value: u32) {
+    for i in 0..4 {
+        buf[at + i] = (value >> (8 * i)) as u8;
+    }
+}
+
+fn phdr(p_type: u32, offset: u32, addr: u32, filesz: u32, memsz: u32, flags: u32) -> [u8; 32] {
+    let mut phdr = [0u8; 32];
+    put32(&mut phdr, 0, p_type);
+    put32(&mut phdr, 4, offset);
+    put32(&mut phdr, 8, addr);
+    put32(&mut phdr, 12, addr);
+    put32(&mut phdr, 16, filesz);
+    put32(&mut phdr, 20, memsz);
+    put32(&mut phdr, 24, flags);
+    put32(&mut phdr, 28, 4);
+    phdr
+}
+
+/// Appends to the reason, truncating on a character boundary
+struct ReasonWriter<'a>(&'a mut CrashDump);
+
+impl<'a> fmt::Write for ReasonWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let dump = &mut *self.0;
+        for c in s.chars() {
+            let start = dump.reason_len as usize;
+            let end = start + c.len_utf8();
+            if end > REASON_SIZE {
+                return Err(fmt::Error);
+            }
+            c.encode_utf8(&mut dump.reason[start..end]);
+            dump.reason_len = end as u32;
+        }
+        Ok(())
+    }
+}
+
+/// Turns on the backup SRAM clock, backup domain write access and the backup
+/// regulator, which keeps the SRAM alive on VBAT. False if the regulator
+/// did not report ready, the SRAM can be used anyway but may not survive
+/// on VBAT.
+pub fn enable_backup_domain(rcc: &RccRegs, pwr: &PwrRegs) -> bool {
+    rcc.apb1enr.bit_or(RCC_APB1ENR_PWREN);
+    pwr.cr.bit_or(PWR_CR_DBP);
+    rcc.ahb1enr.bit_or(RCC_AHB1ENR_BKPSRAMEN);
+    pwr.csr.bit_or(PWR_CSR_BRE);
+
+    // Also runs in the panic and fault handlers, which must not hang here
+    wait_for(|| pwr.csr.get() & PWR_CSR_BRR != 0)
+}
+
+/// Access to the crash dump in backup SRAM
+pub struct BackupSram {
+    dump    : &'static mut CrashDump,
+}
+
+impl BackupSram {
+    /// Enables backup SRAM. The caller must make sure no other instance
+    /// exists, they would share the dump.
+    pub unsafe fn enable(rcc: &RccRegs, pwr: &PwrRegs) -> BackupSram {
+        enable_backup_domain(rcc, pwr);
+        BackupSram {
+            dump    : &mut *ptr::addr_of_mut!(CRASH_DUMP),
+        }
+    }
+
+    /// The dump left by the previous run, if any
+    pub fn crash_dump(&self) -> Option<&CrashDump> {
+        if self.dump.is_valid() { Some(&*self.dump) } else { None }
+    }
+
+    pub fn clear_crash_dump(&mut self) {
+        self.dump.clear();
+    }
+}
+
+#[cfg(not(test))]
+extern "C" {
+    // Top of the main stack and start of its region, defined by the
+    // linker script
+    static _estack: u32;
+    static _stack_origin: u32;
+}
+
+/// The stack slice from `sp` to the top of the main stack
+#[cfg(not(test))]
+unsafe fn stack_from(sp: u32) -> &'static [u32] {
+    let top = &_estack as *const u32 as u32;
+    let origin = &_stack_origin as *const u32 as u32;
+    if sp < origin || sp >= top || sp & 0x3 != 0 {
+        return &[];
+    }
+    slice::from_raw_parts(sp as *const u32, cmp::min((top - sp) as usize / 4, STACK_WORDS))
+}
+
+/// Records a panic, called by the panic handler
+#[cfg(not(test))]
+pub unsafe fn save_panic(reason: fmt::Arguments) {
+    // Nothing else runs any more, the blocks are stolen from their owners
+    let sram = BackupSram::enable(&RccRegs::init(), &PwrRegs::init());
+    let sp = ::stack::current_sp();
+
+    sram.dump.record(Cause::Panic, &ExceptionFrame::default(), 0, sp,
+                     [0; 4], stack_from(sp), reason);
+}
+
+/// Records a fault, called by the fault handlers with the address of the
+/// stacked frame
+#[cfg(not(test))]
+pub unsafe fn save_fault(report: &FaultReport, frame_address: u32, exc_return: u32) {
+    // Nothing else runs any more, the blocks are stolen from their owners
+    let sram = BackupSram::enable(&RccRegs::init(), &PwrRegs::init());
+
+    // The stack of the crashed code continues above the frame: 8 words, or
+    // 26 with the FP context (EXC_RETURN bit 4 clear), plus one word of
+    // alignment padding if xPSR bit 9 is set
+    let mut sp = frame_address + if exc_return & 0x10 == 0 { 0x68 } else { 0x20 };
+    if report.frame.xpsr & (1 << 9) != 0 {
+        sp += 4;
+    }
+
+    sram.dump.record(Cause::Fault(report.exception), &report.frame, exc_return, sp,
+                     [report.cfsr, report.hfsr, report.mmfar.unwrap_or(0), report.bfar.unwrap_or(0)],
+                     stack_from(sp), format_args!("{}", report.summary()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::core_cm4::core_cm4_regs::scb::*;
+    use ::stm32f4xx::constants::PWR_BASE;
+
+    fn empty() -> Box<CrashDump> {
+        Box::new(unsafe { mem::zeroed() })
+    }
+
+    fn fault_dump() -> Box<CrashDump> {
+        let mut dump = empty();
+        let frame = ExceptionFrame { r0: 1, pc: 0x0800_0400, lr: 0x0800_0201, ..Default::default() };
+        dump.record(Cause::Fault(Exception::UsageFault), &frame, 0xFFFF_FFFD, 0x2001_FF00,
+                    [SCB_CFSR_DIVBYZERO_MSK, 0, 0, 0], &[0xAAAA_AAAA, 0xBBBB_BBBB],
+                    format_args!("UsageFault: divide by zero"));
+        dump
+    }
+
+    #[test]
+    fn recorded_dump_reads_back() {
+        let dump = fault_dump();
+
+        assert!(dump.is_valid());
+        assert_eq!(dump.cause(), Cause::Fault(Exception::UsageFault));
+        assert_eq!(dump.reason(), "UsageFault: divide by zero");
+        assert_eq!(dump.stack(), [0xAAAA_AAAA, 0xBBBB_BBBB]);
+        assert!(dump.fault_report().unwrap().has(::core_cm4::fault::FaultCause::DivideByZero));
+    }
+
+    #[test]
+    fn cleared_or_corrupted_dump_is_invalid() {
+        assert!(!empty().is_valid());
+
+        let mut dump = fault_dump();
+        dump.stack[1] ^= 1;
+        assert!(!dump.is_valid());
+
+        let mut dump = fault_dump();
+        dump.clear();
+        assert!(!dump.is_valid());
+    }
+
+    #[test]
+    fn long_reason_is_truncated_on_char_boundary() {
+        let mut dump = empty();
+        let long = "é".repeat(REASON_SIZE);
+        dump.record(Cause::Panic, &ExceptionFrame::default(), 0, 0, [0; 4], &[],
+                    format_args!("{}", long));
+
+        assert_eq!(dump.reason().len(), REASON_SIZE);
+        assert_eq!(dump.cause(), Cause::Panic);
+        assert!(dump.fault_report().is_none());
+    }
+
+    #[test]
+    fn core_file_has_registers_and_stack() {
+        let mut core = Vec::new();
+        fault_dump().write_core(|bytes| core.extend_from_slice(bytes));
+
+        assert_eq!(&core[..4], b"\x7FELF");
+        assert_eq!(core.len(), 52 + 2 * 32 + 12 + 8 + 148 + 8);
+        // PT_LOAD maps the stack at sp
+        assert_eq!(&core[52 + 32 + 8..52 + 32 + 12], &[0x00, 0xFF, 0x01, 0x20]);
+        // pr_reg[15] is pc
+        let pc = 52 + 64 + 20 + 72 + 15 * 4;
+        assert_eq!(&core[pc..pc + 4], &[0x00, 0x04, 0x00, 0x08]);
+        assert_eq!(&core[core.len() - 4..], &[0xBB; 4]);
+    }
+
+    #[test]
+    fn core_file_reads_back_with_an_elf_reader() {
+        use object::{ elf, Architecture, LittleEndian, Object, ObjectKind, ObjectSegment };
+        use object::read::elf::{ ElfFile32, ProgramHeader };
+
+        let mut core = Vec::new();
+        fault_dump().write_core(|bytes| core.extend_from_slice(bytes));
+
+        let file = ElfFile32::<LittleEndian>::parse(&core[..]).unwrap();
+        assert_eq!(file.kind(), ObjectKind::Core);
+        assert_eq!(file.architecture(), Architecture::Arm);
+
+        let mut notes = Vec::new();
+        for phdr in file.elf_program_headers() {
+            if let Some(mut iter) = phdr.notes(LittleEndian, file.data()).unwrap() {
+                while let Some(note) = iter.next().unwrap() {
+                    notes.push((note.name().to_vec(), note.n_type(LittleEndian),
+                                note.desc().to_vec()));
+                }
+            }
+        }
+        assert_eq!(notes.len(), 1);
+        let (ref name, n_type, ref prstatus) = notes[0];
+        assert_eq!(&name[..], b"CORE");
+        assert_eq!(n_type, elf::NT_PRSTATUS);
+        assert_eq!(prstatus.len(), 148);
+
+        // pr_cursig SIGSEGV, then pr_reg at 72: r0, ..., sp, lr, pc
+        let reg = |i: usize| {
+            let at = 72 + 4 * i;
+            u32::from_le_bytes([prstatus[at], prstatus[at + 1], prstatus[at + 2], prstatus[at + 3]])
+        };
+        assert_eq!(prstatus[12], 11);
+        assert_eq!((reg(0), reg(13), reg(14), reg(15)), (1, 0x2001_FF00, 0x0800_0201, 0x0800_0400));
+
+        let stack = file.segments().find(|s| s.address() == 0x2001_FF00).unwrap();
+        assert_eq!(stack.data().unwrap(), &[0xAA, 0xAA, 0xAA, 0xAA, 0xBB, 0xBB, 0xBB, 0xBB]);
+    }
+
+    #[test]
+    fn hex_dump_lists_stack_words() {
+        let mut text = String::new();
+        fault_dump().write_hex(&mut text).unwrap();
+
+        assert!(text.starts_with("crash dump: UsageFault: divide by zero\r\n"));
+        assert!(text.ends_with("2001FF00: AAAAAAAA BBBBBBBB\r\n"));
+    }
+
+    #[test]
+    fn backup_domain_waits_for_regulator() {
+        const CSR: u32 = PWR_BASE + 0x04;
+        mock_regs::set_hook(CSR, |value| {
+            if value & PWR_CSR_BRE != 0 { value | PWR_CSR_BRR } else { value }
+        });
+
+        assert!(enable_backup_domain(unsafe { &RccRegs::init() }, unsafe { &PwrRegs::init() }));
+
+        assert_eq!(mock_regs::read(PWR_BASE) & PWR_CR_DBP, PWR_CR_DBP);
+        assert_eq!(mock_regs::read(CSR), PWR_CSR_BRE | PWR_CSR_BRR);
+    }
+
+    #[test]
+    fn backup_domain_gives_up_on_a_dead_regulator() {
+        assert!(!enable_backup_domain(unsafe { &RccRegs::init() }, unsafe { &PwrRegs::init() }));
+        assert_eq!(mock_regs::read(PWR_BASE) & PWR_CR_DBP, PWR_CR_DBP);
+    }
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+
+//! HardFault, MemManage, BusFault and UsageFault handlers
+//!
+//! Each fault finds the exception frame on the stack that was in use, writes
+//! the decoded `FaultReport` to the panic sinks, saves a crash dump if the
+//! `crash-dump` feature is enabled and then takes the panic action. Blinking
+//! shows the exception number instead of the panic code.
+//!
+//! With the `stack-guard` feature the handlers first turn the MPU off and
+//! move to the fault stack, the main stack may have run into its guard.
+
+use ::core_cm4::exceptions::Exception;
+#[cfg(not(test))]
+use ::core_cm4::exceptions;
+#[cfg(not(test))]
+use ::core_cm4::fault::{ ExceptionFrame, FaultReport };
+use ::panic::{ self, Action };
+
+// Bit 2 of EXC_RETURN in LR tells whether the frame was pushed on the main
+// or on the process stack, EXC_RETURN goes along in r1
+#[cfg(all(target_arch = "arm", not(test)))]
+global_asm!(r#"
+    .syntax unified
+    .macro fault_trampoline name
+    .section .text.\name, "ax", %progbits
+    .global \name
+    .thumb_func
+\name:
+    tst lr, #4
+    ite eq
+    mrseq r0, MSP
+    mrsne r0, PSP
+    mov r1, lr
+    b fault_switch_stack
+    .endm
+
+    fault_trampoline HardFault
+    fault_trampoline MemManage
+    fault_trampoline BusFault
+    fault_trampoline UsageFault
+"#);
+
+// MPU_CTRL is at 0xE000ED94
+#[cfg(all(target_arch = "arm", not(test), feature = "stack-guard"))]
+global_asm!(r#"
+    .syntax unified
+    .section .text.fault_switch_stack, "ax", %progbits
+    .thumb_func
+fault_switch_stack:
+    ldr r2, =0xE000ED94
+    movs r3, #0
+    str r3, [r2]
+    dsb
+    isb
+    ldr r2, =_efault_stack
+    mov sp, r2
+    b fault_entry
+"#);
+
+#[cfg(all(target_arch = "arm", not(test), not(feature = "stack-guard")))]
+global_asm!(r#"
+    .thumb_set fault_switch_stack, fault_entry
+"#);
+
+#[cfg(not(test))]
+#[no_mangle]
+pub unsafe extern "C" fn fault_entry(frame: &ExceptionFrame, exc_return: u32) -> ! {
+    ::core_cm4::interrupt::disable();
+
+    let exception = Exception::from_number(exceptions::active()).unwrap_or(Exception::HardFault);
+    let report = FaultReport::capture(exception, frame);
+
+    #[cfg(feature = "crash-dump")]
+    ::crash_dump::save_fault(&report, frame as *const ExceptionFrame as u32, exc_return);
+    // Only the crash dump keeps it
+    #[cfg(not(feature = "crash-dump"))]
+    let _ = exc_return;
+
+    use core::fmt::Write;
+    #[cfg(feature = "stack-guard")]
+    {
+        if ::stack::hit_guard(&report, frame as *const ExceptionFrame as u32, &::stack::guard()) {
+            let _ = write!(panic::Sinks, "stack overflow, {} bytes of stack\r\n", ::stack::size());
+        }
+    }
+    let _ = write!(panic::Sinks, "{}\r\n", report);
+
+    panic::act(action(exception))
+}
+
+/// The panic action, blinking the exception number
+fn action(exception: Exception) -> Action {
+    match panic::ACTION {
+        Action::BlinkLeds(_) => Action::BlinkLeds(exception as u32),
+        action => action,
+    }
+}
@@ -2,6 +2,13 @@
 
 use ::stm32f4xx::regs::gpio::*;
 use ::hal::peripheral_clock::PeripheralClock;
+use ::stm32f4xx::regs::rcc::RccRegs;
+
+pub use ::stm32f4xx::regs::gpio::moder::PinMode;
+pub use ::stm32f4xx::regs::gpio::otyper::PinOutputType;
+pub use ::stm32f4xx::regs::gpio::ospeedr::PinSpeed;
+pub use ::stm32f4xx::regs::gpio::pupdr::PinPullUpDown;
+pub use ::stm32f4xx::regs::gpio::afr::AltFunc;
 
 impl Port {
     pub fn get_clock(&self) -> PeripheralClock {
@@ -15,78 +22,129 @@ impl Port {
             &Port::GpioG => PeripheralClock::GpioG,
             &Port::GpioH => PeripheralClock::GpioH,
             &Port::GpioI => PeripheralClock::GpioI,
+            #[cfg(feature = "stm32f42x")]
+            &Port::GpioJ => PeripheralClock::GpioJ,
+            #[cfg(feature = "stm32f42x")]
+            &Port::GpioK => PeripheralClock::GpioK,
+        }
+    }
+}
+
+/// The pins of one port, each pin can be handed out to a different driver
+/// while the port registers stay mutably borrowed, so a port can not be
+/// split twice
+pub struct Pins<'a> {
+    pub p0:     Pin<'a>,
+    pub p1:     Pin<'a>,
+    pub p2:     Pin<'a>,
+    pub p3:     Pin<'a>,
+    pub p4:     Pin<'a>,
+    pub p5:     Pin<'a>,
+    pub p6:     Pin<'a>,
+    pub p7:     Pin<'a>,
+    pub p8:     Pin<'a>,
+    pub p9:     Pin<'a>,
+    pub p10:    Pin<'a>,
+    pub p11:    Pin<'a>,
+    pub p12:    Pin<'a>,
+    pub p13:    Pin<'a>,
+    pub p14:    Pin<'a>,
+    pub p15:    Pin<'a>,
+}
+
+impl<'a> Pins<'a> {
+    /// Borrows the registers of a port exclusively and splits them into pins
+    pub fn split(gpio: &'a mut GpioRegs) -> Pins<'a> {
+        let gpio: &'a GpioRegs = gpio;
+
+        Pins {
+            p0:     Pin::new(gpio, 0),
+            p1:     Pin::new(gpio, 1),
+            p2:     Pin::new(gpio, 2),
+            p3:     Pin::new(gpio, 3),
+            p4:     Pin::new(gpio, 4),
+            p5:     Pin::new(gpio, 5),
+            p6:     Pin::new(gpio, 6),
+            p7:     Pin::new(gpio, 7),
+            p8:     Pin::new(gpio, 8),
+            p9:     Pin::new(gpio, 9),
+            p10:    Pin::new(gpio, 10),
+            p11:    Pin::new(gpio, 11),
+            p12:    Pin::new(gpio, 12),
+            p13:    Pin::new(gpio, 13),
+            p14:    Pin::new(gpio, 14),
+            p15:    Pin::new(gpio, 15),
         }
     }
 }
 
-pub struct Pin {
-    pub port:       Port,
-    pub pin_num:    u8,
+/// A single pin, only obtainable through `Pins::split`
+pub struct Pin<'a> {
+    gpio:           &'a GpioRegs,
+    pin_num:        u8,
     pub mode:       PinMode,
 }
 
-impl Pin {
-    pub fn init(port: Port, pin_num: u8) -> Pin {
+impl<'a> Pin<'a> {
+    fn new(gpio: &'a GpioRegs, pin_num: u8) -> Pin<'a> {
         Pin {
-            port:       port,
+            gpio:       gpio,
             pin_num:    pin_num,
             mode:       PinMode::Input,
         }
     }
 
-    pub fn enable_clock(&self) {
-        self.port.get_clock().enable();
+    /// Registers of the pin's port; the pin owns its own bits in them
+    fn regs(&self) -> &GpioRegs {
+        self.gpio
     }
 
-    pub fn setup_mode(&mut self, mode: PinMode) {
-        let gpio = GpioRegs::init(&self.port);
-        let mut mask: u32 = 0b11 << (2 * self.pin_num);
-        mask = !mask;
+    pub fn port(&self) -> Port {
+        self.gpio.port
+    }
 
-        let val: u32 = (mode as u32) << (2 * self.pin_num);
-        let mut mode_reg = gpio.moder.get();
+    pub fn pin_num(&self) -> u8 {
+        self.pin_num
+    }
 
-        mode_reg &= mask;
-        mode_reg |= val;
-        gpio.moder.set(mode_reg);
+    pub fn enable_clock(&self, rcc: &RccRegs) {
+        self.port().get_clock().enable(rcc);
+    }
+
+    pub fn setup_mode(&mut self, mode: PinMode) {
+        let gpio = self.regs();
+        gpio.moder.modify(|_, w| w.set_at(self.pin_num as u32, mode));
 
         self.mode = mode;
 
     }
     pub fn setup_output_type(&self, output_type: PinOutputType) {
-        let gpio = GpioRegs::init(&self.port);
-
-        let val = (1 as u32) << self.pin_num;
-
-        if output_type == PinOutputType::OpenDrain {
-            gpio.otyper.bit_or(val);
-        } else if output_type == PinOutputType::PushPull{
-            gpio.otyper.bit_and(!val);
-        }
+        let gpio = self.regs();
+        gpio.otyper.modify(|_, w| w.set_at(self.pin_num as u32, output_type));
     }
 
     pub fn setup_speed(&self, speed: PinSpeed) {
-        let gpio = GpioRegs::init(&self.port);
-        gpio.ospeedr.bit_or((speed as u32) << (2 * self.pin_num));
+        let gpio = self.regs();
+        gpio.ospeedr.modify(|_, w| w.set_at(self.pin_num as u32, speed));
     }
 
     pub fn setup_pull_up_down(&self, pull_up_down: PinPullUpDown) {
-        let gpio = GpioRegs::init(&self.port);
-        gpio.pupdr.bit_or((pull_up_down as u32) << (2 * self.pin_num));
+        let gpio = self.regs();
+        gpio.pupdr.modify(|_, w| w.set_at(self.pin_num as u32, pull_up_down));
     }
 
-    pub fn setup_alt_func(&self, alt_func: u32) {
-        let gpio = GpioRegs::init(&self.port);
+    pub fn setup_alt_func(&self, alt_func: AltFunc) {
+        let gpio = self.regs();
         if self.pin_num < 8 {
-            gpio.afrl.bit_or(alt_func << (self.pin_num * 4));
+            gpio.afrl.modify(|_, w| w.set_at(self.pin_num as u32, alt_func));
         } else {
-            gpio.afrh.bit_or(alt_func << ((self.pin_num - 8) * 4));
+            gpio.afrh.modify(|_, w| w.set_at((self.pin_num - 8) as u32, alt_func));
         }
     }
 
     /// Set the value of the pin
     pub fn set(&self, val: bool) {
-        let regs = GpioRegs::init(&self.port);
+        let regs = self.regs();
         if val {
             regs.bsrr.set(1 << self.pin_num);
         } else {
@@ -96,7 +154,7 @@ impl Pin {
 
     /// Returns the value of the pin
     pub fn get(&self) -> u32 {
-        let regs = GpioRegs::init(&self.port);
+        let regs = self.regs();
         let mut val: u32;
 
         val = regs.idr.get();
@@ -118,31 +176,58 @@ impl Pin {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum PinMode {
-    Input   = 0x00,
-    Output  = 0x01,
-    AltFunc = 0x02,
-    Analog  = 0x03,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::stm32f4xx::constants::{ GPIOB_BASE, GPIOD_BASE };
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum PinOutputType {
-    PushPull    = 0x00,
-    OpenDrain   = 0x01,
-}
+    const MODER     : u32   = 0x00;
+    const IDR       : u32   = 0x10;
+    const BSRR      : u32   = 0x18;
+    const AFRL      : u32   = 0x20;
+    const AFRH      : u32   = 0x24;
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum PinSpeed {
-    Low         = 0x00,
-    Medium      = 0x01,
-    High        = 0x02,
-    VeryHigh    = 0x03,
-}
+    #[test]
+    fn setup_mode_changes_only_own_bits() {
+        mock_regs::write(GPIOD_BASE + MODER, 0xFFFFFFFF);
+
+        let mut gpiod = unsafe { GpioRegs::init(&Port::GpioD) };
+        let mut pin = Pins::split(&mut gpiod).p12;
+        pin.setup_mode(PinMode::Output);
+
+        assert_eq!(mock_regs::read(GPIOD_BASE + MODER), 0xFDFFFFFF);
+        assert!(pin.mode == PinMode::Output);
+
+        pin.setup_mode(PinMode::Input);
+        assert_eq!(mock_regs::read(GPIOD_BASE + MODER), 0xFCFFFFFF);
+    }
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum PinPullUpDown {
-    NoPullUpDown    = 0x00,
-    PullUp          = 0x01,
-    PullDown        = 0x02,
+    #[test]
+    fn setup_alt_func_low_and_high_register() {
+        mock_regs::write(GPIOB_BASE + AFRL, 0x77777777);
+        mock_regs::write(GPIOB_BASE + AFRH, 0x77777777);
+
+        let mut gpiob = unsafe { GpioRegs::init(&Port::GpioB) };
+        let pins = Pins::split(&mut gpiob);
+        pins.p3.setup_alt_func(AltFunc::Af5);
+        pins.p13.setup_alt_func(AltFunc::Af5);
+
+        assert_eq!(mock_regs::read(GPIOB_BASE + AFRL), 0x77775777);
+        assert_eq!(mock_regs::read(GPIOB_BASE + AFRH), 0x77577777);
+    }
+
+    #[test]
+    fn toggle_writes_bsrr_from_input_state() {
+        let mut gpiod = unsafe { GpioRegs::init(&Port::GpioD) };
+        let pin = Pins::split(&mut gpiod).p14;
+
+        mock_regs::write(GPIOD_BASE + IDR, 0);
+        assert_eq!(pin.toggle(), 1);
+        assert_eq!(mock_regs::read(GPIOD_BASE + BSRR), 1 << 14);
+
+        mock_regs::write(GPIOD_BASE + IDR, 1 << 14);
+        assert_eq!(pin.toggle(), 0);
+        assert_eq!(mock_regs::read(GPIOD_BASE + BSRR), 1 << (14 + 16));
+    }
 }
@@ -1,3 +1,7 @@
 
 pub mod gpio;
 pub mod peripheral_clock;
+pub mod plli2s;
+pub mod rcc;
+pub mod reset_cause;
+pub mod time;
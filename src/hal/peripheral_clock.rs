@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use ::stm32f4xx::regs::rcc::*;
+use ::stm32f4xx::bit_band;
 
 pub enum PeripheralClock {
     // AHB1
@@ -13,27 +14,43 @@ pub enum PeripheralClock {
     GpioG,
     GpioH,
     GpioI,
+    #[cfg(feature = "stm32f42x")]
+    GpioJ,
+    #[cfg(feature = "stm32f42x")]
+    GpioK,
     Crc,
     BkpSram,
     CcmDataRam,
     Dma1,
     Dma2,
+    #[cfg(feature = "stm32f42x")]
+    Dma2d,
+    #[cfg(feature = "eth")]
     EthMac,
+    #[cfg(feature = "eth")]
     EthMacTx,
+    #[cfg(feature = "eth")]
     EthMacRx,
+    #[cfg(feature = "eth")]
     EthMacPtp,
     OtgHs,
     OtgHsulpi,
 
     // AHB2
+    #[cfg(feature = "dcmi")]
     Dcmi,
+    #[cfg(feature = "cryp")]
     Cryp,
+    #[cfg(feature = "cryp")]
     Hash,
     Rng,
     OtgFs,
 
     // AHB3
+    #[cfg(feature = "stm32f40x")]
     Fsmc,
+    #[cfg(feature = "stm32f42x")]
+    Fmc,
 
     // APB1
     Tim2,
@@ -59,6 +76,10 @@ pub enum PeripheralClock {
     Can2,
     Pwr,
     Dac,
+    #[cfg(feature = "stm32f42x")]
+    Uart7,
+    #[cfg(feature = "stm32f42x")]
+    Uart8,
 
     // APB2
     Tim1,
@@ -74,54 +95,98 @@ pub enum PeripheralClock {
     Tim9,
     Tim10,
     Tim11,
+    #[cfg(feature = "stm32f42x")]
+    Spi4,
+    #[cfg(feature = "stm32f42x")]
+    Spi5,
+    #[cfg(feature = "stm32f42x")]
+    Spi6,
+    #[cfg(feature = "stm32f42x")]
+    Sai1,
+    #[cfg(feature = "ltdc")]
+    Ltdc,
 }
 
 impl PeripheralClock {
-    pub fn enable(&self) {
-        self.set_bit(true);
+    pub fn enable(&self, rcc: &RccRegs) {
+        self.set_bit(rcc, true);
     }
-    pub fn disable(&self) {
-        self.set_bit(false);
+    pub fn disable(&self, rcc: &RccRegs) {
+        self.set_bit(rcc, false);
     }
 
-    fn set_bit(&self, set: bool) {
+    fn set_bit(&self, rcc: &RccRegs, set: bool) {
         use self::PeripheralClock::*;
 
         let bit_pos: u32 = self.get_bit_pos();
-        let bit_msk: u32 = !bit_pos;
-
-        let rcc = RccRegs::init();
+        let bit_num: u32 = bit_pos.trailing_zeros();
 
         let reg = match *self {
             GpioA | GpioB | GpioC | GpioD | GpioE | GpioF | GpioG | GpioH |
-            GpioI | Crc | BkpSram | CcmDataRam | Dma1 | Dma2 | EthMac |
-            EthMacTx | EthMacRx | EthMacPtp | OtgHs | OtgHsulpi =>
-                rcc.ahb1enr,
+            GpioI | Crc | BkpSram | CcmDataRam | Dma1 | Dma2 | OtgHs |
+            OtgHsulpi =>
+                &rcc.ahb1enr,
+
+            #[cfg(feature = "eth")]
+            EthMac | EthMacTx | EthMacRx | EthMacPtp =>
+                &rcc.ahb1enr,
+
+            #[cfg(feature = "stm32f42x")]
+            GpioJ | GpioK | Dma2d =>
+                &rcc.ahb1enr,
 
-            Dcmi | Cryp | Hash | Rng | OtgFs =>
-                rcc.ahb2enr,
+            Rng | OtgFs =>
+                &rcc.ahb2enr,
 
+            #[cfg(feature = "dcmi")]
+            Dcmi =>
+                &rcc.ahb2enr,
+
+            #[cfg(feature = "cryp")]
+            Cryp | Hash =>
+                &rcc.ahb2enr,
+
+            #[cfg(feature = "stm32f40x")]
             Fsmc =>
-                rcc.ahb3enr,
+                &rcc.ahb3enr,
+
+            #[cfg(feature = "stm32f42x")]
+            Fmc =>
+                &rcc.ahb3enr,
 
             Tim2 | Tim3 | Tim4 | Tim5 | Tim6 | Tim7 | Tim12 | Tim13 | Tim14 |
             WWdg | Spi2 | Spi3 | Usart2 | Usart3 | Uart4 | Uart5 | I2c1 |
             I2c2 | I2c3 | Can1 | Can2 | Pwr | Dac =>
-                rcc.apb1enr,
+                &rcc.apb1enr,
+
+            #[cfg(feature = "stm32f42x")]
+            Uart7 | Uart8 =>
+                &rcc.apb1enr,
 
             Tim1 | Tim8 | Usart1 | Usart6 | Adc1 | Adc2 | Adc3 | Sdio |
             Spi1 | SysCfg | Tim9 | Tim10 | Tim11 =>
-                rcc.apb2enr,
+                &rcc.apb2enr,
+
+            #[cfg(feature = "stm32f42x")]
+            Spi4 | Spi5 | Spi6 | Sai1 =>
+                &rcc.apb2enr,
+
+            #[cfg(feature = "ltdc")]
+            Ltdc =>
+                &rcc.apb2enr,
         };
 
+        // Bit-band writes so that drivers enabling clocks from different
+        // contexts can not lose each other's bits
         if set {
-            reg.bit_or(bit_pos);
+            bit_band::set_bit(reg, bit_num);
 
             // stall instruction pipeline, until instruction completes, as
             // per Errata 2.1.13, "Delay after an RCC peripheral clock enabling"
+            #[cfg(target_arch = "arm")]
             unsafe { asm!("dsb"); }
         } else {
-            reg.bit_and(bit_msk);
+            bit_band::clear_bit(reg, bit_num);
         }
 
 
@@ -139,25 +204,41 @@ impl PeripheralClock {
             GpioG       => RCC_AHB1ENR_GPIOGEN      ,
             GpioH       => RCC_AHB1ENR_GPIOHEN      ,
             GpioI       => RCC_AHB1ENR_GPIOIEN      ,
+            #[cfg(feature = "stm32f42x")]
+            GpioJ       => RCC_AHB1ENR_GPIOJEN      ,
+            #[cfg(feature = "stm32f42x")]
+            GpioK       => RCC_AHB1ENR_GPIOKEN      ,
             Crc         => RCC_AHB1ENR_CRCEN        ,
             BkpSram     => RCC_AHB1ENR_BKPSRAMEN    ,
             CcmDataRam  => RCC_AHB1ENR_CCMDATARAMEN ,
             Dma1        => RCC_AHB1ENR_DMA1EN       ,
             Dma2        => RCC_AHB1ENR_DMA2EN       ,
+            #[cfg(feature = "stm32f42x")]
+            Dma2d       => RCC_AHB1ENR_DMA2DEN      ,
+            #[cfg(feature = "eth")]
             EthMac      => RCC_AHB1ENR_ETHMACEN     ,
+            #[cfg(feature = "eth")]
             EthMacTx    => RCC_AHB1ENR_ETHMACTXEN   ,
+            #[cfg(feature = "eth")]
             EthMacRx    => RCC_AHB1ENR_ETHMACRXEN   ,
+            #[cfg(feature = "eth")]
             EthMacPtp   => RCC_AHB1ENR_ETHMACPTPEN  ,
             OtgHs       => RCC_AHB1ENR_OTGHSEN      ,
             OtgHsulpi   => RCC_AHB1ENR_OTGHSULPIEN  ,
 
+            #[cfg(feature = "dcmi")]
             Dcmi        => RCC_AHB2ENR_DCMIEN       ,
+            #[cfg(feature = "cryp")]
             Cryp        => RCC_AHB2ENR_CRYPEN       ,
+            #[cfg(feature = "cryp")]
             Hash        => RCC_AHB2ENR_HASHEN       ,
             Rng         => RCC_AHB2ENR_RNGEN        ,
             OtgFs       => RCC_AHB2ENR_OTGFSEN      ,
 
+            #[cfg(feature = "stm32f40x")]
             Fsmc        => RCC_AHB3ENR_FSMCEN       ,
+            #[cfg(feature = "stm32f42x")]
+            Fmc         => RCC_AHB3ENR_FMCEN        ,
 
             Tim2        => RCC_APB1ENR_TIM2EN       ,
             Tim3        => RCC_APB1ENR_TIM3EN       ,
@@ -182,6 +263,10 @@ impl PeripheralClock {
             Can2        => RCC_APB1ENR_CAN2EN       ,
             Pwr         => RCC_APB1ENR_PWREN        ,
             Dac         => RCC_APB1ENR_DACEN        ,
+            #[cfg(feature = "stm32f42x")]
+            Uart7       => RCC_APB1ENR_UART7EN      ,
+            #[cfg(feature = "stm32f42x")]
+            Uart8       => RCC_APB1ENR_UART8EN      ,
 
             Tim1        => RCC_APB2ENR_TIM1EN       ,
             Tim8        => RCC_APB2ENR_TIM8EN       ,
@@ -193,9 +278,63 @@ impl PeripheralClock {
             Sdio        => RCC_APB2ENR_SDIOEN       ,
             Spi1        => RCC_APB2ENR_SPI1EN       ,
             SysCfg      => RCC_APB2ENR_SYSCFGEN     ,
-            Tim9        => RCC_APB2ENR_TIM11EN      ,
+            Tim9        => RCC_APB2ENR_TIM9EN       ,
             Tim10       => RCC_APB2ENR_TIM10EN      ,
-            Tim11       => RCC_APB2ENR_TIM9EN       ,
+            Tim11       => RCC_APB2ENR_TIM11EN      ,
+            #[cfg(feature = "stm32f42x")]
+            Spi4        => RCC_APB2ENR_SPI4EN       ,
+            #[cfg(feature = "stm32f42x")]
+            Spi5        => RCC_APB2ENR_SPI5EN       ,
+            #[cfg(feature = "stm32f42x")]
+            Spi6        => RCC_APB2ENR_SPI6EN       ,
+            #[cfg(feature = "stm32f42x")]
+            Sai1        => RCC_APB2ENR_SAI1EN       ,
+            #[cfg(feature = "ltdc")]
+            Ltdc        => RCC_APB2ENR_LTDCEN       ,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::stm32f4xx::constants::RCC_BASE;
+
+    const AHB1ENR   : u32   = RCC_BASE + 0x30;
+    const APB1ENR   : u32   = RCC_BASE + 0x40;
+    const APB2ENR   : u32   = RCC_BASE + 0x44;
+
+    #[test]
+    fn enable_sets_bit_in_bus_register() {
+        mock_regs::write(APB1ENR, RCC_APB1ENR_PWREN);
+
+        PeripheralClock::Tim7.enable(unsafe { &RccRegs::init() });
+
+        assert_eq!(mock_regs::read(APB1ENR), RCC_APB1ENR_PWREN | RCC_APB1ENR_TIM7EN);
+        assert_eq!(mock_regs::read(AHB1ENR), 0);
+    }
+
+    #[test]
+    fn disable_clears_only_own_bit() {
+        mock_regs::write(AHB1ENR, RCC_AHB1ENR_GPIOAEN | RCC_AHB1ENR_GPIODEN);
+
+        PeripheralClock::GpioD.disable(unsafe { &RccRegs::init() });
+
+        assert_eq!(mock_regs::read(AHB1ENR), RCC_AHB1ENR_GPIOAEN);
+    }
+
+    #[test]
+    fn tim9_and_tim11_use_their_own_bits() {
+        // RM0090 RCC_APB2ENR: TIM9EN is bit 16, TIM11EN bit 18
+        assert_eq!(PeripheralClock::Tim9.get_bit_pos(), 0x0001_0000);
+        assert_eq!(PeripheralClock::Tim11.get_bit_pos(), 0x0004_0000);
+
+        let rcc = unsafe { RccRegs::init() };
+        mock_regs::write(APB2ENR, 0);
+        PeripheralClock::Tim9.enable(&rcc);
+        assert_eq!(mock_regs::read(APB2ENR), 0x0001_0000);
+        PeripheralClock::Tim11.enable(&rcc);
+        assert_eq!(mock_regs::read(APB2ENR), 0x0005_0000);
+    }
+}
@@ -0,0 +1,355 @@
+#![allow(dead_code)]
+
+//! PLLI2S set up for audio sample rates
+//!
+//! The I2S clock comes from PLLI2S, which shares its input and the M
+//! divider with the main PLL: I2SCLK = PLL input * PLLI2SN / PLLI2SR. The
+//! SPI/I2S prescaler divides it further by 2 * I2SDIV + ODD and by the
+//! frame length, or by 256 when MCLK is output. `I2sConfig` searches all
+//! PLLI2SN, PLLI2SR and prescaler values for the sample rate closest to
+//! the requested one:
+//!
+//! let setup = I2sConfig::new(48.khz()).format(DataFormat::Bits24)
+//!                                     .master_clock()
+//!                                     .apply(&rcc, Some(8.mhz()), &spi3)?;
+//!
+//! Few rates come out exact, `I2sSetup` tells the achieved rate and the
+//! error. Rates more than `MAX_ERROR_PPM` off are refused, e.g. 192 kHz with
+//! MCLK, which needs an I2SCLK above the maximum. The main PLL must be
+//! configured first, see `hal::rcc`.
+
+use ::hal::rcc::{ self, PLL_IN_MIN, PLL_IN_MAX, VCO_MIN, VCO_MAX, HSI_FREQ };
+use ::hal::time::Hertz;
+use ::stm32f4xx::regs::rcc::*;
+use ::stm32f4xx::regs::spi::*;
+
+pub const SAMPLE_RATE_MIN   : u32   = 8_000;
+pub const SAMPLE_RATE_MAX   : u32   = 192_000;
+
+/// Highest PLLI2S output
+pub const I2SCLK_MAX        : u32   = 192_000_000;
+
+/// Largest sample rate error accepted, 0.1 %; every standard rate but
+/// 192 kHz with MCLK comes out within 0.02 %
+pub const MAX_ERROR_PPM     : i32   = 1_000;
+
+const PLLI2SN_MIN           : u32   = 50;
+const PLLI2SN_MAX           : u32   = 432;
+const PLLI2SR_MIN           : u32   = 2;
+const PLLI2SR_MAX           : u32   = 7;
+
+/// 2 * I2SDIV + ODD, I2SDIV 0 and 1 are not allowed
+const PRESCALER_MIN         : u32   = 4;
+const PRESCALER_MAX         : u32   = 511;
+
+/// I2SCLK cycles per sample with MCLK output, whatever the frame length
+const MCLK_RATIO            : u32   = 256;
+
+/// Why no I2S clock can be set up
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum I2sClockError {
+    /// Sample rate outside 8 to 192 kHz
+    SampleRateOutOfRange,
+    /// No setting comes within `MAX_ERROR_PPM` of the sample rate
+    SampleRateNotReachable,
+    /// The main PLL input, shared with PLLI2S, is not within 1 to 2 MHz
+    PllInputOutOfRange,
+    /// PLLI2S did not lock or did not stop
+    PllI2sNotReady,
+}
+
+/// Bits of data per sample and bits per channel on the wire
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DataFormat {
+    /// 16 bit data in a 16 bit channel
+    Bits16,
+    /// 16 bit data in a 32 bit channel
+    Bits16Extended,
+    Bits24,
+    Bits32,
+}
+
+impl DataFormat {
+    /// Bits per channel, a frame holds two channels
+    pub fn channel_bits(&self) -> u32 {
+        match *self {
+            DataFormat::Bits16 => 16,
+            _ => 32,
+        }
+    }
+
+    /// DATLEN and CHLEN bits of SPI_I2SCFGR
+    pub fn i2scfgr(&self) -> u32 {
+        match *self {
+            DataFormat::Bits16 => 0,
+            DataFormat::Bits16Extended => SPI_I2SCFGR_CHLEN,
+            DataFormat::Bits24 => SPI_I2SCFGR_DATLEN_0 | SPI_I2SCFGR_CHLEN,
+            DataFormat::Bits32 => SPI_I2SCFGR_DATLEN_1 | SPI_I2SCFGR_CHLEN,
+        }
+    }
+}
+
+/// A solved configuration
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct I2sSetup {
+    pub plli2sn     : u32,
+    pub plli2sr     : u32,
+    pub i2sdiv      : u32,
+    pub odd         : bool,
+    pub mclk        : bool,
+    pub i2sclk      : Hertz,
+    /// Achieved sample rate, rounded
+    pub sample_rate : Hertz,
+    /// Achieved rate against the requested one in parts per million
+    pub error_ppm   : i32,
+}
+
+impl I2sSetup {
+    /// Value for SPI_I2SPR
+    pub fn i2spr(&self) -> u32 {
+        let mut i2spr = self.i2sdiv;
+        if self.odd {
+            i2spr |= SPI_I2SPR_ODD;
+        }
+        if self.mclk {
+            i2spr |= SPI_I2SPR_MCKOE;
+        }
+        i2spr
+    }
+}
+
+/// Wanted sample rate and frame, see the module documentation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct I2sConfig {
+    sample_rate : Hertz,
+    format      : DataFormat,
+    mclk        : bool,
+}
+
+impl I2sConfig {
+    /// 16 bit frames without MCLK unless told otherwise
+    pub fn new(sample_rate: Hertz) -> I2sConfig {
+        I2sConfig {
+            sample_rate,
+            format      : DataFormat::Bits16,
+            mclk        : false,
+        }
+    }
+
+    pub fn format(mut self, format: DataFormat) -> I2sConfig {
+        self.format = format;
+        self
+    }
+
+    /// Outputs MCLK at 256 times the sample rate, most codecs need it
+    pub fn master_clock(mut self) -> I2sConfig {
+        self.mclk = true;
+        self
+    }
+
+    /// Works out the best settings for a PLL input of `pll_input` without
+    /// touching the hardware
+    pub fn solve(&self, pll_input: Hertz) -> Result<I2sSetup, I2sClockError> {
+        let Hertz(fs) = self.sample_rate;
+        if !(SAMPLE_RATE_MIN..=SAMPLE_RATE_MAX).contains(&fs) {
+            return Err(I2sClockError::SampleRateOutOfRange);
+        }
+        let Hertz(input) = pll_input;
+        if !(PLL_IN_MIN..=PLL_IN_MAX).contains(&input) {
+            return Err(I2sClockError::PllInputOutOfRange);
+        }
+
+        // I2SCLK cycles per sample for a prescaler of one
+        let ratio = if self.mclk { MCLK_RATIO } else { 2 * self.format.channel_bits() };
+
+        // (error, n, r, prescaler), the error as I2SCLK offset over target
+        let mut best: Option<(u64, u32, u32, u32)> = None;
+        for r in PLLI2SR_MIN..PLLI2SR_MAX + 1 {
+            for n in PLLI2SN_MIN..PLLI2SN_MAX + 1 {
+                let vco = input * n;
+                if !(VCO_MIN..=VCO_MAX).contains(&vco) || vco / r > I2SCLK_MAX {
+                    continue;
+                }
+                let i2sclk = (vco / r) as u64;
+
+                let per_step = (fs * ratio) as u64;
+                let prescaler = ((i2sclk + per_step / 2) / per_step)
+                                    .max(PRESCALER_MIN as u64)
+                                    .min(PRESCALER_MAX as u64);
+                let target = per_step * prescaler;
+                let offset = i2sclk.abs_diff(target);
+                let error = offset * 1_000_000_000 / target;
+
+                if best.is_none_or(|(best_error, ..)| error < best_error) {
+                    best = Some((error, n, r, prescaler as u32));
+                }
+            }
+        }
+
+        // The VCO range always leaves some n for a 1 to 2 MHz input
+        let (_, n, r, prescaler) = best.unwrap();
+        let i2sclk = input * n / r;
+        let per_sample = (ratio * prescaler) as u64;
+        let error_ppm = (i2sclk as i64 * 1_000_000 / (per_sample * fs as u64) as i64)
+                        - 1_000_000;
+        if error_ppm.abs() > MAX_ERROR_PPM as i64 {
+            return Err(I2sClockError::SampleRateNotReachable);
+        }
+
+        Ok(I2sSetup {
+            plli2sn     : n,
+            plli2sr     : r,
+            i2sdiv      : prescaler / 2,
+            odd         : prescaler % 2 != 0,
+            mclk        : self.mclk,
+            i2sclk      : Hertz(i2sclk),
+            sample_rate : Hertz(((i2sclk as u64 + per_sample / 2) / per_sample) as u32),
+            error_ppm   : error_ppm as i32,
+        })
+    }
+
+    /// Sets PLLI2S up for the configuration, selects it as I2S clock and
+    /// puts `spi` in I2S mode with the data format and prescaler; `hse` is
+    /// the crystal frequency if the main PLL runs from it. The I2S
+    /// peripheral must be disabled.
+    pub fn apply(&self, rcc: &RccRegs, hse: Option<Hertz>, spi: &SpiRegs)
+        -> Result<I2sSetup, I2sClockError>
+    {
+        let setup = self.solve(pll_input(rcc, hse))?;
+
+        // PLLI2SCFGR can only change while PLLI2S is off
+        rcc.cr.bit_and(!RCC_CR_PLLI2SON);
+        if !rcc::wait_for(|| rcc.cr.get() & RCC_CR_PLLI2SRDY == 0) {
+            return Err(I2sClockError::PllI2sNotReady);
+        }
+
+        // PLLI2SQ of the STM32F42x feeds the SAI and must keep its value
+        rcc.plli2scfgr.modify(|_, w| unsafe {
+            w.set(plli2scfgr::PllI2sN::new_unchecked(setup.plli2sn))
+             .set(plli2scfgr::PllI2sR::new_unchecked(setup.plli2sr))
+        });
+        rcc.cfgr.modify(|_, w| w.set(cfgr::I2sSrc::PllI2s));
+        rcc.cr.bit_or(RCC_CR_PLLI2SON);
+        if !rcc::wait_for(|| rcc.cr.get() & RCC_CR_PLLI2SRDY != 0) {
+            rcc.cr.bit_and(!RCC_CR_PLLI2SON);
+            return Err(I2sClockError::PllI2sNotReady);
+        }
+
+        let i2scfgr = spi.i2scfgr.get() & !(SPI_I2SCFGR_DATLEN | SPI_I2SCFGR_CHLEN);
+        spi.i2scfgr.set(i2scfgr | SPI_I2SCFGR_I2SMOD | self.format.i2scfgr());
+        spi.i2spr.set(setup.i2spr());
+
+        Ok(setup)
+    }
+}
+
+/// Input of both PLLs after the M divider, 0 if M is not set
+fn pll_input(rcc: &RccRegs, hse: Option<Hertz>) -> Hertz {
+    let pll = rcc.pllcfgr.read();
+    let source = match pll.get::<pllcfgr::PllSrc>() {
+        Some(pllcfgr::PllSrc::Hse) => hse.map_or(0, |Hertz(freq)| freq),
+        _ => HSI_FREQ,
+    };
+    match pll.get::<pllcfgr::PllM>().map_or(0, |m| m.value()) {
+        0 => Hertz(0),
+        m => Hertz(source / m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::hal::rcc::{ ClockConfig, Clocks };
+    use ::hal::time::U32Ext;
+    use ::mock_regs;
+    use ::stm32f4xx::regs::flash::FlashRegs;
+    use ::stm32f4xx::regs::pwr::PwrRegs;
+
+    /// Sample rate the settings give, in millihertz
+    fn rate_millihertz(setup: &I2sSetup, ratio: u32) -> u64 {
+        let prescaler = 2 * setup.i2sdiv + setup.odd as u32;
+        setup.i2sclk.0 as u64 * 1000 / (ratio * prescaler) as u64
+    }
+
+    #[test]
+    fn standard_rates_are_close() {
+        let rates = [8_000, 11_025, 16_000, 22_050, 32_000, 44_100, 48_000, 96_000, 192_000];
+        for &fs in rates.iter() {
+            for &mclk in [false, true].iter() {
+                let config = I2sConfig::new(fs.hz());
+                let (config, ratio) = if mclk { (config.master_clock(), 256) } else { (config, 32) };
+
+                // 192 kHz with MCLK needs an I2SCLK of 196.6 MHz at least
+                if fs == 192_000 && mclk {
+                    assert_eq!(config.solve(1.mhz()), Err(I2sClockError::SampleRateNotReachable));
+                    continue;
+                }
+
+                let setup = config.solve(1.mhz()).unwrap();
+                assert!(setup.i2sdiv >= 2 && setup.i2sdiv <= 255);
+                assert!(setup.i2sclk.0 <= I2SCLK_MAX);
+                assert!(setup.error_ppm.abs() < 200, "{} Hz: {:?}", fs, setup);
+                assert_eq!((rate_millihertz(&setup, ratio) + 500) / 1000,
+                           setup.sample_rate.0 as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn frame_length_sets_the_ratio_without_mclk() {
+        let setup = I2sConfig::new(48.khz()).format(DataFormat::Bits32).solve(1.mhz()).unwrap();
+        assert_eq!(setup.i2spr() & SPI_I2SPR_MCKOE, 0);
+        assert!((rate_millihertz(&setup, 64) as i64 - 48_000_000).abs() < 48_000 * 5 / 10);
+
+        // 8 kHz with 16 bit frames comes out exact, e.g. 32 MHz / (32 * 125)
+        let setup = I2sConfig::new(8.khz()).solve(1.mhz()).unwrap();
+        assert_eq!(rate_millihertz(&setup, 32), 8_000_000);
+        assert_eq!((setup.sample_rate, setup.error_ppm), (8.khz(), 0));
+    }
+
+    #[test]
+    fn impossible_requests_fail() {
+        assert_eq!(I2sConfig::new(4.khz()).solve(1.mhz()),
+                   Err(I2sClockError::SampleRateOutOfRange));
+        assert_eq!(I2sConfig::new(48.khz()).solve(Hertz(0)),
+                   Err(I2sClockError::PllInputOutOfRange));
+    }
+
+    #[test]
+    fn apply_starts_plli2s_and_sets_the_prescaler() {
+        mock_regs::simulate_rcc();
+
+        let rcc = unsafe { RccRegs::init() };
+        ClockConfig::new().use_hse(8.mhz()).sysclk(168.mhz()).require_48mhz()
+            .apply(&rcc, unsafe { &PwrRegs::init() }, unsafe { &FlashRegs::init() }).unwrap();
+
+        let spi3 = unsafe { SpiRegs::init(SpiInst::Spi3) };
+        let setup = I2sConfig::new(48.khz()).format(DataFormat::Bits24).master_clock()
+                        .apply(&rcc, Some(8.mhz()), &spi3).unwrap();
+
+        assert_eq!(spi3.i2spr.get(), setup.i2spr());
+        assert_eq!(spi3.i2scfgr.get(),
+                   SPI_I2SCFGR_I2SMOD | SPI_I2SCFGR_DATLEN_0 | SPI_I2SCFGR_CHLEN);
+        assert_eq!(Clocks::read(&rcc, Some(8.mhz())).i2sclk(), Some(setup.i2sclk));
+    }
+
+    #[test]
+    fn apply_keeps_the_other_plli2s_fields() {
+        // PLLI2SQ = 4 in bits 24..27 on the STM32F42x
+        const PLLI2SCFGR: u32 = ::stm32f4xx::constants::RCC_BASE + 0x84;
+        const PLLI2SQ: u32 = 0x0F00_0000;
+        mock_regs::simulate_rcc();
+        // Reset value, HSI divided by M = 16
+        mock_regs::write(::stm32f4xx::constants::RCC_BASE + 0x04, 0x2400_3010);
+        mock_regs::write(PLLI2SCFGR, 0x2400_3000);
+
+        let rcc = unsafe { RccRegs::init() };
+        let spi3 = unsafe { SpiRegs::init(SpiInst::Spi3) };
+        let setup = I2sConfig::new(48.khz()).apply(&rcc, None, &spi3).unwrap();
+
+        let plli2scfgr = mock_regs::read(PLLI2SCFGR);
+        assert_eq!(plli2scfgr & PLLI2SQ, 0x0400_0000);
+        assert_eq!(plli2scfgr & RCC_PLLI2SCFGR_PLLI2SN, setup.plli2sn << 6);
+        assert_eq!(plli2scfgr & RCC_PLLI2SCFGR_PLLI2SR, setup.plli2sr << 28);
+    }
+}
@@ -0,0 +1,966 @@
+#![allow(dead_code)]
+
+//! System clock configuration
+//!
+//! `ClockConfig` collects the wanted frequencies, works out the PLL
+//! factors, bus prescalers, voltage scale and flash wait states within the
+//! datasheet limits and applies them:
+//!
+//! let setup = ClockConfig::new().use_hse(8.mhz())
+//!                               .sysclk(120.mhz())
+//!                               .pclk1(30.mhz())
+//!                               .require_48mhz()
+//!                               .apply(&rcc, &pwr, &flash)?;
+//!
+//! Frequencies that are not requested default to the fastest allowed. The
+//! requested ones are met exactly or the configuration fails with a
+//! `ClockError`. Over-drive is not supported, SYSCLK stays at 168 MHz at
+//! most on the STM32F42x as well.
+//!
+//! `apply_or_hsi` is the same, but falls back to a PLL from HSI when the
+//! crystal does not start; `clock_fault` keeps what went wrong. With
+//! `clock_security` the Clock Security System watches the crystal while it
+//! is in use. If it stops, the hardware switches SYSCLK to HSI and raises
+//! an NMI, `on_clock_failure` clears it and sets `clock_fault` to
+//! `HseFailed`. The chip then runs at 16 MHz, `Clocks` read before are
+//! stale.
+//!
+//! `enable_mco1` and `enable_mco2` put a clock on PA8 or PC9 for a scope
+//! or frequency counter.
+//!
+//! Drivers take their bus and timer frequencies from `Clocks`, read back
+//! from the RCC registers once the clocks are set up:
+//!
+//! let clocks = Clocks::read(&peripherals.rcc, Some(8.mhz()));
+//! usart.brr.set(clocks.pclk1().0 / 115_200);
+
+use core::sync::atomic::{ AtomicUsize, Ordering };
+
+use ::hal::gpio::{ AltFunc, Pin, PinMode, PinSpeed };
+use ::hal::time::Hertz;
+use ::reg_field::FieldValue;
+use ::stm32f4xx::regs::flash::{ acr, FlashRegs };
+use ::stm32f4xx::regs::pwr::{ self, PwrRegs };
+use ::stm32f4xx::regs::gpio::Port;
+use ::stm32f4xx::regs::rcc::*;
+
+pub use ::stm32f4xx::regs::rcc::cfgr::{ Mco1, Mco2 };
+
+/// Internal RC oscillator
+pub const HSI_FREQ          : u32   = 16_000_000;
+
+/// Crystal range of the HSE oscillator
+pub const HSE_MIN           : u32   = 4_000_000;
+pub const HSE_MAX           : u32   = 26_000_000;
+
+pub const SYSCLK_MAX        : u32   = 168_000_000;
+#[cfg(feature = "stm32f40x")]
+pub const PCLK1_MAX         : u32   = 42_000_000;
+#[cfg(feature = "stm32f40x")]
+pub const PCLK2_MAX         : u32   = 84_000_000;
+#[cfg(feature = "stm32f42x")]
+pub const PCLK1_MAX         : u32   = 45_000_000;
+#[cfg(feature = "stm32f42x")]
+pub const PCLK2_MAX         : u32   = 90_000_000;
+
+/// PLL input after the M divider, 2 MHz gives the least jitter
+pub const PLL_IN_MIN        : u32   = 1_000_000;
+pub const PLL_IN_MAX        : u32   = 2_000_000;
+/// VCO output
+pub const VCO_MIN           : u32   = 100_000_000;
+pub const VCO_MAX           : u32   = 432_000_000;
+
+/// USB OTG FS, SDIO and RNG clock from the PLL Q output
+pub const PLL48_FREQ        : u32   = 48_000_000;
+
+/// HCLK per flash wait state at 2.7 to 3.6 V
+const FLASH_WS_FREQ         : u32   = 30_000_000;
+
+/// Highest HCLK in voltage scale 2 and 3
+const SCALE2_HCLK_MAX       : u32   = 144_000_000;
+const SCALE3_HCLK_MAX       : u32   = 120_000_000;
+
+/// HSE start-up polls before giving up, as in CMSIS
+const HSE_STARTUP_TIMEOUT   : u32   = 0x500;
+
+/// Polls of the other ready flags before giving up, some milliseconds at
+/// 16 MHz; the PLL locks within 0.1 ms
+const READY_TIMEOUT         : u32   = 0x10000;
+
+const HPRE_DIVIDERS         : [u32; 9]  = [1, 2, 4, 8, 16, 64, 128, 256, 512];
+const PPRE_DIVIDERS         : [u32; 5]  = [1, 2, 4, 8, 16];
+const PLLP_DIVIDERS         : [u32; 4]  = [2, 4, 6, 8];
+
+/// Why a clock configuration cannot be used
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ClockError {
+    /// HSE frequency outside 4 to 26 MHz
+    HseOutOfRange,
+    /// SYSCLK above the maximum
+    SysclkOutOfRange,
+    /// No PLL setting gives exactly the requested SYSCLK
+    SysclkNotReachable,
+    /// SYSCLK is reachable, but not together with 48 MHz for USB
+    Pll48NotReachable,
+    /// No AHB prescaler gives exactly the requested HCLK
+    HclkNotReachable,
+    /// PCLK1 above the maximum or not reachable with an APB1 prescaler
+    Pclk1NotReachable,
+    /// PCLK2 above the maximum or not reachable with an APB2 prescaler
+    Pclk2NotReachable,
+    /// The crystal did not start
+    HseNotReady,
+    /// HSI did not report ready
+    HsiNotReady,
+    /// The PLL did not lock or did not stop
+    PllNotReady,
+    /// SWS did not follow the new SYSCLK source
+    SwitchTimeout,
+    /// The crystal stopped while in use and the Clock Security System
+    /// switched SYSCLK to HSI
+    HseFailed,
+}
+
+/// Order of the `ClockError` codes kept by `CLOCK_FAULT`
+const CLOCK_ERRORS          : [ClockError; 12]  = [
+    ClockError::HseOutOfRange,
+    ClockError::SysclkOutOfRange,
+    ClockError::SysclkNotReachable,
+    ClockError::Pll48NotReachable,
+    ClockError::HclkNotReachable,
+    ClockError::Pclk1NotReachable,
+    ClockError::Pclk2NotReachable,
+    ClockError::HseNotReady,
+    ClockError::HsiNotReady,
+    ClockError::PllNotReady,
+    ClockError::SwitchTimeout,
+    ClockError::HseFailed,
+];
+
+/// Oscillator driving SYSCLK or the PLL
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Oscillator {
+    Hsi,
+    Hse,
+}
+
+/// Main PLL factors: VCO = input / m * n, SYSCLK = VCO / p, PLL48CLK = VCO / q
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PllSetup {
+    pub m       : u32,
+    pub n       : u32,
+    pub p       : u32,
+    pub q       : u32,
+}
+
+/// A solved configuration, all dividers as plain numbers
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ClockSetup {
+    pub oscillator      : Oscillator,
+    /// `None` if SYSCLK comes straight from the oscillator
+    pub pll             : Option<PllSetup>,
+    pub sysclk          : Hertz,
+    pub hclk            : Hertz,
+    pub pclk1           : Hertz,
+    pub pclk2           : Hertz,
+    /// PLL Q output, `None` without the PLL
+    pub pll48clk        : Option<Hertz>,
+    pub hpre            : u32,
+    pub ppre1           : u32,
+    pub ppre2           : u32,
+    /// Regulator voltage scale 1, 2 or 3
+    pub voltage_scale   : u32,
+    pub wait_states     : u32,
+}
+
+/// Wanted clock frequencies, see the module documentation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ClockConfig {
+    hse             : Option<Hertz>,
+    sysclk          : Option<Hertz>,
+    hclk            : Option<Hertz>,
+    pclk1           : Option<Hertz>,
+    pclk2           : Option<Hertz>,
+    require_48mhz   : bool,
+    clock_security  : bool,
+}
+
+impl ClockConfig {
+    /// Runs from HSI at 16 MHz unless told otherwise
+    pub fn new() -> ClockConfig {
+        ClockConfig {
+            hse             : None,
+            sysclk          : None,
+            hclk            : None,
+            pclk1           : None,
+            pclk2           : None,
+            require_48mhz   : false,
+            clock_security  : false,
+        }
+    }
+
+    /// Uses a crystal of `freq` on OSC_IN/OSC_OUT instead of HSI
+    pub fn use_hse(mut self, freq: Hertz) -> ClockConfig {
+        self.hse = Some(freq);
+        self
+    }
+
+    pub fn sysclk(mut self, freq: Hertz) -> ClockConfig {
+        self.sysclk = Some(freq);
+        self
+    }
+
+    pub fn hclk(mut self, freq: Hertz) -> ClockConfig {
+        self.hclk = Some(freq);
+        self
+    }
+
+    pub fn pclk1(mut self, freq: Hertz) -> ClockConfig {
+        self.pclk1 = Some(freq);
+        self
+    }
+
+    pub fn pclk2(mut self, freq: Hertz) -> ClockConfig {
+        self.pclk2 = Some(freq);
+        self
+    }
+
+    /// Runs SYSCLK from the PLL so that its Q output gives exactly 48 MHz
+    /// for USB OTG FS, SDIO and RNG
+    pub fn require_48mhz(mut self) -> ClockConfig {
+        self.require_48mhz = true;
+        self
+    }
+
+    /// Enables the Clock Security System once the crystal runs, see the
+    /// module documentation
+    pub fn clock_security(mut self) -> ClockConfig {
+        self.clock_security = true;
+        self
+    }
+
+    /// The same frequencies from HSI instead of the crystal
+    pub fn without_hse(mut self) -> ClockConfig {
+        self.hse = None;
+        self.clock_security = false;
+        self
+    }
+
+    /// Works out the register settings without touching the hardware
+    pub fn solve(&self) -> Result<ClockSetup, ClockError> {
+        let (oscillator, input) = match self.hse {
+            Some(Hertz(freq)) if !(HSE_MIN..=HSE_MAX).contains(&freq) =>
+                return Err(ClockError::HseOutOfRange),
+            Some(Hertz(freq)) => (Oscillator::Hse, freq),
+            None => (Oscillator::Hsi, HSI_FREQ),
+        };
+
+        let sysclk = self.sysclk.map_or(input, |Hertz(freq)| freq);
+        if sysclk > SYSCLK_MAX {
+            return Err(ClockError::SysclkOutOfRange);
+        }
+
+        let pll = if sysclk == input && !self.require_48mhz {
+            None
+        } else {
+            match solve_pll(input, sysclk, self.require_48mhz) {
+                Some(pll) => Some(pll),
+                None if self.require_48mhz && solve_pll(input, sysclk, false).is_some() =>
+                    return Err(ClockError::Pll48NotReachable),
+                None => return Err(ClockError::SysclkNotReachable),
+            }
+        };
+
+        let hpre = divider(sysclk, self.hclk, SYSCLK_MAX, &HPRE_DIVIDERS)
+                       .ok_or(ClockError::HclkNotReachable)?;
+        let hclk = sysclk / hpre;
+        let ppre1 = divider(hclk, self.pclk1, PCLK1_MAX, &PPRE_DIVIDERS)
+                        .ok_or(ClockError::Pclk1NotReachable)?;
+        let ppre2 = divider(hclk, self.pclk2, PCLK2_MAX, &PPRE_DIVIDERS)
+                        .ok_or(ClockError::Pclk2NotReachable)?;
+
+        Ok(ClockSetup {
+            oscillator,
+            pll,
+            sysclk          : Hertz(sysclk),
+            hclk            : Hertz(hclk),
+            pclk1           : Hertz(hclk / ppre1),
+            pclk2           : Hertz(hclk / ppre2),
+            pll48clk        : pll.map(|pll| Hertz(vco(input, &pll) / pll.q)),
+            hpre,
+            ppre1,
+            ppre2,
+            voltage_scale   : voltage_scale(hclk),
+            wait_states     : (hclk - 1) / FLASH_WS_FREQ,
+        })
+    }
+
+    /// Switches to the configuration; SYSCLK runs from HSI in between, so
+    /// no bus is ever clocked too fast
+    pub fn apply(&self, rcc: &RccRegs, pwr: &PwrRegs, flash: &FlashRegs)
+        -> Result<ClockSetup, ClockError>
+    {
+        let setup = self.solve()?;
+
+        // Run from HSI while the PLL is reconfigured
+        rcc.cr.bit_or(RCC_CR_HSION);
+        if !wait_for(|| rcc.cr.get() & RCC_CR_HSIRDY != 0) {
+            return Err(ClockError::HsiNotReady);
+        }
+        rcc.cfgr.modify(|_, w| w.set(cfgr::Sw::Hsi));
+        if !wait_for(|| rcc.cfgr.read().get::<cfgr::Sws>() == Some(cfgr::Sws::Hsi)) {
+            return Err(ClockError::SwitchTimeout);
+        }
+
+        if setup.oscillator == Oscillator::Hse {
+            rcc.cr.bit_or(RCC_CR_HSEON);
+            let mut start_up_counter: u32 = 0;
+            while rcc.cr.get() & RCC_CR_HSERDY == 0 {
+                start_up_counter += 1;
+                if start_up_counter == HSE_STARTUP_TIMEOUT {
+                    rcc.cr.bit_and(!RCC_CR_HSEON);
+                    return Err(ClockError::HseNotReady);
+                }
+            }
+            if self.clock_security {
+                rcc.cr.bit_or(RCC_CR_CSSON);
+            }
+        }
+
+        rcc.cr.bit_and(!RCC_CR_PLLON);
+        if !wait_for(|| rcc.cr.get() & RCC_CR_PLLRDY == 0) {
+            return Err(ClockError::PllNotReady);
+        }
+
+        // The voltage scale can only change while the PLL is off
+        rcc.apb1enr.bit_or(RCC_APB1ENR_PWREN);
+        pwr.cr.modify(|_, w| w.set(vos(setup.voltage_scale)));
+
+        if let Some(pll) = setup.pll {
+            let source = match setup.oscillator {
+                Oscillator::Hsi => pllcfgr::PllSrc::Hsi,
+                Oscillator::Hse => pllcfgr::PllSrc::Hse,
+            };
+            rcc.pllcfgr.write(|w| unsafe {
+                w.set(pllcfgr::PllM::new_unchecked(pll.m))
+                 .set(pllcfgr::PllN::new_unchecked(pll.n))
+                 .set(pllp(pll.p))
+                 .set(source)
+                 .set(pllcfgr::PllQ::new_unchecked(pll.q))
+            });
+            rcc.cr.bit_or(RCC_CR_PLLON);
+            if !wait_for(|| rcc.cr.get() & RCC_CR_PLLRDY != 0) {
+                rcc.cr.bit_and(!RCC_CR_PLLON);
+                return Err(ClockError::PllNotReady);
+            }
+        }
+
+        // More wait states before speeding up, fewer only afterwards
+        let current = flash.acr.read().get::<acr::Latency>().map_or(0, |l| l as u32);
+        if setup.wait_states > current {
+            set_flash(flash, setup.wait_states);
+        }
+
+        rcc.cfgr.modify(|_, w| w.set(hpre(setup.hpre))
+                                .set(ppre1(setup.ppre1))
+                                .set(ppre2(setup.ppre2)));
+
+        let (sw, sws) = match (setup.pll, setup.oscillator) {
+            (Some(_), _) => (cfgr::Sw::Pll, cfgr::Sws::Pll),
+            (None, Oscillator::Hse) => (cfgr::Sw::Hse, cfgr::Sws::Hse),
+            (None, Oscillator::Hsi) => (cfgr::Sw::Hsi, cfgr::Sws::Hsi),
+        };
+        rcc.cfgr.modify(|_, w| w.set(sw));
+        if !wait_for(|| rcc.cfgr.read().get::<cfgr::Sws>() == Some(sws)) {
+            return Err(ClockError::SwitchTimeout);
+        }
+
+        set_flash(flash, setup.wait_states);
+
+        Ok(setup)
+    }
+
+    /// Like `apply`, but if the crystal does not start or the PLL does not
+    /// lock on it, sets up the same frequencies from HSI. The error is kept
+    /// for `clock_fault`, the returned setup tells which oscillator is used.
+    pub fn apply_or_hsi(&self, rcc: &RccRegs, pwr: &PwrRegs, flash: &FlashRegs)
+        -> Result<ClockSetup, ClockError>
+    {
+        match self.apply(rcc, pwr, flash) {
+            Err(error @ ClockError::HseNotReady) | Err(error @ ClockError::PllNotReady)
+                if self.hse.is_some() => {
+                set_clock_fault(error);
+                rcc.cr.bit_and(!(RCC_CR_HSEON | RCC_CR_CSSON));
+                self.without_hse().apply(rcc, pwr, flash)
+            }
+            result => result,
+        }
+    }
+
+    /// Applies the configuration and returns the resulting clocks
+    pub fn freeze(&self, rcc: &RccRegs, pwr: &PwrRegs, flash: &FlashRegs)
+        -> Result<Clocks, ClockError>
+    {
+        self.apply(rcc, pwr, flash)?;
+        Ok(Clocks::read(rcc, self.hse))
+    }
+}
+
+/// Code + 1 of the last `ClockError` of `apply_or_hsi` or the Clock
+/// Security System, 0 for none
+static CLOCK_FAULT: AtomicUsize = AtomicUsize::new(0);
+
+fn set_clock_fault(error: ClockError) {
+    let code = CLOCK_ERRORS.iter().position(|&e| e == error).unwrap_or(0);
+    CLOCK_FAULT.store(code + 1, Ordering::SeqCst);
+}
+
+/// What made the clocks fall back to HSI, `None` while they run as
+/// configured
+pub fn clock_fault() -> Option<ClockError> {
+    match CLOCK_FAULT.load(Ordering::SeqCst) {
+        0 => None,
+        code => CLOCK_ERRORS.get(code - 1).cloned(),
+    }
+}
+
+/// Handles the Clock Security System NMI, bound to `NMI` in main.rs with
+/// the `clock-security` feature
+///
+/// The hardware has already stopped HSE and the PLL and switched SYSCLK to
+/// HSI; this clears the interrupt, which would otherwise fire again at
+/// once, and notes the failure for `clock_fault`. It leaves CFGR alone, an
+/// NMI cannot be masked and could cut into a read-modify-write of it. Any
+/// other NMI, e.g. a software one, panics.
+pub fn on_clock_failure() {
+    // Nothing but this handler writes CIR after system_init
+    let rcc = unsafe { RccRegs::init() };
+    if rcc.cir.get() & RCC_CIR_CSSF == 0 {
+        panic!("NMI without a clock failure");
+    }
+    rcc.cir.bit_or(RCC_CIR_CSSC);
+    set_clock_fault(ClockError::HseFailed);
+}
+
+/// Divider of a clock output
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum McoPrescaler {
+    Div1,
+    Div2,
+    Div3,
+    Div4,
+    Div5,
+}
+
+/// Outputs `source` divided by `prescaler` on MCO1, `pin` must be PA8
+pub fn enable_mco1(rcc: &RccRegs, pin: &mut Pin, source: Mco1, prescaler: McoPrescaler) {
+    assert!(pin.port() == Port::GpioA && pin.pin_num() == 8, "MCO1 is on PA8");
+
+    // Source and prescaler first, they should not change while the pin
+    // outputs the clock
+    let pre = match prescaler {
+        McoPrescaler::Div1 => cfgr::Mco1Pre::Div1,
+        McoPrescaler::Div2 => cfgr::Mco1Pre::Div2,
+        McoPrescaler::Div3 => cfgr::Mco1Pre::Div3,
+        McoPrescaler::Div4 => cfgr::Mco1Pre::Div4,
+        McoPrescaler::Div5 => cfgr::Mco1Pre::Div5,
+    };
+    rcc.cfgr.modify(|_, w| w.set(source).set(pre));
+    mco_pin(rcc, pin);
+}
+
+/// Outputs `source` divided by `prescaler` on MCO2, `pin` must be PC9
+pub fn enable_mco2(rcc: &RccRegs, pin: &mut Pin, source: Mco2, prescaler: McoPrescaler) {
+    assert!(pin.port() == Port::GpioC && pin.pin_num() == 9, "MCO2 is on PC9");
+
+    let pre = match prescaler {
+        McoPrescaler::Div1 => cfgr::Mco2Pre::Div1,
+        McoPrescaler::Div2 => cfgr::Mco2Pre::Div2,
+        McoPrescaler::Div3 => cfgr::Mco2Pre::Div3,
+        McoPrescaler::Div4 => cfgr::Mco2Pre::Div4,
+        McoPrescaler::Div5 => cfgr::Mco2Pre::Div5,
+    };
+    rcc.cfgr.modify(|_, w| w.set(source).set(pre));
+    mco_pin(rcc, pin);
+}
+
+/// MCO1 and MCO2 are AF0, fast enough for up to 100 MHz
+fn mco_pin(rcc: &RccRegs, pin: &mut Pin) {
+    pin.enable_clock(rcc);
+    pin.setup_speed(PinSpeed::VeryHigh);
+    pin.setup_alt_func(AltFunc::Af0);
+    pin.setup_mode(PinMode::AltFunc);
+}
+
+/// Polls `ready` up to `READY_TIMEOUT` times, false if it never held
+pub fn wait_for<F>(ready: F) -> bool
+    where F: Fn() -> bool
+{
+    (0..READY_TIMEOUT).any(|_| ready())
+}
+
+/// Frequencies the chip actually runs at
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Clocks {
+    sysclk      : Hertz,
+    hclk        : Hertz,
+    pclk1       : Hertz,
+    pclk2       : Hertz,
+    timclk1     : Hertz,
+    timclk2     : Hertz,
+    pll48clk    : Option<Hertz>,
+    i2sclk      : Option<Hertz>,
+}
+
+impl Clocks {
+    /// Works the frequencies out from CR, CFGR, PLLCFGR and PLLI2SCFGR;
+    /// `hse` is the crystal frequency, the registers cannot tell it
+    pub fn read(rcc: &RccRegs, hse: Option<Hertz>) -> Clocks {
+        let cr = rcc.cr.get();
+        let cfgr = rcc.cfgr.read();
+        let pll = rcc.pllcfgr.read();
+        let plli2s = rcc.plli2scfgr.read();
+        let hse = hse.map_or(0, |Hertz(freq)| freq) as u64;
+
+        // Both PLLs share the input and the M divider
+        let pll_in = match pll.get::<pllcfgr::PllSrc>() {
+            Some(pllcfgr::PllSrc::Hse) => hse,
+            _ => HSI_FREQ as u64,
+        };
+        let m = pll.get::<pllcfgr::PllM>().map_or(0, |m| m.value()) as u64;
+        let vco = |n: u64| (pll_in * n).checked_div(m).unwrap_or(0);
+
+        let pll_vco = vco(pll.get::<pllcfgr::PllN>().map_or(0, |n| n.value()) as u64);
+        let pllp = match pll.get::<pllcfgr::PllP>() {
+            Some(pllcfgr::PllP::Div4) => 4,
+            Some(pllcfgr::PllP::Div6) => 6,
+            Some(pllcfgr::PllP::Div8) => 8,
+            _ => 2,
+        };
+
+        let sysclk = match cfgr.get::<cfgr::Sws>() {
+            Some(cfgr::Sws::Hse) => hse,
+            Some(cfgr::Sws::Pll) => pll_vco / pllp,
+            _ => HSI_FREQ as u64,
+        } as u32;
+
+        // Reserved encodings divide by one
+        let hclk = sysclk / match cfgr.get::<cfgr::Hpre>() {
+            Some(hpre) => 1 << hpre_shift(hpre),
+            None => 1,
+        };
+        let ppre1 = cfgr.get::<cfgr::Ppre1>().map_or(1, |ppre| 1 << ppre_shift(ppre.bits()));
+        let ppre2 = cfgr.get::<cfgr::Ppre2>().map_or(1, |ppre| 1 << ppre_shift(ppre.bits()));
+
+        let q = pll.get::<pllcfgr::PllQ>().map_or(0, |q| q.value()) as u64;
+        let pll48clk = if cr & RCC_CR_PLLRDY != 0 && q >= 2 {
+            Some(Hertz((pll_vco / q) as u32))
+        } else {
+            None
+        };
+
+        // I2S_CKIN is an external clock of unknown frequency
+        let r = plli2s.get::<plli2scfgr::PllI2sR>().map_or(0, |r| r.value()) as u64;
+        let i2sclk = match cfgr.get::<cfgr::I2sSrc>() {
+            Some(cfgr::I2sSrc::PllI2s) if cr & RCC_CR_PLLI2SRDY != 0 && r >= 2 => {
+                let n = plli2s.get::<plli2scfgr::PllI2sN>().map_or(0, |n| n.value()) as u64;
+                Some(Hertz((vco(n) / r) as u32))
+            },
+            _ => None,
+        };
+
+        Clocks {
+            sysclk      : Hertz(sysclk),
+            hclk        : Hertz(hclk),
+            pclk1       : Hertz(hclk / ppre1),
+            pclk2       : Hertz(hclk / ppre2),
+            timclk1     : Hertz(timer_clock(hclk, ppre1)),
+            timclk2     : Hertz(timer_clock(hclk, ppre2)),
+            pll48clk,
+            i2sclk,
+        }
+    }
+
+    pub fn sysclk(&self) -> Hertz {
+        self.sysclk
+    }
+
+    /// AHB clock, core and DMA
+    pub fn hclk(&self) -> Hertz {
+        self.hclk
+    }
+
+    /// APB1 peripheral clock
+    pub fn pclk1(&self) -> Hertz {
+        self.pclk1
+    }
+
+    /// APB2 peripheral clock
+    pub fn pclk2(&self) -> Hertz {
+        self.pclk2
+    }
+
+    /// Clock of the timers on APB1: TIM2..7 and TIM12..14
+    pub fn timclk1(&self) -> Hertz {
+        self.timclk1
+    }
+
+    /// Clock of the timers on APB2: TIM1, TIM8 and TIM9..11
+    pub fn timclk2(&self) -> Hertz {
+        self.timclk2
+    }
+
+    /// USB OTG FS, SDIO and RNG clock, `None` while the PLL is off
+    pub fn pll48clk(&self) -> Option<Hertz> {
+        self.pll48clk
+    }
+
+    /// I2S clock, `None` while PLLI2S is off or I2S_CKIN is selected
+    pub fn i2sclk(&self) -> Option<Hertz> {
+        self.i2sclk
+    }
+}
+
+/// Timers run at twice the APB clock unless the APB prescaler is 1
+fn timer_clock(hclk: u32, ppre: u32) -> u32 {
+    if ppre == 1 { hclk } else { 2 * hclk / ppre }
+}
+
+/// log2 of the AHB divider
+fn hpre_shift(hpre: cfgr::Hpre) -> u32 {
+    match hpre.bits() {
+        0 => 0,
+        // 32 is skipped
+        bits if bits >= 12 => bits - 6,
+        bits => bits - 7,
+    }
+}
+
+/// log2 of the APB divider from the PPRE1 or PPRE2 bits
+fn ppre_shift(bits: u32) -> u32 {
+    if bits < 4 { 0 } else { bits - 3 }
+}
+
+/// Finds PLL factors giving exactly `sysclk` from `input`, and 48 MHz on the
+/// Q output if `need_48mhz`; the smallest M, the fastest PLL input, wins
+fn solve_pll(input: u32, sysclk: u32, need_48mhz: bool) -> Option<PllSetup> {
+    let input = input as u64;
+    for m in 2..64u64 {
+        if input < PLL_IN_MIN as u64 * m {
+            break;
+        }
+        if input > PLL_IN_MAX as u64 * m {
+            continue;
+        }
+
+        for &p in PLLP_DIVIDERS.iter() {
+            let vco = sysclk as u64 * p as u64;
+            if !(VCO_MIN as u64..=VCO_MAX as u64).contains(&vco)
+                || !(vco * m).is_multiple_of(input) {
+                continue;
+            }
+            let n = vco * m / input;
+            if !(50..=432).contains(&n) {
+                continue;
+            }
+
+            // Otherwise the slowest Q output not above 48 MHz
+            let q = if need_48mhz {
+                if !vco.is_multiple_of(PLL48_FREQ as u64) {
+                    continue;
+                }
+                vco / PLL48_FREQ as u64
+            } else {
+                vco.div_ceil(PLL48_FREQ as u64)
+            };
+            if !(2..=15).contains(&q) {
+                continue;
+            }
+
+            return Some(PllSetup { m: m as u32, n: n as u32, p, q: q as u32 });
+        }
+    }
+    None
+}
+
+fn vco(input: u32, pll: &PllSetup) -> u32 {
+    (input as u64 * pll.n as u64 / pll.m as u64) as u32
+}
+
+/// The divider giving exactly `wanted`, or the smallest one staying within
+/// `max` if nothing was asked for
+fn divider(input: u32, wanted: Option<Hertz>, max: u32, dividers: &[u32]) -> Option<u32> {
+    match wanted {
+        Some(Hertz(freq)) if freq > max => None,
+        Some(Hertz(freq)) =>
+            dividers.iter().cloned().find(|&div| input.is_multiple_of(div) && input / div == freq),
+        None => dividers.iter().cloned().find(|&div| input / div <= max),
+    }
+}
+
+#[cfg(feature = "stm32f40x")]
+fn voltage_scale(hclk: u32) -> u32 {
+    if hclk > SCALE2_HCLK_MAX { 1 } else { 2 }
+}
+
+#[cfg(feature = "stm32f42x")]
+fn voltage_scale(hclk: u32) -> u32 {
+    if hclk > SCALE2_HCLK_MAX {
+        1
+    } else if hclk > SCALE3_HCLK_MAX {
+        2
+    } else {
+        3
+    }
+}
+
+#[cfg(feature = "stm32f40x")]
+fn vos(scale: u32) -> pwr::cr::Vos {
+    match scale {
+        1 => pwr::cr::Vos::Scale1,
+        _ => pwr::cr::Vos::Scale2,
+    }
+}
+
+#[cfg(feature = "stm32f42x")]
+fn vos(scale: u32) -> pwr::cr::Vos {
+    match scale {
+        1 => pwr::cr::Vos::Scale1,
+        2 => pwr::cr::Vos::Scale2,
+        _ => pwr::cr::Vos::Scale3,
+    }
+}
+
+fn set_flash(flash: &FlashRegs, wait_states: u32) {
+    let latency = match wait_states {
+        0 => acr::Latency::Ws0,
+        1 => acr::Latency::Ws1,
+        2 => acr::Latency::Ws2,
+        3 => acr::Latency::Ws3,
+        4 => acr::Latency::Ws4,
+        5 => acr::Latency::Ws5,
+        6 => acr::Latency::Ws6,
+        _ => acr::Latency::Ws7,
+    };
+    flash.acr.write(|w| w.set(acr::IcEn(true))
+                         .set(acr::DcEn(true))
+                         .set(latency));
+}
+
+fn pllp(p: u32) -> pllcfgr::PllP {
+    match p {
+        2 => pllcfgr::PllP::Div2,
+        4 => pllcfgr::PllP::Div4,
+        6 => pllcfgr::PllP::Div6,
+        _ => pllcfgr::PllP::Div8,
+    }
+}
+
+fn hpre(div: u32) -> cfgr::Hpre {
+    match div {
+        1 => cfgr::Hpre::Div1,
+        2 => cfgr::Hpre::Div2,
+        4 => cfgr::Hpre::Div4,
+        8 => cfgr::Hpre::Div8,
+        16 => cfgr::Hpre::Div16,
+        64 => cfgr::Hpre::Div64,
+        128 => cfgr::Hpre::Div128,
+        256 => cfgr::Hpre::Div256,
+        _ => cfgr::Hpre::Div512,
+    }
+}
+
+fn ppre1(div: u32) -> cfgr::Ppre1 {
+    match div {
+        1 => cfgr::Ppre1::Div1,
+        2 => cfgr::Ppre1::Div2,
+        4 => cfgr::Ppre1::Div4,
+        8 => cfgr::Ppre1::Div8,
+        _ => cfgr::Ppre1::Div16,
+    }
+}
+
+fn ppre2(div: u32) -> cfgr::Ppre2 {
+    match div {
+        1 => cfgr::Ppre2::Div1,
+        2 => cfgr::Ppre2::Div2,
+        4 => cfgr::Ppre2::Div4,
+        8 => cfgr::Ppre2::Div8,
+        _ => cfgr::Ppre2::Div16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::hal::time::U32Ext;
+    use ::mock_regs;
+
+    #[test]
+    fn discovery_board_at_168mhz() {
+        let setup = ClockConfig::new().use_hse(8.mhz())
+                                      .sysclk(168.mhz())
+                                      .require_48mhz()
+                                      .solve().unwrap();
+
+        assert_eq!(setup.pll, Some(PllSetup { m: 4, n: 168, p: 2, q: 7 }));
+        assert_eq!(setup.pll48clk, Some(48.mhz()));
+        assert_eq!((setup.hclk, setup.pclk1, setup.pclk2), (168.mhz(), 42.mhz(), 84.mhz()));
+        assert_eq!((setup.voltage_scale, setup.wait_states), (1, 5));
+    }
+
+    #[test]
+    fn requested_bus_clocks_are_met_exactly() {
+        let setup = ClockConfig::new().sysclk(120.mhz())
+                                      .hclk(60.mhz())
+                                      .pclk1(15.mhz())
+                                      .solve().unwrap();
+
+        assert_eq!(setup.oscillator, Oscillator::Hsi);
+        assert_eq!((setup.hpre, setup.ppre1, setup.ppre2), (2, 4, 1));
+        assert_eq!(setup.wait_states, 1);
+
+        // Straight from HSI, no PLL needed
+        let setup = ClockConfig::new().solve().unwrap();
+        assert_eq!((setup.pll, setup.sysclk, setup.wait_states), (None, 16.mhz(), 0));
+    }
+
+    #[test]
+    fn impossible_requests_fail() {
+        let hse = ClockConfig::new().use_hse(8.mhz());
+
+        assert_eq!(hse.sysclk(180.mhz()).solve(), Err(ClockError::SysclkOutOfRange));
+        assert_eq!(hse.sysclk(100.mhz()).require_48mhz().solve(),
+                   Err(ClockError::Pll48NotReachable));
+        assert_eq!(hse.sysclk(168.mhz()).pclk1(84.mhz()).solve(),
+                   Err(ClockError::Pclk1NotReachable));
+        assert_eq!(hse.sysclk(168.mhz()).hclk(100.mhz()).solve(),
+                   Err(ClockError::HclkNotReachable));
+        assert_eq!(ClockConfig::new().use_hse(30.mhz()).solve(), Err(ClockError::HseOutOfRange));
+    }
+
+    #[test]
+    fn clocks_are_read_back_from_the_registers() {
+        mock_regs::simulate_rcc();
+
+        let rcc = unsafe { RccRegs::init() };
+        let clocks = ClockConfig::new().use_hse(8.mhz()).sysclk(168.mhz()).require_48mhz()
+                         .freeze(&rcc, unsafe { &PwrRegs::init() }, unsafe { &FlashRegs::init() })
+                         .unwrap();
+
+        assert_eq!((clocks.sysclk(), clocks.hclk()), (168.mhz(), 168.mhz()));
+        assert_eq!((clocks.pclk1(), clocks.pclk2()), (42.mhz(), 84.mhz()));
+        assert_eq!((clocks.timclk1(), clocks.timclk2()), (84.mhz(), 168.mhz()));
+        assert_eq!(clocks.pll48clk(), Some(48.mhz()));
+        assert_eq!(clocks.i2sclk(), None);
+    }
+
+    #[test]
+    fn prescalers_decode_to_dividers() {
+        assert_eq!(hpre_shift(cfgr::Hpre::Div1), 0);
+        assert_eq!(hpre_shift(cfgr::Hpre::Div16), 4);
+        assert_eq!(hpre_shift(cfgr::Hpre::Div64), 6);
+        assert_eq!(hpre_shift(cfgr::Hpre::Div512), 9);
+        assert_eq!(1 << ppre_shift(cfgr::Ppre1::Div8.bits()), 8);
+        assert_eq!(timer_clock(84_000_000, 1), 84_000_000);
+    }
+
+    #[test]
+    fn apply_raises_wait_states_before_switching() {
+        mock_regs::simulate_rcc();
+        mock_regs::start_trace();
+
+        let setup = ClockConfig::new().use_hse(8.mhz()).sysclk(168.mhz())
+                        .apply(unsafe { &RccRegs::init() }, unsafe { &PwrRegs::init() },
+                               unsafe { &FlashRegs::init() }).unwrap();
+
+        let trace = mock_regs::take_trace();
+        let latency = trace.iter().position(|a| a.kind == mock_regs::AccessKind::Write
+                                                && a.address == ::stm32f4xx::constants::FLASH_R_BASE
+                                                && a.new & 0x7 == 5).unwrap();
+        let switch = trace.iter().position(|a| a.kind == mock_regs::AccessKind::Write
+                                               && a.address == ::stm32f4xx::constants::RCC_BASE + 0x08
+                                               && a.new & RCC_CFGR_SW == RCC_CFGR_SW_PLL).unwrap();
+        assert!(latency < switch);
+        assert_eq!(setup.sysclk, 168.mhz());
+    }
+
+    #[test]
+    fn mco2_outputs_sysclk_on_pc9() {
+        use ::hal::gpio::Pins;
+        use ::stm32f4xx::regs::gpio::GpioRegs;
+
+        let rcc = unsafe { RccRegs::init() };
+        let mut gpioc = unsafe { GpioRegs::init(&Port::GpioC) };
+        let mut pc9 = Pins::split(&mut gpioc).p9;
+        enable_mco2(&rcc, &mut pc9, Mco2::SysClk, McoPrescaler::Div4);
+
+        let cfgr = rcc.cfgr.get();
+        assert_eq!(cfgr & (RCC_CFGR_MCO2 | RCC_CFGR_MCO2PRE),
+                   RCC_CFGR_MCO2PRE_1 | RCC_CFGR_MCO2PRE_2);
+        // AF0 in AFRH, alternate function mode
+        let base = ::stm32f4xx::constants::GPIOC_BASE;
+        assert_eq!(mock_regs::read(base + 0x24) & 0xF0, 0);
+        assert_eq!(mock_regs::read(base) >> 18 & 0x3, 0x2);
+    }
+
+    #[test]
+    #[should_panic(expected = "MCO1 is on PA8")]
+    fn mco1_on_the_wrong_pin_panics() {
+        use ::hal::gpio::Pins;
+        use ::stm32f4xx::regs::gpio::GpioRegs;
+
+        let mut gpioc = unsafe { GpioRegs::init(&Port::GpioC) };
+        let mut pc9 = Pins::split(&mut gpioc).p9;
+        enable_mco1(unsafe { &RccRegs::init() }, &mut pc9, Mco1::Hse, McoPrescaler::Div1);
+    }
+
+    #[test]
+    fn dead_crystal_falls_back_to_hsi_and_is_reported() {
+        fn dead_crystal(value: u32) -> u32 {
+            let mut value = value & !(RCC_CR_HSIRDY | RCC_CR_HSERDY | RCC_CR_PLLRDY);
+            if value & RCC_CR_HSION != 0 { value |= RCC_CR_HSIRDY; }
+            if value & RCC_CR_PLLON != 0 { value |= RCC_CR_PLLRDY; }
+            value
+        }
+
+        mock_regs::simulate_rcc();
+        mock_regs::set_hook(::stm32f4xx::constants::RCC_BASE, dead_crystal);
+
+        let rcc = unsafe { RccRegs::init() };
+        let config = ClockConfig::new().use_hse(8.mhz()).sysclk(168.mhz()).require_48mhz()
+                                       .clock_security();
+        let setup = config.apply_or_hsi(&rcc, unsafe { &PwrRegs::init() },
+                                        unsafe { &FlashRegs::init() }).unwrap();
+
+        assert_eq!(setup.oscillator, Oscillator::Hsi);
+        assert_eq!((setup.sysclk, setup.pll48clk), (168.mhz(), Some(48.mhz())));
+        assert_eq!(rcc.cr.get() & (RCC_CR_HSEON | RCC_CR_CSSON), 0);
+        assert_eq!(clock_fault(), Some(ClockError::HseNotReady));
+
+        // The CSS NMI replaces the start-up error
+        mock_regs::write(::stm32f4xx::constants::RCC_BASE + 0x0C, RCC_CIR_CSSF);
+        on_clock_failure();
+        assert_ne!(rcc.cir.get() & RCC_CIR_CSSC, 0);
+        assert_eq!(clock_fault(), Some(ClockError::HseFailed));
+    }
+
+    #[test]
+    #[should_panic(expected = "NMI without a clock failure")]
+    fn other_nmis_panic() {
+        on_clock_failure();
+    }
+
+    #[test]
+    fn stuck_pll_times_out() {
+        mock_regs::set_hook(::stm32f4xx::constants::RCC_BASE,
+                            |value| value | RCC_CR_HSIRDY | RCC_CR_HSERDY);
+        mock_regs::set_hook(::stm32f4xx::constants::RCC_BASE + 0x08,
+                            |value| (value & !RCC_CFGR_SWS) | ((value & RCC_CFGR_SW) << 2));
+
+        let result = ClockConfig::new().sysclk(168.mhz())
+                         .apply(unsafe { &RccRegs::init() }, unsafe { &PwrRegs::init() },
+                                unsafe { &FlashRegs::init() });
+        assert_eq!(result, Err(ClockError::PllNotReady));
+    }
+}
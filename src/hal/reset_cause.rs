@@ -0,0 +1,109 @@
+#![allow(dead_code)]
+
+//! Why the chip was reset, decoded from the RCC_CSR reset flags
+//!
+//! The flags stay set across resets until RMVF clears them, so `capture`
+//! reads and clears them once early in `system_init` and keeps them for
+//! `reset_cause`.
+
+use core::sync::atomic::{ AtomicUsize, Ordering };
+
+use ::stm32f4xx::regs::rcc::*;
+
+/// All reset flags of RCC_CSR
+pub const RESET_FLAGS_MSK   : u32   = RCC_CSR_BORRSTF | RCC_CSR_PADRSTF | RCC_CSR_PORRSTF
+                                      | RCC_CSR_SFTRSTF | RCC_CSR_WDGRSTF | RCC_CSR_WWDGRSTF
+                                      | RCC_CSR_LPWRRSTF;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResetCause {
+    /// Power-on or power-down reset
+    PowerOn,
+    /// Supply voltage below the brown-out threshold
+    BrownOut,
+    /// NRST pin pulled low, e.g. the reset button or a debugger
+    Pin,
+    /// SYSRESETREQ, see `core_cm4::reset::system_reset`
+    Software,
+    IndependentWatchdog,
+    WindowWatchdog,
+    /// Entering Standby or Stop mode while the option bytes forbid it
+    LowPower,
+    /// No flag set, the flags were already cleared
+    Unknown,
+}
+
+impl ResetCause {
+    /// Decodes the RCC_CSR flags. Every reset drives NRST and sets the pin
+    /// flag, and a power-on also sets the brown-out flag, so the most
+    /// specific flag wins.
+    pub fn from_flags(csr: u32) -> ResetCause {
+        if csr & RCC_CSR_LPWRRSTF != 0 {
+            ResetCause::LowPower
+        } else if csr & RCC_CSR_WWDGRSTF != 0 {
+            ResetCause::WindowWatchdog
+        } else if csr & RCC_CSR_WDGRSTF != 0 {
+            ResetCause::IndependentWatchdog
+        } else if csr & RCC_CSR_SFTRSTF != 0 {
+            ResetCause::Software
+        } else if csr & RCC_CSR_PORRSTF != 0 {
+            ResetCause::PowerOn
+        } else if csr & RCC_CSR_BORRSTF != 0 {
+            ResetCause::BrownOut
+        } else if csr & RCC_CSR_PADRSTF != 0 {
+            ResetCause::Pin
+        } else {
+            ResetCause::Unknown
+        }
+    }
+}
+
+static FLAGS: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps the reset flags and clears them in RCC_CSR, called by
+/// `system_init`
+pub fn capture(rcc: &RccRegs) -> ResetCause {
+    let flags = rcc.csr.get() & RESET_FLAGS_MSK;
+    FLAGS.store(flags as usize, Ordering::SeqCst);
+    rcc.csr.bit_or(RCC_CSR_RMVF);
+    ResetCause::from_flags(flags)
+}
+
+/// Why the chip was reset last
+pub fn reset_cause() -> ResetCause {
+    ResetCause::from_flags(reset_flags())
+}
+
+/// The RCC_CSR reset flags found at boot, for when several matter
+pub fn reset_flags() -> u32 {
+    FLAGS.load(Ordering::SeqCst) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::stm32f4xx::constants::RCC_BASE;
+
+    const CSR       : u32   = RCC_BASE + 0x74;
+
+    #[test]
+    fn most_specific_flag_wins() {
+        assert_eq!(ResetCause::from_flags(RCC_CSR_PORRSTF | RCC_CSR_BORRSTF | RCC_CSR_PADRSTF),
+                   ResetCause::PowerOn);
+        assert_eq!(ResetCause::from_flags(RCC_CSR_BORRSTF | RCC_CSR_PADRSTF), ResetCause::BrownOut);
+        assert_eq!(ResetCause::from_flags(RCC_CSR_WDGRSTF | RCC_CSR_PADRSTF),
+                   ResetCause::IndependentWatchdog);
+        assert_eq!(ResetCause::from_flags(RCC_CSR_PADRSTF), ResetCause::Pin);
+        assert_eq!(ResetCause::from_flags(RCC_CSR_LSION), ResetCause::Unknown);
+    }
+
+    #[test]
+    fn capture_keeps_flags_and_clears_them() {
+        mock_regs::write(CSR, RCC_CSR_SFTRSTF | RCC_CSR_PADRSTF | RCC_CSR_LSION);
+
+        assert_eq!(capture(unsafe { &RccRegs::init() }), ResetCause::Software);
+        assert_eq!(mock_regs::read(CSR), RCC_CSR_SFTRSTF | RCC_CSR_PADRSTF | RCC_CSR_LSION
+                                         | RCC_CSR_RMVF);
+    }
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+//! Frequencies
+//!
+//! let sysclk = 168.mhz();
+
+/// A frequency in Hz
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Hertz(pub u32);
+
+/// Builds frequencies from integers
+pub trait U32Ext {
+    fn hz(self) -> Hertz;
+    fn khz(self) -> Hertz;
+    fn mhz(self) -> Hertz;
+}
+
+impl U32Ext for u32 {
+    fn hz(self) -> Hertz {
+        Hertz(self)
+    }
+
+    fn khz(self) -> Hertz {
+        Hertz(self * 1_000)
+    }
+
+    fn mhz(self) -> Hertz {
+        Hertz(self * 1_000_000)
+    }
+}
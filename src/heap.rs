@@ -0,0 +1,340 @@
+#![allow(dead_code)]
+
+//! Heap for the `alloc` crate
+//!
+//! With the `heap` feature a first fit allocator over the `_sheap`..`_eheap`
+//! region of the linker script becomes the global allocator, so `Box`, `Vec`
+//! and `String` can be used. The size comes from `HEAP_SIZE` (16 KB by
+//! default with the feature, see build.rs), the `heap-in-ccmram` feature
+//! moves the heap to CCM RAM, which DMA cannot reach.
+//!
+//! Running out of memory panics with the size of the failed request.
+//! `stats` tells how much of the heap is in use and how fragmented it is.
+
+use core::alloc::{ GlobalAlloc, Layout };
+use core::cell::UnsafeCell;
+use core::{ cmp, mem, ptr };
+
+use ::core_cm4::interrupt;
+
+/// Free block, stored in the block itself; the list is sorted by address
+struct Hole {
+    size    : usize,
+    next    : *mut Hole,
+}
+
+/// Allocation granularity, every block and hole is a multiple of it
+const UNIT  : usize = mem::size_of::<Hole>();
+
+/// Heap usage, all sizes in bytes
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct HeapStats {
+    pub size            : usize,
+    pub used            : usize,
+    /// Most bytes ever in use at once
+    pub peak            : usize,
+    /// Largest block that can still be allocated
+    pub largest_free    : usize,
+    /// Successful allocations so far
+    pub allocations     : usize,
+    /// Allocations that failed for lack of memory
+    pub failures        : usize,
+}
+
+/// First fit allocator with a free list, freed blocks are merged with
+/// their neighbours
+pub struct Heap {
+    holes       : *mut Hole,
+    size        : usize,
+    used        : usize,
+    peak        : usize,
+    allocations : usize,
+    failures    : usize,
+}
+
+fn align_up(address: usize, align: usize) -> usize {
+    (address + align - 1) & !(align - 1)
+}
+
+/// Block size and alignment actually used for `layout`
+fn block(layout: &Layout) -> (usize, usize) {
+    (align_up(cmp::max(layout.size(), UNIT), UNIT), cmp::max(layout.align(), UNIT))
+}
+
+impl Heap {
+    pub const fn empty() -> Heap {
+        Heap {
+            holes       : ptr::null_mut(),
+            size        : 0,
+            used        : 0,
+            peak        : 0,
+            allocations : 0,
+            failures    : 0,
+        }
+    }
+
+    /// Hands `size` bytes at `start` to the heap; the memory must not be
+    /// used for anything else
+    pub unsafe fn init(&mut self, start: usize, size: usize) {
+        let aligned = align_up(start, UNIT);
+        let size = size.saturating_sub(aligned - start) & !(UNIT - 1);
+
+        self.holes = ptr::null_mut();
+        self.size = size;
+        if size > 0 {
+            self.holes = aligned as *mut Hole;
+            ptr::write(self.holes, Hole { size, next: ptr::null_mut() });
+        }
+    }
+
+    /// Start of a block for `layout`, null if no hole is large enough
+    pub unsafe fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let (size, align) = block(&layout);
+
+        let mut link: *mut *mut Hole = &mut self.holes;
+        while !(*link).is_null() {
+            let hole = *link;
+            let hole_start = hole as usize;
+            let hole_end = hole_start + (*hole).size;
+
+            // Front padding must be able to stay a hole of its own
+            let mut start = align_up(hole_start, align);
+            if start != hole_start && start - hole_start < UNIT {
+                start = align_up(hole_start + UNIT, align);
+            }
+
+            if start + size <= hole_end {
+                let mut next = (*hole).next;
+                if start + size < hole_end {
+                    let rest = (start + size) as *mut Hole;
+                    ptr::write(rest, Hole { size: hole_end - start - size, next });
+                    next = rest;
+                }
+                if start == hole_start {
+                    *link = next;
+                } else {
+                    (*hole).size = start - hole_start;
+                    (*hole).next = next;
+                }
+
+                self.used += size;
+                self.peak = cmp::max(self.peak, self.used);
+                self.allocations += 1;
+                return start as *mut u8;
+            }
+
+            link = &mut (*hole).next;
+        }
+
+        self.failures += 1;
+        ptr::null_mut()
+    }
+
+    /// Returns a block from `allocate` with the same `layout`
+    pub unsafe fn deallocate(&mut self, block_start: *mut u8, layout: Layout) {
+        let (size, _) = block(&layout);
+        let start = block_start as usize;
+        self.used -= size;
+
+        // Find the holes before and after the block
+        let mut before: *mut Hole = ptr::null_mut();
+        let mut after = self.holes;
+        while !after.is_null() && (after as usize) < start {
+            before = after;
+            after = (*after).next;
+        }
+
+        let freed = start as *mut Hole;
+        ptr::write(freed, Hole { size, next: after });
+        if !after.is_null() && start + size == after as usize {
+            (*freed).size += (*after).size;
+            (*freed).next = (*after).next;
+        }
+
+        if before.is_null() {
+            self.holes = freed;
+        } else if before as usize + (*before).size == start {
+            (*before).size += (*freed).size;
+            (*before).next = (*freed).next;
+        } else {
+            (*before).next = freed;
+        }
+    }
+
+    pub fn stats(&self) -> HeapStats {
+        let mut largest_free = 0;
+        let mut hole = self.holes;
+        while !hole.is_null() {
+            unsafe {
+                largest_free = cmp::max(largest_free, (*hole).size);
+                hole = (*hole).next;
+            }
+        }
+
+        HeapStats {
+            size            : self.size,
+            used            : self.used,
+            peak            : self.peak,
+            largest_free,
+            allocations     : self.allocations,
+            failures        : self.failures,
+        }
+    }
+}
+
+/// The global allocator, takes the linker script heap on first use
+pub struct FirmwareHeap {
+    heap    : UnsafeCell<Heap>,
+}
+
+// Only accessed with interrupts masked
+unsafe impl Sync for FirmwareHeap {}
+
+impl FirmwareHeap {
+    pub const fn empty() -> FirmwareHeap {
+        FirmwareHeap { heap: UnsafeCell::new(Heap::empty()) }
+    }
+
+    fn with<F, R>(&self, f: F) -> R
+        where F: FnOnce(&mut Heap) -> R
+    {
+        interrupt::free(|| {
+            let heap = unsafe { &mut *self.heap.get() };
+            #[cfg(not(test))]
+            {
+                if heap.size == 0 {
+                    let (start, size) = region();
+                    unsafe { heap.init(start, size); }
+                }
+            }
+            f(heap)
+        })
+    }
+
+    pub fn stats(&self) -> HeapStats {
+        self.with(|heap| heap.stats())
+    }
+}
+
+unsafe impl GlobalAlloc for FirmwareHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.with(|heap| heap.allocate(layout))
+    }
+
+    unsafe fn dealloc(&self, block_start: *mut u8, layout: Layout) {
+        self.with(|heap| heap.deallocate(block_start, layout))
+    }
+}
+
+#[cfg(not(test))]
+extern "C" {
+    // Defined by the linker script
+    static _sheap: u8;
+    static _eheap: u8;
+}
+
+/// Start and size of the heap reserved by the linker script
+#[cfg(not(test))]
+fn region() -> (usize, usize) {
+    unsafe {
+        let start = &_sheap as *const u8 as usize;
+        (start, &_eheap as *const u8 as usize - start)
+    }
+}
+
+#[cfg(all(feature = "heap", not(test)))]
+#[global_allocator]
+static HEAP: FirmwareHeap = FirmwareHeap::empty();
+
+/// Usage of the global heap
+#[cfg(all(feature = "heap", not(test)))]
+pub fn stats() -> HeapStats {
+    HEAP.stats()
+}
+
+#[cfg(all(feature = "heap", not(test)))]
+#[alloc_error_handler]
+fn out_of_memory(layout: Layout) -> ! {
+    panic!("out of memory allocating {} bytes aligned to {}, {:?}",
+           layout.size(), layout.align(), stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Heap over the capacity of `buffer`
+    fn heap(buffer: &mut Vec<Hole>) -> Heap {
+        let mut heap = Heap::empty();
+        unsafe { heap.init(buffer.as_mut_ptr() as usize, buffer.capacity() * UNIT); }
+        heap
+    }
+
+    /// Room for `units` allocation units
+    fn buffer(units: usize) -> Vec<Hole> {
+        Vec::with_capacity(units)
+    }
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn blocks_are_aligned_and_disjoint() {
+        let mut buffer = buffer(64);
+        let mut heap = heap(&mut buffer);
+
+        unsafe {
+            let a = heap.allocate(layout(3, 1));
+            let b = heap.allocate(layout(2 * UNIT, 8 * UNIT));
+            let c = heap.allocate(layout(UNIT + 1, 1));
+
+            assert_eq!(b as usize % (8 * UNIT), 0);
+            assert!(a as usize + UNIT <= b as usize);
+            assert!(c as usize >= b as usize + 2 * UNIT || c as usize + 2 * UNIT <= b as usize);
+            assert_eq!(heap.stats().used, 5 * UNIT);
+        }
+    }
+
+    #[test]
+    fn freed_neighbours_merge_again() {
+        let mut buffer = buffer(16);
+        let mut heap = heap(&mut buffer);
+
+        unsafe {
+            let blocks: Vec<*mut u8> = (0..4).map(|_| heap.allocate(layout(4 * UNIT, 1))).collect();
+            assert!(heap.allocate(layout(1, 1)).is_null());
+
+            // Free out of order, the heap must end up in one piece
+            for &i in [1, 3, 0, 2].iter() {
+                heap.deallocate(blocks[i], layout(4 * UNIT, 1));
+            }
+
+            let stats = heap.stats();
+            assert_eq!(stats.used, 0);
+            assert_eq!(stats.largest_free, 16 * UNIT);
+            assert!(!heap.allocate(layout(16 * UNIT, 1)).is_null());
+        }
+    }
+
+    #[test]
+    fn stats_count_peak_and_failures() {
+        let mut buffer = buffer(8);
+        let mut heap = heap(&mut buffer);
+
+        unsafe {
+            let a = heap.allocate(layout(6 * UNIT, 1));
+            assert!(heap.allocate(layout(4 * UNIT, 1)).is_null());
+            heap.deallocate(a, layout(6 * UNIT, 1));
+        }
+
+        assert_eq!(heap.stats(), HeapStats {
+            size            : 8 * UNIT,
+            used            : 0,
+            peak            : 6 * UNIT,
+            largest_free    : 8 * UNIT,
+            allocations     : 1,
+            failures        : 1,
+        });
+    }
+}
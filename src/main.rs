@@ -0,0 +1,344 @@
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(test), no_main)]
+#![feature(asm)]
+#![feature(core_intrinsics)]
+#![feature(global_asm)]
+#![cfg_attr(all(feature = "heap", not(test)), feature(alloc_error_handler))]
+
+
+#[cfg(test)]
+extern crate core;
+#[cfg(test)]
+extern crate object;
+#[cfg(feature = "heap")]
+extern crate alloc;
+
+#[cfg(not(any(feature = "stm32f40x", feature = "stm32f42x")))]
+compile_error!("select the chip with one of the features stm32f405, stm32f407, \
+                stm32f415, stm32f417, stm32f427 or stm32f429");
+
+#[cfg(all(feature = "stm32f40x", feature = "stm32f42x"))]
+compile_error!("more than one chip selected, build with \
+                --no-default-features --features <chip>");
+
+#[macro_use]
+mod reg_field;
+#[macro_use]
+mod sections;
+mod volatile_reg;
+mod volatile_reg8;
+mod volatile_reg16;
+mod volatile_reg32;
+#[cfg(test)]
+mod mock_regs;
+#[macro_use]
+mod stm32f4xx;
+#[macro_use]
+mod core_cm4;
+mod config;
+mod startup;
+mod panic;
+mod faults;
+mod crash_dump;
+mod stack;
+mod heap;
+mod hal;
+mod peripherals;
+
+use core_cm4::core_cm4_regs::scb::*;
+use core_cm4::fault;
+
+use stm32f4xx::constants::FLASH_BASE;
+use stm32f4xx::regs::flash::*;
+use stm32f4xx::regs::fsmc::*;
+use stm32f4xx::regs::gpio::{ GpioRegs, Port };
+use stm32f4xx::regs::pwr::*;
+use stm32f4xx::regs::rcc::*;
+use stm32f4xx::regs::tim::*;
+use hal::gpio;
+use hal::peripheral_clock;
+use hal::rcc::{ ClockConfig, Clocks };
+use hal::time::{ Hertz, U32Ext };
+use peripherals::Peripherals;
+
+/// Crystal of the Discovery board
+pub const HSE_FREQ : Hertz = Hertz(8_000_000);
+
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn main() {
+    let mut peripherals = Peripherals::take().unwrap();
+    let clocks = Clocks::read(&peripherals.rcc, Some(HSE_FREQ));
+    panic::use_clocks(&clocks);
+    fault::enable_fault_handlers(&peripherals.scb);
+    // The guard comes from the linker script, host tests have none
+    #[cfg(all(feature = "stack-guard", not(test)))]
+    stack::enable_guard(&peripherals.mpu);
+
+    let gpiod = gpio::Pins::split(&mut peripherals.gpiod);
+
+    // Green LED
+    let mut pin_d12 = gpiod.p12;
+    pin_d12.enable_clock(&peripherals.rcc);
+    pin_d12.setup_mode(gpio::PinMode::Output);
+
+    // Orange LED
+    let mut pin_d13 = gpiod.p13;
+    pin_d13.setup_mode(gpio::PinMode::Output);
+
+    // Red LED
+    let mut pin_d14 = gpiod.p14;
+    pin_d14.setup_mode(gpio::PinMode::Output);
+
+    // Blue LED
+    let mut pin_d15 = gpiod.p15;
+    pin_d15.setup_mode(gpio::PinMode::Output);
+
+    // Enable clock for TIMER7
+    {
+        let clock_tim7 = peripheral_clock::PeripheralClock::Tim7;
+        clock_tim7.enable(&peripherals.rcc);
+    }
+    let tim7_regs = peripherals.tim7;
+    tim7_regs.cr1.bit_or(TIM_CR1_OPM | TIM_CR1_URS);
+    // Count at 2 kHz
+    tim7_regs.psc.set(clocks.timclk1().0 / 2_000 - 1);
+    tim7_regs.arr.set(2);
+
+    let wait_ms = |time: u32| {
+        tim7_regs.arr.set(2 * time);
+        tim7_regs.cr1.bit_or(TIM_CR1_CEN);
+        while tim7_regs.sr.get() == 0 {
+
+        }
+        tim7_regs.sr.set(0);
+    };
+
+/*
+    // Setup pins for SPI
+    let gpiob = gpio::Pins::split(&mut peripherals.gpiob);
+
+    let mut reset = gpiob.p10;
+    reset.enable_clock(&peripherals.rcc);
+    reset.setup_mode(gpio::PinMode::Output);
+    reset.setup_speed(gpio::PinSpeed::High);
+
+    let mut dc = gpiob.p11;
+    reset.setup_mode(gpio::PinMode::Output);
+    reset.setup_speed(gpio::PinSpeed::High);
+
+    {
+        let mut cs = gpiob.p12;
+        cs.setup_alt_func(gpio::AltFunc::Af5);
+        cs.setup_speed(gpio::PinSpeed::High);
+        cs.setup_mode(gpio::PinMode::AltFunc);
+
+        let mut sck = gpiob.p13;
+        sck.setup_alt_func(gpio::AltFunc::Af5);
+        sck.setup_speed(gpio::PinSpeed::High);
+        sck.setup_mode(gpio::PinMode::AltFunc);
+
+        let mut miso = gpiob.p14;
+        miso.setup_alt_func(gpio::AltFunc::Af5);
+        miso.setup_speed(gpio::PinSpeed::High);
+        miso.setup_mode(gpio::PinMode::AltFunc);
+
+        let mut mosi = gpiob.p15;
+        mosi.setup_alt_func(gpio::AltFunc::Af5);
+        mosi.setup_speed(gpio::PinSpeed::High);
+        mosi.setup_mode(gpio::PinMode::AltFunc);
+    }
+*/
+
+
+    loop {
+        wait_ms(500);
+        pin_d12.toggle();
+
+        wait_ms(500);
+        pin_d13.toggle();
+
+        wait_ms(500);
+        pin_d14.toggle();
+
+        wait_ms(500);
+        pin_d15.toggle();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn system_init() {
+
+    // Runs from the reset handler before main, nothing else owns the blocks yet
+    let rcc = unsafe { RccRegs::init() };
+    let scb = unsafe { ScbRegs::init() };
+
+    // Keep the reset flags for the application before anything else resets
+    hal::reset_cause::capture(&rcc);
+
+    // /* FPU settings ------------------------------------------------------------*/
+    // #if (__FPU_PRESENT == 1) && (__FPU_USED == 1)
+    //     SCB->CPACR |= ((3UL << 10*2)|(3UL << 11*2));  /* set CP10 and CP11 Full Access */
+    // #endif
+    scb.cpacr.modify(|_, w| w.set(cpacr::Cp10::Full).set(cpacr::Cp11::Full));
+
+
+    // Reset the RCC clock configuration to the default reset state
+    // Set HSION bit
+    // RCC->CR |= (uint32_t)0x00000001;
+    rcc.cr.bit_or(0x00000001);
+
+    // Reset CFGR register
+    // RCC->CFGR = 0x00000000;
+    rcc.cfgr.set(0x00000000);
+
+    // Reset HSEON, CSSON and PLLON bits
+    // RCC->CR &= (uint32_t)0xFEF6FFFF;
+    rcc.cr.bit_and(0xFEF6FFFF);
+
+    // Reset PLLCFGR register
+    // RCC->PLLCFGR = 0x24003010;
+    rcc.pllcfgr.set(0x24003010);
+
+    // /* Reset HSEBYP bit */
+    // RCC->CR &= (uint32_t)0xFFFBFFFF;
+    rcc.cr.bit_and(0xFFFBFFFF);
+
+    // Disable all interrupts
+    // RCC->CIR = 0x00000000;
+    rcc.cir.set(0x00000000);
+
+    // The external SRAM is set up by the reset handler, see startup.rs
+
+    // /* Configure the System clock source, PLL Multiplier and Divider factors,
+    //    AHB/APBx prescalers and Flash settings ----------------------------------*/
+    // set_sys_clock();
+    set_sys_clock();
+
+    // Configure the Vector Table location, the copy made by the reset
+    // handler at the start of SRAM or the image in flash, moved up by
+    // VECT_TAB_OFFSET behind a bootloader
+    if cfg!(feature = "vect-tab-sram") {
+        scb.vtor.set(startup::vector_table_ram());
+    } else {
+        scb.vtor.set(FLASH_BASE + config::VECT_TAB_OFFSET);
+    }
+}
+
+/// Sets up FSMC bank 1, NE2, for the external SRAM of the STM324xG-EVAL
+///
+/// Called by the reset handler before .data and .bss exist, must not touch
+/// any static.
+#[no_mangle]
+pub extern "C" fn system_init_ext_mem_ctl() {
+    // Nothing else owns the blocks yet
+    let rcc = unsafe { RccRegs::init() };
+    let fsmc = unsafe { FsmcRegs::init() };
+
+    // Enable GPIOD, GPIOE, GPIOF and GPIOG interface clock
+    rcc.ahb1enr.bit_or(RCC_AHB1ENR_GPIODEN | RCC_AHB1ENR_GPIOEEN
+                       | RCC_AHB1ENR_GPIOFEN | RCC_AHB1ENR_GPIOGEN);
+
+    // Data, address and control lines in AF12, 100 MHz, push-pull, no pull
+    // (AFRL, AFRH, MODER, OSPEEDR)
+    let ports = [
+        (Port::GpioD, 0x00cc00cc, 0xcccccccc, 0xaaaa0a0a, 0xffff0f0f),
+        (Port::GpioE, 0xcccccccc, 0xcccccccc, 0xaaaaaaaa, 0xffffffff),
+        (Port::GpioF, 0x00cccccc, 0xcccc0000, 0xaa000aaa, 0xff000fff),
+        (Port::GpioG, 0x00cccccc, 0x000000c0, 0x00080aaa, 0x000c0fff),
+    ];
+    for &(ref port, afrl, afrh, moder, ospeedr) in ports.iter() {
+        let gpio = unsafe { GpioRegs::init(port) };
+        gpio.afrl.set(afrl);
+        gpio.afrh.set(afrh);
+        gpio.moder.set(moder);
+        gpio.ospeedr.set(ospeedr);
+        gpio.otyper.set(0x00000000);
+        gpio.pupdr.set(0x00000000);
+    }
+
+    // Enable the FSMC interface clock
+    #[cfg(feature = "stm32f40x")]
+    rcc.ahb3enr.bit_or(RCC_AHB3ENR_FSMCEN);
+    #[cfg(feature = "stm32f42x")]
+    rcc.ahb3enr.bit_or(RCC_AHB3ENR_FMCEN);
+
+    // 16 bit SRAM, asynchronous, ADDSET 1, DATAST 2 HCLK cycles
+    fsmc.bcr2.set(FSMC_BCR2_MBKEN | FSMC_BCR2_MWID_0 | FSMC_BCR2_WREN);
+    fsmc.btr2.set(0x00000201);
+    fsmc.bwtr2.set(0x0fffffff);
+}
+
+
+/// 168 MHz from the 8 MHz crystal of the Discovery board, with 48 MHz for
+/// USB. If the crystal does not start, the same clocks come from HSI and
+/// `hal::rcc::clock_fault` tells why. With the `clock-security` feature a
+/// crystal failure at runtime switches to HSI as well, see `hal::rcc`.
+#[no_mangle]
+pub extern "C" fn set_sys_clock() {
+    // Runs from system_init before main, nothing else owns the blocks yet
+    let rcc = unsafe { RccRegs::init() };
+    let pwr = unsafe { PwrRegs::init() };
+    let flash = unsafe { FlashRegs::init() };
+
+    let mut config = ClockConfig::new().use_hse(HSE_FREQ)
+                                       .sysclk(168.mhz())
+                                       .require_48mhz();
+    if cfg!(feature = "clock-security") {
+        config = config.clock_security();
+    }
+
+    // If even HSI fails there is nothing left to fall back to, the chip
+    // runs on from HSI at 16 MHz
+    let _ = config.apply_or_hsi(&rcc, &pwr, &flash);
+}
+
+#[cfg(feature = "clock-security")]
+exception!(NMI, hal::rcc::on_clock_failure);
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_regs;
+
+    // The golden traces are recorded on the STM32F40x with the vector table
+    // in flash and without the Clock Security System, the F42x sets two VOS
+    // bits
+    #[test]
+    #[cfg(all(feature = "stm32f40x", not(feature = "clock-security")))]
+    fn set_sys_clock_matches_golden_trace() {
+        mock_regs::simulate_rcc();
+        mock_regs::start_trace();
+
+        set_sys_clock();
+
+        mock_regs::assert_trace(&mock_regs::take_trace(),
+                                include_str!("golden/set_sys_clock.trace"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "stm32f40x", not(feature = "vect-tab-sram"),
+              not(feature = "clock-security")))]
+    fn system_init_matches_golden_trace() {
+        mock_regs::simulate_rcc();
+        mock_regs::start_trace();
+
+        system_init();
+
+        mock_regs::assert_trace(&mock_regs::take_trace(),
+                                include_str!("golden/system_init.trace"));
+    }
+
+    #[test]
+    fn ext_mem_ctl_sets_up_sram_on_ne2() {
+        use stm32f4xx::constants::{ FSMC_BANK1_R_BASE, FSMC_BANK1E_R_BASE };
+
+        system_init_ext_mem_ctl();
+
+        assert_eq!(mock_regs::read(FSMC_BANK1_R_BASE + 0x08), 0x00001011);
+        assert_eq!(mock_regs::read(FSMC_BANK1_R_BASE + 0x0C), 0x00000201);
+        assert_eq!(mock_regs::read(FSMC_BANK1E_R_BASE + 0x08), 0x0fffffff);
+        // Bank 1, NE1, untouched
+        assert_eq!(mock_regs::read(FSMC_BANK1_R_BASE), 0);
+    }
+}
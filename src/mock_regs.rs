@@ -0,0 +1,221 @@
+//! In-memory register file used instead of the MMIO space by `cargo test`
+//!
+//! Every test thread gets its own register file, so tests running in
+//! parallel do not see each other's writes. Registers read as zero until
+//! they are written.
+//!
+//! Accesses to the bit-band alias regions are applied to the aliased bit.
+//!
+//! Hardware behaviour a driver waits for (ready flags, status mirrors) can be
+//! simulated with `set_hook`, and the ordered sequence of accesses can be
+//! recorded with `start_trace`/`take_trace` and compared against a golden
+//! trace with `assert_trace`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use ::stm32f4xx::regs::rcc::*;
+use ::stm32f4xx::constants::{ RCC_BASE,
+                              SRAM1_BASE,
+                              SRAM1_BB_BASE,
+                              PERIPH_BASE,
+                              PERIPH_BB_BASE,
+                              };
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AccessKind {
+    Read,
+    Write,
+}
+
+/// One recorded register access
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Access {
+    pub kind    : AccessKind,
+    pub address : u32,
+    /// Register value before the access
+    pub old     : u32,
+    /// Value read, or value written by the software
+    pub new     : u32,
+}
+
+struct RegisterFile {
+    regs    : HashMap<u32, u32>,
+    hooks   : HashMap<u32, fn(u32) -> u32>,
+    trace   : Option<Vec<Access>>,
+}
+
+thread_local! {
+    static REGS: RefCell<RegisterFile> = RefCell::new(RegisterFile {
+        regs    : HashMap::new(),
+        hooks   : HashMap::new(),
+        trace   : None,
+    });
+}
+
+/// Splits a bit-band alias address into the aliased word and bit
+fn bit_band_target(address: u32) -> Option<(u32, u32)> {
+    let (alias_base, region_base) =
+        if (SRAM1_BB_BASE..SRAM1_BB_BASE + 0x02000000).contains(&address) {
+            (SRAM1_BB_BASE, SRAM1_BASE)
+        } else if (PERIPH_BB_BASE..PERIPH_BB_BASE + 0x02000000).contains(&address) {
+            (PERIPH_BB_BASE, PERIPH_BASE)
+        } else {
+            return None;
+        };
+
+    let offset = address - alias_base;
+    Some(((region_base + offset / 32) & !0x3, (offset % 128) / 4))
+}
+
+/// Word, shift and mask of the bits an access of `size` bytes at `address`
+/// reaches
+fn lane(address: u32, size: u32) -> (u32, u32, u32) {
+    match bit_band_target(address) {
+        Some((word, bit)) => (word, bit, 1),
+        None => {
+            let mask: u32 = if size >= 4 { !0 } else { (1 << (size * 8)) - 1 };
+            (address & !0x3, (address & 0x3) * 8, mask)
+        },
+    }
+}
+
+/// Returns the value of the register at `address`
+pub fn read(address: u32) -> u32 {
+    read_sized(address, 4)
+}
+
+/// Stores `value` in the register at `address`, through the hook of the
+/// register if one is set
+pub fn write(address: u32, value: u32) {
+    write_sized(address, 4, value);
+}
+
+/// Returns the value of the 1, 2 or 4 byte register at `address`
+pub fn read_sized(address: u32, size: u32) -> u32 {
+    REGS.with(|file| {
+        let mut file = file.borrow_mut();
+        let (word, shift, mask) = lane(address, size);
+        let value = (*file.regs.get(&word).unwrap_or(&0) >> shift) & mask;
+
+        if let Some(ref mut trace) = file.trace {
+            trace.push(Access { kind: AccessKind::Read, address, old: value, new: value });
+        }
+
+        value
+    })
+}
+
+/// Stores `value` in the 1, 2 or 4 byte register at `address`; the hook of
+/// the containing word sees the whole word
+pub fn write_sized(address: u32, size: u32, value: u32) {
+    REGS.with(|file| {
+        let mut file = file.borrow_mut();
+        let (word, shift, mask) = lane(address, size);
+        let word_value = *file.regs.get(&word).unwrap_or(&0);
+
+        if let Some(ref mut trace) = file.trace {
+            let old = (word_value >> shift) & mask;
+            trace.push(Access { kind: AccessKind::Write, address, old, new: value });
+        }
+
+        let value = (word_value & !(mask << shift)) | ((value & mask) << shift);
+        let stored = match file.hooks.get(&word) {
+            Some(hook) => hook(value),
+            None => value,
+        };
+        file.regs.insert(word, stored);
+    });
+}
+
+/// Makes every write to `address` store `hook(value)` instead of `value`,
+/// which lets a test model how the hardware reacts to a write
+pub fn set_hook(address: u32, hook: fn(u32) -> u32) {
+    REGS.with(|file| {
+        file.borrow_mut().hooks.insert(address, hook);
+    });
+}
+
+/// Sets all registers back to zero, removes the hooks and stops tracing
+pub fn reset() {
+    REGS.with(|file| {
+        let mut file = file.borrow_mut();
+        file.regs.clear();
+        file.hooks.clear();
+        file.trace = None;
+    });
+}
+
+/// Starts recording register accesses, dropping any previous record
+pub fn start_trace() {
+    REGS.with(|file| file.borrow_mut().trace = Some(Vec::new()));
+}
+
+/// Stops recording and returns the accesses recorded so far
+pub fn take_trace() -> Vec<Access> {
+    REGS.with(|file| file.borrow_mut().trace.take().unwrap_or(Vec::new()))
+}
+
+/// Formats a trace one access per line: `W <address> <old> -> <new>`
+pub fn format_trace(trace: &[Access]) -> String {
+    let mut text = String::new();
+
+    for access in trace {
+        match access.kind {
+            AccessKind::Read => writeln!(text, "R {:08X} {:08X}",
+                                         access.address, access.new),
+            AccessKind::Write => writeln!(text, "W {:08X} {:08X} -> {:08X}",
+                                          access.address, access.old, access.new),
+        }.unwrap();
+    }
+
+    text
+}
+
+/// Compares a trace with a golden trace, reporting the first differing line
+///
+/// Running the tests with `UPDATE_GOLDEN=<path>` writes the actual trace to
+/// `<path>` instead, for refreshing a golden file after an intended change.
+pub fn assert_trace(trace: &[Access], golden: &str) {
+    let actual = format_trace(trace);
+
+    if let Ok(path) = ::std::env::var("UPDATE_GOLDEN") {
+        let mut file = ::std::fs::File::create(path).unwrap();
+        ::std::io::Write::write_all(&mut file, actual.as_bytes()).unwrap();
+        return;
+    }
+
+    let mut actual_lines = actual.lines();
+    let mut golden_lines = golden.lines();
+    let mut line: usize = 1;
+
+    loop {
+        match (actual_lines.next(), golden_lines.next()) {
+            (None, None) => break,
+            (a, g) => assert!(a == g, "trace differs at line {}: expected {:?}, got {:?}",
+                              line, g, a),
+        }
+        line += 1;
+    }
+}
+
+/// Models the RCC ready flags: oscillators and PLLs report ready as soon as
+/// they are switched on, and SWS follows SW
+pub fn simulate_rcc() {
+    fn cr(value: u32) -> u32 {
+        let mut value = value & !(RCC_CR_HSIRDY | RCC_CR_HSERDY | RCC_CR_PLLRDY | RCC_CR_PLLI2SRDY);
+        if value & RCC_CR_HSION != 0 { value |= RCC_CR_HSIRDY; }
+        if value & RCC_CR_HSEON != 0 { value |= RCC_CR_HSERDY; }
+        if value & RCC_CR_PLLON != 0 { value |= RCC_CR_PLLRDY; }
+        if value & RCC_CR_PLLI2SON != 0 { value |= RCC_CR_PLLI2SRDY; }
+        value
+    }
+
+    fn cfgr(value: u32) -> u32 {
+        (value & !RCC_CFGR_SWS) | ((value & RCC_CFGR_SW) << 2)
+    }
+
+    set_hook(RCC_BASE, cr);
+    set_hook(RCC_BASE + 0x08, cfgr);
+}
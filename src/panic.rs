@@ -0,0 +1,330 @@
+#![allow(dead_code)]
+
+//! Panic handler: reports the message and location, then halts, blinks or
+//! resets
+//!
+//! The report goes to every sink enabled with the `panic-itm`, `panic-uart`
+//! and `panic-semihosting` features. Afterwards the core halts for the
+//! debugger, unless `panic-blink` or `panic-reset` selects another action.
+//! The `crash-dump` feature also keeps the report in backup SRAM, see
+//! `crash_dump.rs`.
+
+use core::fmt::{ self, Write };
+#[cfg(not(test))]
+use core::panic::PanicInfo;
+use core::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+
+use ::core_cm4::core_cm4_regs::core_debug::*;
+use ::core_cm4::core_cm4_regs::itm::*;
+#[cfg(not(test))]
+use ::core_cm4::interrupt;
+use ::core_cm4::reset;
+use ::hal::gpio;
+use ::hal::peripheral_clock::PeripheralClock;
+use ::hal::rcc::{ Clocks, HSI_FREQ };
+use ::stm32f4xx::regs::gpio::{ GpioRegs, Port };
+use ::stm32f4xx::regs::rcc::RccRegs;
+use ::stm32f4xx::regs::usart::*;
+
+#[cfg(all(feature = "panic-blink", feature = "panic-reset"))]
+compile_error!("select at most one of the features panic-blink and panic-reset");
+
+/// What happens once the report is out
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    /// Stops at a breakpoint if a debugger is attached, spins otherwise
+    Halt,
+    /// Blinks the red Discovery LED `code` times, pauses and repeats
+    BlinkLeds(u32),
+    /// Resets the chip through SCB_AIRCR_SYSRESETREQ
+    Reset,
+}
+
+/// Error code blinked after a panic, faults blink their exception number
+pub const PANIC_BLINK_CODE  : u32       = 1;
+
+#[cfg(feature = "panic-blink")]
+pub const ACTION            : Action    = Action::BlinkLeds(PANIC_BLINK_CODE);
+#[cfg(all(feature = "panic-reset", not(feature = "panic-blink")))]
+pub const ACTION            : Action    = Action::Reset;
+#[cfg(not(any(feature = "panic-blink", feature = "panic-reset")))]
+pub const ACTION            : Action    = Action::Halt;
+
+/// Baud rate of the UART sink, USART2 with TX on PA2
+pub const UART_BAUD         : u32       = 115_200;
+
+/// Busy loop iterations per millisecond at 168 MHz, roughly
+const LOOPS_PER_MS          : u32       = 168_000 / 4;
+
+/// Semihosting operation writing the character pointed to by r1
+const SYS_WRITEC            : u32       = 0x03;
+
+static PANICKING: AtomicBool = AtomicBool::new(false);
+static UART_READY: AtomicBool = AtomicBool::new(false);
+
+/// PCLK1 the UART baud rate is derived from, HSI after reset until
+/// `use_clocks` is called
+static UART_PCLK1: AtomicUsize = AtomicUsize::new(HSI_FREQ as usize);
+
+/// Hands the frozen clocks to the panic sinks, called by `main` once the
+/// clocks are set up and again whenever they change
+pub fn use_clocks(clocks: &Clocks) {
+    UART_PCLK1.store(clocks.pclk1().0 as usize, Ordering::SeqCst);
+}
+
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    interrupt::disable();
+
+    // A panic while reporting goes straight to the action
+    if !PANICKING.swap(true, Ordering::SeqCst) {
+        #[cfg(feature = "crash-dump")]
+        unsafe { ::crash_dump::save_panic(format_args!("{}", info)); }
+
+        let _ = write!(Sinks, "{}\r\n", info);
+    }
+
+    act(ACTION)
+}
+
+/// Writes to every sink selected with the features
+pub struct Sinks;
+
+impl Write for Sinks {
+    #[allow(unused_variables)]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        #[cfg(feature = "panic-itm")]
+        Itm.write_str(s)?;
+        #[cfg(feature = "panic-uart")]
+        Uart.write_str(s)?;
+        #[cfg(feature = "panic-semihosting")]
+        Semihosting.write_str(s)?;
+        Ok(())
+    }
+}
+
+/// Stimulus port 0 of the ITM, read out through SWO by the debug probe
+pub struct Itm;
+
+impl Write for Itm {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Nothing else runs any more, the ITM is stolen from its owner
+        let itm = unsafe { ItmRegs::init() };
+
+        // Dropped unless the debugger enabled the ITM and port 0
+        if itm.tcr.get() & ITM_TCR_ITMENA_MSK == 0 || itm.ter.get() & 1 == 0 {
+            return Ok(());
+        }
+
+        for byte in s.bytes() {
+            while itm.stim(0).get() == 0 {
+            }
+            itm.stim8(0).set(byte);
+        }
+        Ok(())
+    }
+}
+
+/// USART2 transmitting on PA2, set up on first use
+pub struct Uart;
+
+impl Uart {
+    fn regs() -> UsartRegs {
+        // Nothing else runs any more, USART2 is stolen from its owner
+        let usart = unsafe { UsartRegs::init(UsartInst::Usart2) };
+
+        if !UART_READY.swap(true, Ordering::SeqCst) {
+            // Stolen as well, only the enable bits are touched
+            let rcc = unsafe { RccRegs::init() };
+            PeripheralClock::Usart2.enable(&rcc);
+
+            let mut gpioa = unsafe { GpioRegs::init(&Port::GpioA) };
+            let mut tx = gpio::Pins::split(&mut gpioa).p2;
+            tx.enable_clock(&rcc);
+            tx.setup_alt_func(gpio::AltFunc::Af7);
+            tx.setup_mode(gpio::PinMode::AltFunc);
+
+            let pclk1 = UART_PCLK1.load(Ordering::SeqCst) as u32;
+
+            // 8N1, 16 times oversampling
+            usart.cr1.set(0);
+            usart.cr2.set(0);
+            usart.cr3.set(0);
+            usart.brr.set((pclk1 + UART_BAUD / 2) / UART_BAUD);
+            usart.cr1.set(USART_CR1_UE | USART_CR1_TE);
+        }
+
+        usart
+    }
+}
+
+impl Write for Uart {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let usart = Uart::regs();
+
+        for byte in s.bytes() {
+            while usart.sr.get() & USART_SR_TXE == 0 {
+            }
+            usart.dr.set(byte as u32);
+        }
+
+        // Let the last character leave before a reset cuts it off
+        while usart.sr.get() & USART_SR_TC == 0 {
+        }
+        Ok(())
+    }
+}
+
+/// Host console of the debugger through ARM semihosting
+pub struct Semihosting;
+
+impl Write for Semihosting {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Without a debugger the semihosting breakpoint would HardFault
+        if !debugger_attached() {
+            return Ok(());
+        }
+
+        for byte in s.bytes() {
+            sys_writec(byte);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "arm")]
+fn sys_writec(c: u8) {
+    let _result: u32;
+    unsafe {
+        asm!("bkpt 0xAB" : "={r0}"(_result)
+                         : "{r0}"(SYS_WRITEC), "{r1}"(&c as *const u8)
+                         : "memory" : "volatile");
+    }
+}
+
+#[cfg(not(target_arch = "arm"))]
+fn sys_writec(_c: u8) {}
+
+#[cfg(target_arch = "arm")]
+fn breakpoint() {
+    unsafe { asm!("bkpt 0" :::: "volatile"); }
+}
+
+#[cfg(not(target_arch = "arm"))]
+fn breakpoint() {}
+
+#[cfg(target_arch = "arm")]
+fn nop() {
+    unsafe { asm!("nop" :::: "volatile"); }
+}
+
+#[cfg(not(target_arch = "arm"))]
+fn nop() {}
+
+/// True while a debugger has debug enabled on the core
+pub fn debugger_attached() -> bool {
+    let core_debug = unsafe { CoreDebugRegs::init() };
+    core_debug.dhcsr.get() & CORE_DEBUG_DHCSR_C_DEBUGEN_MSK != 0
+}
+
+fn delay_ms(ms: u32) {
+    for _ in 0..ms * LOOPS_PER_MS {
+        nop();
+    }
+}
+
+/// Performs `action`, never returns
+pub fn act(action: Action) -> ! {
+    match action {
+        Action::Halt => {
+            if debugger_attached() {
+                breakpoint();
+            }
+            // Stays put for a debugger attached later
+            #[allow(clippy::empty_loop)]
+            loop {}
+        },
+        Action::BlinkLeds(code) => blink(code),
+        Action::Reset => reset::system_reset(),
+    }
+}
+
+/// Turns the Discovery LEDs off and blinks `code` on the red one (PD14)
+pub fn blink(code: u32) -> ! {
+    // Nothing else runs any more, GPIOD is stolen from its owner
+    let mut gpiod = unsafe { GpioRegs::init(&Port::GpioD) };
+    let pins = gpio::Pins::split(&mut gpiod);
+    let mut leds = [pins.p12, pins.p13, pins.p14, pins.p15];
+
+    leds[0].enable_clock(unsafe { &RccRegs::init() });
+    for led in leds.iter_mut() {
+        led.set(false);
+        led.setup_mode(gpio::PinMode::Output);
+    }
+
+    let red = &leds[2];
+    loop {
+        for _ in 0..code {
+            red.set(true);
+            delay_ms(200);
+            red.set(false);
+            delay_ms(200);
+        }
+        delay_ms(1000);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::core_cm4::constants::ITM_BASE;
+
+    const TER       : u32   = ITM_BASE + 0xE00;
+    const TCR       : u32   = ITM_BASE + 0xE80;
+
+    fn itm_writes() -> Vec<u32> {
+        mock_regs::take_trace().iter()
+                  .filter(|a| a.kind == mock_regs::AccessKind::Write && a.address == ITM_BASE)
+                  .map(|a| a.new)
+                  .collect()
+    }
+
+    #[test]
+    fn itm_sink_writes_bytes_to_port_0() {
+        mock_regs::write(TCR, ITM_TCR_ITMENA_MSK);
+        mock_regs::write(TER, 1);
+        // Port 0 ready
+        mock_regs::write(ITM_BASE, 1);
+        mock_regs::start_trace();
+
+        write!(Itm, "at {}", 7).unwrap();
+
+        assert_eq!(itm_writes(), [b'a' as u32, b't' as u32, b' ' as u32, b'7' as u32]);
+    }
+
+    #[test]
+    fn itm_sink_drops_output_while_disabled() {
+        mock_regs::write(TER, 1);
+        mock_regs::start_trace();
+
+        Itm.write_str("lost").unwrap();
+
+        assert!(itm_writes().is_empty());
+    }
+
+    #[test]
+    fn uart_baud_rate_comes_from_the_given_clocks() {
+        use ::stm32f4xx::constants::{ RCC_BASE, USART2_BASE };
+        use ::stm32f4xx::regs::rcc::RCC_CFGR_PPRE1_DIV2;
+
+        // HSI with APB1 at 8 MHz
+        mock_regs::write(RCC_BASE + 0x08, RCC_CFGR_PPRE1_DIV2);
+        use_clocks(&Clocks::read(unsafe { &RccRegs::init() }, None));
+        mock_regs::write(USART2_BASE, USART_SR_TXE | USART_SR_TC);
+
+        Uart.write_str("x").unwrap();
+        assert_eq!(mock_regs::read(USART2_BASE + 0x08), (8_000_000 + UART_BAUD / 2) / UART_BAUD);
+    }
+}
@@ -0,0 +1,176 @@
+#![allow(dead_code)]
+
+//! Single owner of every register block
+//!
+//! `Peripherals::take()` hands out each register block exactly once. Drivers
+//! consume the blocks they need, so two drivers can not own the same
+//! peripheral without the compiler noticing.
+
+use core::sync::atomic::{ AtomicBool, Ordering };
+
+use ::core_cm4::core_cm4_regs::core_debug::CoreDebugRegs;
+use ::core_cm4::core_cm4_regs::fpu::FpuRegs;
+use ::core_cm4::core_cm4_regs::itm::ItmRegs;
+use ::core_cm4::core_cm4_regs::mpu::MpuRegs;
+use ::core_cm4::core_cm4_regs::nvic::NvicRegs;
+use ::core_cm4::core_cm4_regs::scb::ScbRegs;
+use ::core_cm4::core_cm4_regs::scnscb::ScNScbRegs;
+use ::core_cm4::core_cm4_regs::sys_tick::SysTickRegs;
+use ::stm32f4xx::regs::flash::FlashRegs;
+use ::stm32f4xx::regs::fsmc::FsmcRegs;
+use ::stm32f4xx::regs::gpio::{ GpioRegs, Port };
+use ::stm32f4xx::regs::pwr::PwrRegs;
+use ::stm32f4xx::regs::rcc::RccRegs;
+use ::stm32f4xx::regs::spi::{ SpiRegs, SpiInst };
+use ::stm32f4xx::regs::tim::{ TimRegs, TimInst };
+use ::stm32f4xx::regs::usart::{ UsartRegs, UsartInst };
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+pub struct Peripherals {
+    // Cortex-M4 core peripherals
+    pub core_debug  : CoreDebugRegs,
+    pub fpu         : FpuRegs,
+    pub itm         : ItmRegs,
+    pub mpu         : MpuRegs,
+    pub nvic        : NvicRegs,
+    pub scb         : ScbRegs,
+    pub scnscb      : ScNScbRegs,
+    pub sys_tick    : SysTickRegs,
+
+    // STM32F4xx peripherals
+    pub flash       : FlashRegs,
+    pub fsmc        : FsmcRegs,
+    pub pwr         : PwrRegs,
+    pub rcc         : RccRegs,
+
+    pub gpioa       : GpioRegs,
+    pub gpiob       : GpioRegs,
+    pub gpioc       : GpioRegs,
+    pub gpiod       : GpioRegs,
+    pub gpioe       : GpioRegs,
+    pub gpiof       : GpioRegs,
+    pub gpiog       : GpioRegs,
+    pub gpioh       : GpioRegs,
+    pub gpioi       : GpioRegs,
+    #[cfg(feature = "stm32f42x")]
+    pub gpioj       : GpioRegs,
+    #[cfg(feature = "stm32f42x")]
+    pub gpiok       : GpioRegs,
+
+    pub spi1        : SpiRegs,
+    pub spi2        : SpiRegs,
+    pub spi3        : SpiRegs,
+    pub i2s2ext     : SpiRegs,
+    pub i2s3ext     : SpiRegs,
+    #[cfg(feature = "stm32f42x")]
+    pub spi4        : SpiRegs,
+    #[cfg(feature = "stm32f42x")]
+    pub spi5        : SpiRegs,
+    #[cfg(feature = "stm32f42x")]
+    pub spi6        : SpiRegs,
+
+    pub tim1        : TimRegs,
+    pub tim2        : TimRegs,
+    pub tim3        : TimRegs,
+    pub tim4        : TimRegs,
+    pub tim5        : TimRegs,
+    pub tim6        : TimRegs,
+    pub tim7        : TimRegs,
+    pub tim8        : TimRegs,
+    pub tim9        : TimRegs,
+    pub tim10       : TimRegs,
+    pub tim11       : TimRegs,
+    pub tim12       : TimRegs,
+    pub tim13       : TimRegs,
+    pub tim14       : TimRegs,
+
+    pub usart1      : UsartRegs,
+    pub usart2      : UsartRegs,
+    pub usart3      : UsartRegs,
+    pub uart4       : UsartRegs,
+    pub uart5       : UsartRegs,
+    pub usart6      : UsartRegs,
+    // UART7 and UART8 of the STM32F42x are missing from the STM32F40x SVD
+    // the USART registers are generated from
+}
+
+impl Peripherals {
+    /// Returns all register blocks on the first call and `None` afterwards
+    pub fn take() -> Option<Peripherals> {
+        if TAKEN.swap(true, Ordering::SeqCst) {
+            None
+        } else {
+            Some(unsafe { Peripherals::steal() })
+        }
+    }
+
+    /// Returns the register blocks regardless of whether they were taken
+    /// already, e.g. for fault handlers. The caller is responsible for not
+    /// racing with the owners.
+    pub unsafe fn steal() -> Peripherals {
+        Peripherals {
+            core_debug  : CoreDebugRegs::init(),
+            fpu         : FpuRegs::init(),
+            itm         : ItmRegs::init(),
+            mpu         : MpuRegs::init(),
+            nvic        : NvicRegs::init(),
+            scb         : ScbRegs::init(),
+            scnscb      : ScNScbRegs::init(),
+            sys_tick    : SysTickRegs::init(),
+
+            flash       : FlashRegs::init(),
+            fsmc        : FsmcRegs::init(),
+            pwr         : PwrRegs::init(),
+            rcc         : RccRegs::init(),
+
+            gpioa       : GpioRegs::init(&Port::GpioA),
+            gpiob       : GpioRegs::init(&Port::GpioB),
+            gpioc       : GpioRegs::init(&Port::GpioC),
+            gpiod       : GpioRegs::init(&Port::GpioD),
+            gpioe       : GpioRegs::init(&Port::GpioE),
+            gpiof       : GpioRegs::init(&Port::GpioF),
+            gpiog       : GpioRegs::init(&Port::GpioG),
+            gpioh       : GpioRegs::init(&Port::GpioH),
+            gpioi       : GpioRegs::init(&Port::GpioI),
+            #[cfg(feature = "stm32f42x")]
+            gpioj       : GpioRegs::init(&Port::GpioJ),
+            #[cfg(feature = "stm32f42x")]
+            gpiok       : GpioRegs::init(&Port::GpioK),
+
+            spi1        : SpiRegs::init(SpiInst::Spi1),
+            spi2        : SpiRegs::init(SpiInst::Spi2),
+            spi3        : SpiRegs::init(SpiInst::Spi3),
+            i2s2ext     : SpiRegs::init(SpiInst::I2s2ext),
+            i2s3ext     : SpiRegs::init(SpiInst::I2s3ext),
+            #[cfg(feature = "stm32f42x")]
+            spi4        : SpiRegs::init(SpiInst::Spi4),
+            #[cfg(feature = "stm32f42x")]
+            spi5        : SpiRegs::init(SpiInst::Spi5),
+            #[cfg(feature = "stm32f42x")]
+            spi6        : SpiRegs::init(SpiInst::Spi6),
+
+            tim1        : TimRegs::init(TimInst::TIM1),
+            tim2        : TimRegs::init(TimInst::TIM2),
+            tim3        : TimRegs::init(TimInst::TIM3),
+            tim4        : TimRegs::init(TimInst::TIM4),
+            tim5        : TimRegs::init(TimInst::TIM5),
+            tim6        : TimRegs::init(TimInst::TIM6),
+            tim7        : TimRegs::init(TimInst::TIM7),
+            tim8        : TimRegs::init(TimInst::TIM8),
+            tim9        : TimRegs::init(TimInst::TIM9),
+            tim10       : TimRegs::init(TimInst::TIM10),
+            tim11       : TimRegs::init(TimInst::TIM11),
+            tim12       : TimRegs::init(TimInst::TIM12),
+            tim13       : TimRegs::init(TimInst::TIM13),
+            tim14       : TimRegs::init(TimInst::TIM14),
+
+            usart1      : UsartRegs::init(UsartInst::Usart1),
+            usart2      : UsartRegs::init(UsartInst::Usart2),
+            usart3      : UsartRegs::init(UsartInst::Usart3),
+            uart4       : UsartRegs::init(UsartInst::Uart4),
+            uart5       : UsartRegs::init(UsartInst::Uart5),
+            usart6      : UsartRegs::init(UsartInst::Usart6),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+#![allow(dead_code)]
+
+//! Typed access to the bit fields of a 32-bit register
+//!
+//! Every field is described by a value type implementing `FieldValue`, which
+//! knows the position and width of the field inside its register. Writing a
+//! field through `W::set` always clears the previous value of the field
+//! first, so neighbouring fields are never clobbered.
+//!
+//! Field value types are declared with the `field_enum!`, `field_bits!` and
+//! `field_flag!` macros next to the register block they belong to.
+
+/// Value which can be stored in a register field
+pub trait FieldValue: Sized {
+    /// Position of the least significant bit of the field
+    const POS: u32;
+
+    /// Width of the field in bits (1..31)
+    const WIDTH: u32;
+
+    /// Raw value of the field, not shifted
+    fn bits(&self) -> u32;
+
+    /// Builds the value from raw field bits, `None` for reserved encodings
+    fn from_bits(bits: u32) -> Option<Self>;
+
+    /// Mask of the field inside the register
+    fn mask() -> u32 {
+        (!0u32 >> (32 - Self::WIDTH)) << Self::POS
+    }
+}
+
+/// Snapshot of a register value
+pub struct R {
+    bits: u32,
+}
+
+impl R {
+    pub fn new(bits: u32) -> R {
+        R {
+            bits,
+        }
+    }
+
+    /// Raw register value
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the value of the field
+    pub fn get<V: FieldValue>(&self) -> Option<V> {
+        V::from_bits((self.bits & V::mask()) >> V::POS)
+    }
+
+    /// Returns the value of the `index`-th field of a register made of
+    /// identical fields (e.g. one field per GPIO pin)
+    pub fn get_at<V: FieldValue>(&self, index: u32) -> Option<V> {
+        let shift: u32 = index * V::WIDTH;
+        V::from_bits((self.bits & (V::mask() << shift)) >> (V::POS + shift))
+    }
+}
+
+/// Register value under construction
+pub struct W {
+    bits: u32,
+}
+
+impl W {
+    pub fn new(bits: u32) -> W {
+        W {
+            bits,
+        }
+    }
+
+    /// Raw register value
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Overwrites the whole register value
+    pub unsafe fn set_bits(&mut self, bits: u32) -> &mut W {
+        self.bits = bits;
+        self
+    }
+
+    /// Replaces the value of the field
+    pub fn set<V: FieldValue>(&mut self, value: V) -> &mut W {
+        self.bits &= !V::mask();
+        self.bits |= (value.bits() << V::POS) & V::mask();
+        self
+    }
+
+    /// Replaces the value of the `index`-th field of a register made of
+    /// identical fields (e.g. one field per GPIO pin)
+    pub fn set_at<V: FieldValue>(&mut self, index: u32, value: V) -> &mut W {
+        let shift: u32 = index * V::WIDTH;
+        debug_assert!(V::POS + shift + V::WIDTH <= 32);
+
+        self.bits &= !(V::mask() << shift);
+        self.bits |= (value.bits() << (V::POS + shift)) & (V::mask() << shift);
+        self
+    }
+}
+
+/// Declares an enumerated field: every variant is one valid encoding
+///
+/// field_enum! {
+///     /// System clock switch
+///     pub enum Sw [0, 2] {
+///         Hsi = 0,
+///         Hse = 1,
+///         Pll = 2,
+///     }
+/// }
+macro_rules! field_enum {
+    ($(#[$attr:meta])* pub enum $name:ident [$pos:expr, $width:expr] {
+        $($(#[$var_attr:meta])* $var:ident = $val:expr),* $(,)*
+    }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq)]
+        pub enum $name {
+            $($(#[$var_attr])* $var = $val),*
+        }
+
+        impl $crate::reg_field::FieldValue for $name {
+            const POS: u32 = $pos;
+            const WIDTH: u32 = $width;
+
+            fn bits(&self) -> u32 {
+                *self as u32
+            }
+
+            fn from_bits(bits: u32) -> Option<$name> {
+                $(
+                    if bits == $val {
+                        return Some($name::$var);
+                    }
+                )*
+                None
+            }
+        }
+    }
+}
+
+/// Declares a numeric field; values are range-checked on construction
+///
+/// field_bits! {
+///     /// Main PLL multiplication factor for VCO
+///     pub struct PllN [6, 9];
+/// }
+macro_rules! field_bits {
+    ($(#[$attr:meta])* pub struct $name:ident [$pos:expr, $width:expr];) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq)]
+        pub struct $name(u32);
+
+        impl $name {
+            /// Returns `None` when `value` does not fit into the field
+            pub fn new(value: u32) -> Option<$name> {
+                // u64, fields can be 32 bits wide
+                if value as u64 >> $width == 0 {
+                    Some($name(value))
+                } else {
+                    None
+                }
+            }
+
+            /// Use the `bits!` macro for constants, it checks the range
+            /// at compile time
+            pub unsafe fn new_unchecked(value: u32) -> $name {
+                $name(value)
+            }
+
+            pub fn value(&self) -> u32 {
+                self.0
+            }
+        }
+
+        impl $crate::reg_field::FieldValue for $name {
+            const POS: u32 = $pos;
+            const WIDTH: u32 = $width;
+
+            fn bits(&self) -> u32 {
+                self.0
+            }
+
+            fn from_bits(bits: u32) -> Option<$name> {
+                Some($name(bits))
+            }
+        }
+    }
+}
+
+/// Declares a single bit field
+///
+/// field_flag! {
+///     /// HSE clock enable
+///     pub struct HseOn [16];
+/// }
+macro_rules! field_flag {
+    ($(#[$attr:meta])* pub struct $name:ident [$pos:expr];) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq)]
+        pub struct $name(pub bool);
+
+        impl $crate::reg_field::FieldValue for $name {
+            const POS: u32 = $pos;
+            const WIDTH: u32 = 1;
+
+            fn bits(&self) -> u32 {
+                self.0 as u32
+            }
+
+            fn from_bits(bits: u32) -> Option<$name> {
+                Some($name(bits != 0))
+            }
+        }
+    }
+}
+
+/// Builds a numeric field value from a constant, a value which does not fit
+/// into the field is a compile error
+///
+/// rcc.pllcfgr.modify(|_, w| w.set(bits!(PllN, 336)));
+#[allow(unused_macros)]
+macro_rules! bits {
+    ($name:ty, $value:expr) => {{
+        let _: [(); 0] = [(); (($value) as u64
+                                >> <$name as $crate::reg_field::FieldValue>::WIDTH) as usize];
+        unsafe { <$name>::new_unchecked($value) }
+    }}
+}
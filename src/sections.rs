@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+//! Placement of statics in CCM RAM and backup SRAM
+//!
+//! The 64 KB of CCM RAM are reached by the core only, DMA cannot access
+//! them and code cannot run from them, which makes them a good place for
+//! stacks and data the CPU works on alone. The reset handler copies the
+//! initial values of `.ccmram` from flash and zeroes `.ccmram_bss`, the CCM
+//! clock is enabled out of reset.
+//!
+//! The 4 KB of backup SRAM in `.bkpsram` are never initialized, they keep
+//! their contents across resets. They can only be accessed after
+//! `crash_dump::enable_backup_domain`.
+//!
+//! ccmram_bss! {
+//!     static mut TASK_STACK: [u32; 512] = [0; 512];
+//! }
+
+/// Places statics with an initial value in CCM RAM
+#[allow(unused_macros)]
+macro_rules! ccmram {
+    ($($(#[$attr:meta])* $vis:vis static mut $name:ident : $ty:ty = $init:expr;)*) => {
+        $(
+            $(#[$attr])*
+            #[cfg_attr(not(test), link_section = ".ccmram")]
+            $vis static mut $name: $ty = $init;
+        )*
+    }
+}
+
+/// Places zero initialized statics in CCM RAM; the initializer is not
+/// stored, it must be all zero
+#[allow(unused_macros)]
+macro_rules! ccmram_bss {
+    ($($(#[$attr:meta])* $vis:vis static mut $name:ident : $ty:ty = $init:expr;)*) => {
+        $(
+            $(#[$attr])*
+            #[cfg_attr(not(test), link_section = ".ccmram_bss")]
+            $vis static mut $name: $ty = $init;
+        )*
+    }
+}
+
+/// Places statics in backup SRAM; the initializer is not stored, a static
+/// holds whatever the previous run or power up left there
+#[allow(unused_macros)]
+macro_rules! bkpsram {
+    ($($(#[$attr:meta])* $vis:vis static mut $name:ident : $ty:ty = $init:expr;)*) => {
+        $(
+            $(#[$attr])*
+            #[cfg_attr(not(test), link_section = ".bkpsram")]
+            $vis static mut $name: $ty = $init;
+        )*
+    }
+}
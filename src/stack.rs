@@ -0,0 +1,183 @@
+#![allow(dead_code)]
+
+//! Main stack usage and overflow detection
+//!
+//! The reset handler paints the unused main stack with `STACK_PAINT`, so
+//! `high_water_mark` can later tell how deep the stack has been.
+//!
+//! With the `stack-guard` feature the linker script reserves a guard region
+//! right below the stack, which `enable_guard` makes inaccessible through
+//! the MPU. A push into it raises a MemManage fault instead of overwriting
+//! .bss, and the fault handlers report a stack overflow. The handlers turn
+//! the MPU off and switch to a stack of their own first, see `faults.rs`.
+//! A function with a frame larger than the guard can still jump over it.
+
+use core::ops::Range;
+#[cfg(not(test))]
+use core::{ ptr, slice };
+
+use ::core_cm4::barrier::{ dsb, isb };
+use ::core_cm4::core_cm4_regs::mpu::*;
+use ::core_cm4::fault::{ FaultCause, FaultReport };
+
+/// Fill pattern of the unused stack
+pub const STACK_PAINT       : u32   = 0xDEAD_BEEF;
+
+/// MPU region of the guard, the highest number wins where regions overlap
+pub const GUARD_REGION      : u32   = 7;
+
+/// Bytes below the stack pointer left alone by `paint`, the painting code
+/// itself needs them
+const PAINT_MARGIN          : u32   = 32;
+
+#[cfg(not(test))]
+extern "C" {
+    // Defined by the linker script: top and bottom of the main stack and
+    // bottom of the guard region
+    static _estack: u32;
+    static _sstack: u32;
+    static _sstack_guard: u32;
+}
+
+/// Addresses of the main stack
+#[cfg(not(test))]
+pub fn bounds() -> Range<u32> {
+    unsafe { &_sstack as *const u32 as u32..&_estack as *const u32 as u32 }
+}
+
+/// Addresses of the guard region, empty without the `stack-guard` feature
+#[cfg(not(test))]
+pub fn guard() -> Range<u32> {
+    unsafe { &_sstack_guard as *const u32 as u32..&_sstack as *const u32 as u32 }
+}
+
+/// Size of the main stack in bytes
+#[cfg(not(test))]
+pub fn size() -> usize {
+    let bounds = bounds();
+    (bounds.end - bounds.start) as usize
+}
+
+/// Most bytes of the main stack ever used since `paint`
+#[cfg(not(test))]
+pub fn high_water_mark() -> usize {
+    let bounds = bounds();
+    let stack = unsafe {
+        slice::from_raw_parts(bounds.start as *const u32, (bounds.end - bounds.start) as usize / 4)
+    };
+    used_bytes(stack)
+}
+
+/// Fills the main stack below the stack pointer with `STACK_PAINT`, called
+/// by the reset handler
+#[cfg(not(test))]
+pub unsafe fn paint() {
+    let end = current_sp() - PAINT_MARGIN;
+    let mut dst = &_sstack as *const u32 as *mut u32;
+    while (dst as u32) < end {
+        ptr::write_volatile(dst, STACK_PAINT);
+        dst = dst.offset(1);
+    }
+}
+
+/// Makes the guard region below the main stack inaccessible
+#[cfg(not(test))]
+pub fn enable_guard(mpu: &MpuRegs) {
+    enable_guard_region(mpu, &guard());
+}
+
+/// Bytes of `stack`, lowest word first, written since it was painted
+pub fn used_bytes(stack: &[u32]) -> usize {
+    let untouched = stack.iter().take_while(|&&word| word == STACK_PAINT).count();
+    (stack.len() - untouched) * 4
+}
+
+/// RBAR and RASR values for a no access, execute never region over `guard`;
+/// its size must be a power of two of at least 32 bytes and its start
+/// aligned to the size
+pub fn guard_region(guard: &Range<u32>) -> (u32, u32) {
+    let size = guard.end - guard.start;
+    debug_assert!(size >= 32 && size.is_power_of_two() && guard.start.is_multiple_of(size));
+
+    (guard.start | MPU_RBAR_VALID_MSK | GUARD_REGION,
+     MPU_RASR_XN_MSK | ((size.trailing_zeros() - 1) << MPU_RASR_SIZE_POS) | MPU_RASR_ENABLE_MSK)
+}
+
+/// Protects `guard` and enables the MPU; privileged code keeps the default
+/// memory map everywhere else
+pub fn enable_guard_region(mpu: &MpuRegs, guard: &Range<u32>) {
+    let (rbar, rasr) = guard_region(guard);
+    mpu.rbar.set(rbar);
+    mpu.rasr.set(rasr);
+    mpu.ctrl.set(MPU_CTRL_PRIVDEFENA_MSK | MPU_CTRL_ENABLE_MSK);
+
+    // The region applies from the next instruction on
+    dsb();
+    isb();
+}
+
+/// True if the fault comes from a push into `guard`, either by code or by
+/// the exception entry stacking the frame at `frame_address`
+pub fn hit_guard(report: &FaultReport, frame_address: u32, guard: &Range<u32>) -> bool {
+    let in_guard = |address: u32| guard.contains(&address);
+    report.mmfar.is_some_and(in_guard)
+        || (report.has(FaultCause::MemManageStacking) && in_guard(frame_address))
+}
+
+#[cfg(target_arch = "arm")]
+pub fn current_sp() -> u32 {
+    let sp: u32;
+    unsafe { asm!("mov $0, sp" : "=r"(sp) ::: "volatile"); }
+    sp
+}
+
+#[cfg(not(target_arch = "arm"))]
+pub fn current_sp() -> u32 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::core_cm4::constants::MPU_BASE;
+    use ::core_cm4::core_cm4_regs::scb::*;
+    use ::core_cm4::exceptions::Exception;
+    use ::core_cm4::fault::ExceptionFrame;
+
+    const GUARD     : Range<u32> = 0x2001_FB00..0x2001_FC00;
+
+    #[test]
+    fn used_bytes_counts_from_the_first_overwritten_word() {
+        assert_eq!(used_bytes(&[STACK_PAINT; 8]), 0);
+        assert_eq!(used_bytes(&[STACK_PAINT, STACK_PAINT, 0, STACK_PAINT, 1]), 12);
+    }
+
+    #[test]
+    fn guard_region_is_no_access_and_enables_the_mpu() {
+        let mpu = unsafe { MpuRegs::init() };
+        enable_guard_region(&mpu, &GUARD);
+
+        assert_eq!(mock_regs::read(MPU_BASE + 0x0C), 0x2001_FB00 | MPU_RBAR_VALID_MSK | 7);
+        // 256 bytes: SIZE 7, AP 0
+        assert_eq!(mock_regs::read(MPU_BASE + 0x10),
+                   MPU_RASR_XN_MSK | (7 << MPU_RASR_SIZE_POS) | MPU_RASR_ENABLE_MSK);
+        assert_eq!(mock_regs::read(MPU_BASE + 0x04), MPU_CTRL_PRIVDEFENA_MSK | MPU_CTRL_ENABLE_MSK);
+    }
+
+    #[test]
+    fn pushes_into_the_guard_are_overflows() {
+        let frame = ExceptionFrame::default();
+        let push = FaultReport::new(Exception::MemManage, frame,
+                                    SCB_CFSR_DACCVIOL_MSK | SCB_CFSR_MMARVALID_MSK, 0,
+                                    0x2001_FBFC, 0);
+        let stacking = FaultReport::new(Exception::MemManage, frame, SCB_CFSR_MSTKERR_MSK, 0, 0, 0);
+        let elsewhere = FaultReport::new(Exception::MemManage, frame,
+                                         SCB_CFSR_DACCVIOL_MSK | SCB_CFSR_MMARVALID_MSK, 0,
+                                         0x2001_FC00, 0);
+
+        assert!(hit_guard(&push, 0x2001_FC20, &GUARD));
+        assert!(hit_guard(&stacking, 0x2001_FBE0, &GUARD));
+        assert!(!hit_guard(&elsewhere, 0x2001_FC20, &GUARD));
+    }
+}
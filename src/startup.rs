@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+//! Reset handler: sets up RAM, then runs `system_init` and `main`
+//!
+//! With `data-in-ext-sram` the FSMC is set up first, with `vect-tab-sram`
+//! the vector table is copied to the start of SRAM for `system_init` to
+//! point VTOR at. CCM RAM is set up like RAM, backup SRAM is left alone,
+//! see `sections.rs`.
+
+#[cfg(not(test))]
+use core::ptr;
+
+#[cfg(not(test))]
+extern "C" {
+    // Defined by the linker script
+    static _sidata: u32;
+    static mut _sdata: u32;
+    static mut _edata: u32;
+    static mut _sbss: u32;
+    static mut _ebss: u32;
+    static _siccmram: u32;
+    static mut _sccmram: u32;
+    static mut _eccmram: u32;
+    static mut _sccmram_bss: u32;
+    static mut _eccmram_bss: u32;
+    static _svector: u32;
+    static _evector: u32;
+    static mut _svector_ram: u32;
+}
+
+/// Address of the vector table copy made with `vect-tab-sram`
+#[cfg(not(test))]
+pub fn vector_table_ram() -> u32 {
+    ptr::addr_of!(_svector_ram) as u32
+}
+
+/// The linker script puts the copy at the start of RAM
+#[cfg(test)]
+pub fn vector_table_ram() -> u32 {
+    ::stm32f4xx::constants::SRAM1_BASE
+}
+
+/// Second word of the vector table, right after the initial stack pointer
+#[cfg(not(test))]
+#[link_section = ".isr_vector.reset_vector"]
+#[no_mangle]
+pub static RESET_VECTOR: unsafe extern "C" fn() -> ! = Reset_Handler;
+
+#[cfg(not(test))]
+#[allow(non_snake_case)]
+#[no_mangle]
+pub unsafe extern "C" fn Reset_Handler() -> ! {
+    // For the high-water mark, see stack.rs
+    ::stack::paint();
+
+    // Statics may live in the external SRAM, it has to work before RAM is
+    // set up
+    #[cfg(feature = "data-in-ext-sram")]
+    ::system_init_ext_mem_ctl();
+
+    // Copy the initial values of .data from flash
+    let mut src = ptr::addr_of!(_sidata);
+    let mut dst = ptr::addr_of_mut!(_sdata);
+    while dst < ptr::addr_of_mut!(_edata) {
+        ptr::write_volatile(dst, ptr::read(src));
+        dst = dst.offset(1);
+        src = src.offset(1);
+    }
+
+    // Zero .bss
+    let mut dst = ptr::addr_of_mut!(_sbss);
+    while dst < ptr::addr_of_mut!(_ebss) {
+        ptr::write_volatile(dst, 0);
+        dst = dst.offset(1);
+    }
+
+    // Same for CCM RAM
+    let mut src = ptr::addr_of!(_siccmram);
+    let mut dst = ptr::addr_of_mut!(_sccmram);
+    while dst < ptr::addr_of_mut!(_eccmram) {
+        ptr::write_volatile(dst, ptr::read(src));
+        dst = dst.offset(1);
+        src = src.offset(1);
+    }
+
+    let mut dst = ptr::addr_of_mut!(_sccmram_bss);
+    while dst < ptr::addr_of_mut!(_eccmram_bss) {
+        ptr::write_volatile(dst, 0);
+        dst = dst.offset(1);
+    }
+
+    // Copy the vector table to SRAM, VTOR moves there in system_init
+    if cfg!(feature = "vect-tab-sram") {
+        let mut src = ptr::addr_of!(_svector);
+        let mut dst = ptr::addr_of_mut!(_svector_ram);
+        while src < ptr::addr_of!(_evector) {
+            ptr::write_volatile(dst, ptr::read(src));
+            dst = dst.offset(1);
+            src = src.offset(1);
+        }
+    }
+
+    ::system_init();
+    ::main();
+
+    // Nothing to return to
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+/// Runs for every exception and interrupt without a handler of its own,
+/// stops here so the state can be examined with a debugger
+#[cfg(not(test))]
+#[no_mangle]
+pub unsafe extern "C" fn DefaultHandler() {
+    // Spinning is the point, the debugger halts it here
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
@@ -0,0 +1,101 @@
+#![allow(dead_code)]
+
+//! Bit-band alias access
+//!
+//! The Cortex-M4 maps every bit of the first 1 MB of the SRAM and of the
+//! peripheral regions to a word of an alias region. Writing 0 or 1 to the
+//! alias word clears or sets the bit in a single bus transaction, so unlike a
+//! read-modify-write it can not lose a concurrent update of another bit.
+
+use ::volatile_reg32::VolatileReg32;
+use super::constants::{ SRAM1_BASE,
+                        SRAM1_BB_BASE,
+                        PERIPH_BASE,
+                        PERIPH_BB_BASE,
+                        };
+
+/// Size of each of the bit-band regions
+const BIT_BAND_REGION_SIZE  : u32   = 0x00100000;
+
+/// Returns the address of the alias word for `bit` of the 32-bit word at
+/// `address`, `None` when the word is outside of the bit-band regions
+pub fn alias_address(address: u32, bit: u32) -> Option<u32> {
+    if bit >= 32 || address & 0x3 != 0 {
+        return None;
+    }
+
+    let (region_base, alias_base) =
+        if address >= SRAM1_BASE && address - SRAM1_BASE < BIT_BAND_REGION_SIZE {
+            (SRAM1_BASE, SRAM1_BB_BASE)
+        } else if address >= PERIPH_BASE && address - PERIPH_BASE < BIT_BAND_REGION_SIZE {
+            (PERIPH_BASE, PERIPH_BB_BASE)
+        } else {
+            return None;
+        };
+
+    Some(alias_base + (address - region_base) * 32 + bit * 4)
+}
+
+/// Returns the alias word of `bit` of `reg` as a register of its own, reading
+/// it gives 0 or 1 and writing it changes only that bit
+///
+/// Panics if `reg` is outside of the bit-band regions.
+pub fn alias(reg: &VolatileReg32, bit: u32) -> VolatileReg32 {
+    let address = alias_address(reg.address(), bit)
+        .expect("register is not bit-band addressable");
+
+    VolatileReg32::new(address as *mut u32)
+}
+
+/// Same as `alias` for a word in SRAM, e.g. a `static` flag word
+pub fn alias_ram(word: *mut u32, bit: u32) -> VolatileReg32 {
+    let address = alias_address(word as usize as u32, bit)
+        .expect("variable is not bit-band addressable");
+
+    VolatileReg32::new(address as *mut u32)
+}
+
+/// Atomically sets `bit` of `reg`
+pub fn set_bit(reg: &VolatileReg32, bit: u32) {
+    alias(reg, bit).set(1);
+}
+
+/// Atomically clears `bit` of `reg`
+pub fn clear_bit(reg: &VolatileReg32, bit: u32) {
+    alias(reg, bit).set(0);
+}
+
+/// Reads a single bit of `reg`
+pub fn read_bit(reg: &VolatileReg32, bit: u32) -> bool {
+    alias(reg, bit).get() & 1 != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+    use ::stm32f4xx::constants::{ RCC_BASE, BKPSRAM_BASE, BKPSRAM_BB_BASE, CCMDATARAM_BASE };
+
+    #[test]
+    fn alias_address_of_peripheral_and_sram_bits() {
+        assert_eq!(alias_address(RCC_BASE + 0x30, 3), Some(0x4247060C));
+        assert_eq!(alias_address(BKPSRAM_BASE, 0), Some(BKPSRAM_BB_BASE));
+        assert_eq!(alias_address(SRAM1_BASE + 0x100, 31), Some(0x2200207C));
+        assert_eq!(alias_address(CCMDATARAM_BASE, 0), None);
+        assert_eq!(alias_address(RCC_BASE + 0x31, 0), None);
+    }
+
+    #[test]
+    fn set_and_clear_touch_only_one_bit() {
+        let reg = VolatileReg32::new((RCC_BASE + 0x40) as *mut u32);
+        reg.set(0x00000021);
+
+        set_bit(&reg, 28);
+        assert_eq!(reg.get(), 0x10000021);
+        assert!(read_bit(&reg, 28));
+
+        clear_bit(&reg, 5);
+        assert_eq!(mock_regs::read(RCC_BASE + 0x40), 0x10000001);
+        assert!(!read_bit(&reg, 5));
+    }
+}
@@ -3,9 +3,21 @@
 //! stm32f4xx_const module
 //! This module contans main constants for stm32f4xx series MCUs
 
-/// FLASH(up to 1 MB) base address in the alias region
+/// FLASH(up to 1 MB, 2 MB on STM32F42x) base address in the alias region
 pub const FLASH_BASE            : u32   = 0x08000000;
 
+/// FLASH size of the largest part of the family (xG: 1 MB, xI: 2 MB)
+#[cfg(feature = "stm32f40x")]
+pub const FLASH_SIZE            : u32   = 1024 * 1024;
+#[cfg(feature = "stm32f42x")]
+pub const FLASH_SIZE            : u32   = 2048 * 1024;
+
+/// Size of the contiguous SRAM starting at SRAM1_BASE
+#[cfg(feature = "stm32f40x")]
+pub const SRAM_SIZE             : u32   = 128 * 1024;
+#[cfg(feature = "stm32f42x")]
+pub const SRAM_SIZE             : u32   = 192 * 1024;
+
 /// CCM(core coupled memory) data RAM(64 KB) base address in the alias region
 pub const CCMDATARAM_BASE       : u32   = 0x10000000;
 
@@ -15,29 +27,35 @@ pub const SRAM1_BASE            : u32   = 0x20000000;
 /// SRAM2(16 KB) base address in the alias region
 pub const SRAM2_BASE            : u32	= 0x2001C000;
 
+/// SRAM3(64 KB) base address in the alias region
+#[cfg(feature = "stm32f42x")]
+pub const SRAM3_BASE            : u32	= 0x20020000;
+
 /// Peripheral base address in the alias region
 pub const PERIPH_BASE           : u32	= 0x40000000;
 
 /// Backup SRAM(4 KB) base address in the alias region
 pub const BKPSRAM_BASE          : u32	= 0x40024000;
 
-/// FSMC registers base address
+/// FSMC (FMC on STM32F42x) registers base address
 pub const FSMC_R_BASE           : u32	= 0xA0000000;
 
 /// CCM(core coupled memory) data RAM(64 KB) base address in the bit-band region
+/// (the CCM is outside of the Cortex-M4 bit-band regions, accesses to this
+/// alias do not reach it)
 pub const CCMDATARAM_BB_BASE    : u32	= 0x12000000;
 
 /// SRAM1(112 KB) base address in the bit-band region
 pub const SRAM1_BB_BASE         : u32	= 0x22000000;
 
 /// SRAM2(16 KB) base address in the bit-band region
-pub const SRAM2_BB_BASE         : u32	= 0x2201C000;
+pub const SRAM2_BB_BASE         : u32	= 0x22380000;
 
 /// Peripheral base address in the bit-band region
 pub const PERIPH_BB_BASE        : u32	= 0x42000000;
 
 /// Backup SRAM(4 KB) base address in the bit-band region
-pub const BKPSRAM_BB_BASE       : u32	= 0x42024000;
+pub const BKPSRAM_BB_BASE       : u32	= 0x42480000;
 
 /// Peripheral memory map
 pub const APB1PERIPH_BASE       : u32   = PERIPH_BASE;
@@ -73,6 +91,10 @@ pub const CAN1_BASE             : u32   = APB1PERIPH_BASE + 0x6400;
 pub const CAN2_BASE             : u32   = APB1PERIPH_BASE + 0x6800;
 pub const PWR_BASE              : u32   = APB1PERIPH_BASE + 0x7000;
 pub const DAC_BASE              : u32   = APB1PERIPH_BASE + 0x7400;
+#[cfg(feature = "stm32f42x")]
+pub const UART7_BASE            : u32   = APB1PERIPH_BASE + 0x7800;
+#[cfg(feature = "stm32f42x")]
+pub const UART8_BASE            : u32   = APB1PERIPH_BASE + 0x7C00;
 
 /// APB2 peripherals
 pub const TIM1_BASE             : u32   = APB2PERIPH_BASE + 0x0000;
@@ -85,11 +107,21 @@ pub const ADC3_BASE             : u32   = APB2PERIPH_BASE + 0x2200;
 pub const ADC_BASE              : u32   = APB2PERIPH_BASE + 0x2300;
 pub const SDIO_BASE             : u32   = APB2PERIPH_BASE + 0x2C00;
 pub const SPI1_BASE             : u32   = APB2PERIPH_BASE + 0x3000;
+#[cfg(feature = "stm32f42x")]
+pub const SPI4_BASE             : u32   = APB2PERIPH_BASE + 0x3400;
 pub const SYSCFG_BASE           : u32   = APB2PERIPH_BASE + 0x3800;
 pub const EXTI_BASE             : u32   = APB2PERIPH_BASE + 0x3C00;
 pub const TIM9_BASE             : u32   = APB2PERIPH_BASE + 0x4000;
 pub const TIM10_BASE            : u32   = APB2PERIPH_BASE + 0x4400;
 pub const TIM11_BASE            : u32   = APB2PERIPH_BASE + 0x4800;
+#[cfg(feature = "stm32f42x")]
+pub const SPI5_BASE             : u32   = APB2PERIPH_BASE + 0x5000;
+#[cfg(feature = "stm32f42x")]
+pub const SPI6_BASE             : u32   = APB2PERIPH_BASE + 0x5400;
+#[cfg(feature = "stm32f42x")]
+pub const SAI1_BASE             : u32   = APB2PERIPH_BASE + 0x5800;
+#[cfg(feature = "ltdc")]
+pub const LTDC_BASE             : u32   = APB2PERIPH_BASE + 0x6800;
 
 /// AHB1 peripherals
 pub const GPIOA_BASE            : u32   = AHB1PERIPH_BASE + 0x0000;
@@ -101,6 +133,10 @@ pub const GPIOF_BASE            : u32   = AHB1PERIPH_BASE + 0x1400;
 pub const GPIOG_BASE            : u32   = AHB1PERIPH_BASE + 0x1800;
 pub const GPIOH_BASE            : u32   = AHB1PERIPH_BASE + 0x1C00;
 pub const GPIOI_BASE            : u32   = AHB1PERIPH_BASE + 0x2000;
+#[cfg(feature = "stm32f42x")]
+pub const GPIOJ_BASE            : u32   = AHB1PERIPH_BASE + 0x2400;
+#[cfg(feature = "stm32f42x")]
+pub const GPIOK_BASE            : u32   = AHB1PERIPH_BASE + 0x2800;
 pub const CRC_BASE              : u32   = AHB1PERIPH_BASE + 0x3000;
 pub const RCC_BASE              : u32   = AHB1PERIPH_BASE + 0x3800;
 pub const FLASH_R_BASE          : u32   = AHB1PERIPH_BASE + 0x3C00;
@@ -122,15 +158,25 @@ pub const DMA2_STREAM4_BASE     : u32   = DMA2_BASE + 0x070;
 pub const DMA2_STREAM5_BASE     : u32   = DMA2_BASE + 0x088;
 pub const DMA2_STREAM6_BASE     : u32   = DMA2_BASE + 0x0A0;
 pub const DMA2_STREAM7_BASE     : u32   = DMA2_BASE + 0x0B8;
+#[cfg(feature = "eth")]
 pub const ETH_BASE              : u32   = AHB1PERIPH_BASE + 0x8000;
+#[cfg(feature = "eth")]
 pub const ETH_MAC_BASE          : u32   = ETH_BASE;
+#[cfg(feature = "eth")]
 pub const ETH_MMC_BASE          : u32   = ETH_BASE + 0x0100;
+#[cfg(feature = "eth")]
 pub const ETH_PTP_BASE          : u32   = ETH_BASE + 0x0700;
+#[cfg(feature = "eth")]
 pub const ETH_DMA_BASE          : u32   = ETH_BASE + 0x1000;
+#[cfg(feature = "stm32f42x")]
+pub const DMA2D_BASE            : u32   = AHB1PERIPH_BASE + 0xB000;
 
 /// AHB2 peripherals
+#[cfg(feature = "dcmi")]
 pub const DCMI_BASE             : u32   = AHB2PERIPH_BASE + 0x50000;
+#[cfg(feature = "cryp")]
 pub const CRYP_BASE             : u32   = AHB2PERIPH_BASE + 0x60000;
+#[cfg(feature = "cryp")]
 pub const HASH_BASE             : u32   = AHB2PERIPH_BASE + 0x60400;
 pub const RNG_BASE              : u32   = AHB2PERIPH_BASE + 0x60800;
 
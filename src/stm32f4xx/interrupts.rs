@@ -0,0 +1,289 @@
+#![allow(dead_code)]
+
+//! Device interrupts and the device part of the vector table
+//!
+//! Every interrupt is routed to a symbol of its own name (`TIM7`, `EXTI0`,
+//! ...). The linker script points the symbols nobody defines to
+//! `DefaultHandler`; `interrupt!` defines one for a Rust function.
+//!
+//! Slots of peripherals the selected chip does not have are zero.
+
+#[cfg(not(test))]
+use ::core_cm4::exceptions::Vector;
+
+#[cfg(not(test))]
+macro_rules! one {
+    ($t:tt) => (1)
+}
+
+macro_rules! interrupts {
+    ($($(#[doc = $doc:expr])* $(#[cfg($pred:meta)])* $name:ident = $irq:expr,)*) => {
+        /// Interrupt lines, numbered as in the NVIC
+        // Named like the handler symbols of the vector table
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        pub enum Interrupt {
+            $($(#[doc = $doc])* $(#[cfg($pred)])* $name = $irq,)*
+        }
+
+        #[cfg(not(test))]
+        extern "C" {
+            $($(#[cfg($pred)])* fn $name();)*
+        }
+
+        /// Device part of the vector table, placed after the exceptions
+        #[cfg(not(test))]
+        #[link_section = ".isr_vector.interrupts"]
+        #[no_mangle]
+        pub static INTERRUPTS: [Vector; 0 $(+ one!($name))*] = [$(
+            {
+                $(#[cfg($pred)])*
+                let vector: Vector = Some($name);
+                #[cfg(not(all($($pred),*)))]
+                let vector: Vector = None;
+                vector
+            },
+        )*];
+
+        /// Vector table slot of every entry, in table order
+        #[cfg(test)]
+        const SLOTS: &'static [u32] = &[$($irq),*];
+    }
+}
+
+/// Binds a function to an interrupt; an interrupt name the selected chip
+/// does not have is a compile error
+///
+/// interrupt!(TIM7, on_tim7_update);
+#[allow(unused_macros)]
+macro_rules! interrupt {
+    ($name:ident, $handler:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub extern "C" fn $name() {
+            let _ = $crate::stm32f4xx::interrupts::Interrupt::$name;
+            let handler: fn() = $handler;
+            handler();
+        }
+    }
+}
+
+interrupts! {
+    /// Window watchdog
+    WWDG = 0,
+    /// PVD through EXTI Line detection
+    PVD = 1,
+    /// Tamper and TimeStamps through the EXTI line
+    TAMP_STAMP = 2,
+    /// RTC Wakeup through the EXTI line
+    RTC_WKUP = 3,
+    /// FLASH
+    FLASH = 4,
+    /// RCC
+    RCC = 5,
+    /// EXTI Line0
+    EXTI0 = 6,
+    /// EXTI Line1
+    EXTI1 = 7,
+    /// EXTI Line2
+    EXTI2 = 8,
+    /// EXTI Line3
+    EXTI3 = 9,
+    /// EXTI Line4
+    EXTI4 = 10,
+    /// DMA1 Stream 0
+    DMA1_STREAM0 = 11,
+    /// DMA1 Stream 1
+    DMA1_STREAM1 = 12,
+    /// DMA1 Stream 2
+    DMA1_STREAM2 = 13,
+    /// DMA1 Stream 3
+    DMA1_STREAM3 = 14,
+    /// DMA1 Stream 4
+    DMA1_STREAM4 = 15,
+    /// DMA1 Stream 5
+    DMA1_STREAM5 = 16,
+    /// DMA1 Stream 6
+    DMA1_STREAM6 = 17,
+    /// ADC1, ADC2 and ADC3
+    ADC = 18,
+    /// CAN1 TX
+    CAN1_TX = 19,
+    /// CAN1 RX0
+    CAN1_RX0 = 20,
+    /// CAN1 RX1
+    CAN1_RX1 = 21,
+    /// CAN1 SCE
+    CAN1_SCE = 22,
+    /// EXTI lines 9..5
+    EXTI9_5 = 23,
+    /// TIM1 Break and TIM9
+    TIM1_BRK_TIM9 = 24,
+    /// TIM1 Update and TIM10
+    TIM1_UP_TIM10 = 25,
+    /// TIM1 Trigger and Commutation and TIM11
+    TIM1_TRG_COM_TIM11 = 26,
+    /// TIM1 Capture Compare
+    TIM1_CC = 27,
+    /// TIM2
+    TIM2 = 28,
+    /// TIM3
+    TIM3 = 29,
+    /// TIM4
+    TIM4 = 30,
+    /// I2C1 Event
+    I2C1_EV = 31,
+    /// I2C1 Error
+    I2C1_ER = 32,
+    /// I2C2 Event
+    I2C2_EV = 33,
+    /// I2C2 Error
+    I2C2_ER = 34,
+    /// SPI1
+    SPI1 = 35,
+    /// SPI2
+    SPI2 = 36,
+    /// USART1
+    USART1 = 37,
+    /// USART2
+    USART2 = 38,
+    /// USART3
+    USART3 = 39,
+    /// EXTI lines 15..10
+    EXTI15_10 = 40,
+    /// RTC Alarm (A and B) through EXTI Line
+    RTC_ALARM = 41,
+    /// USB OTG FS Wakeup through EXTI line
+    OTG_FS_WKUP = 42,
+    /// TIM8 Break and TIM12
+    TIM8_BRK_TIM12 = 43,
+    /// TIM8 Update and TIM13
+    TIM8_UP_TIM13 = 44,
+    /// TIM8 Trigger and Commutation and TIM14
+    TIM8_TRG_COM_TIM14 = 45,
+    /// TIM8 Capture Compare
+    TIM8_CC = 46,
+    /// DMA1 Stream 7
+    DMA1_STREAM7 = 47,
+    /// FSMC (FMC on STM32F42x)
+    FSMC = 48,
+    /// SDIO
+    SDIO = 49,
+    /// TIM5
+    TIM5 = 50,
+    /// SPI3
+    SPI3 = 51,
+    /// UART4
+    UART4 = 52,
+    /// UART5
+    UART5 = 53,
+    /// TIM6 and DAC1&2 underrun errors
+    TIM6_DAC = 54,
+    /// TIM7
+    TIM7 = 55,
+    /// DMA2 Stream 0
+    DMA2_STREAM0 = 56,
+    /// DMA2 Stream 1
+    DMA2_STREAM1 = 57,
+    /// DMA2 Stream 2
+    DMA2_STREAM2 = 58,
+    /// DMA2 Stream 3
+    DMA2_STREAM3 = 59,
+    /// DMA2 Stream 4
+    DMA2_STREAM4 = 60,
+    /// Ethernet
+    #[cfg(feature = "eth")]
+    ETH = 61,
+    /// Ethernet Wakeup through EXTI line
+    #[cfg(feature = "eth")]
+    ETH_WKUP = 62,
+    /// CAN2 TX
+    CAN2_TX = 63,
+    /// CAN2 RX0
+    CAN2_RX0 = 64,
+    /// CAN2 RX1
+    CAN2_RX1 = 65,
+    /// CAN2 SCE
+    CAN2_SCE = 66,
+    /// USB OTG FS
+    OTG_FS = 67,
+    /// DMA2 Stream 5
+    DMA2_STREAM5 = 68,
+    /// DMA2 Stream 6
+    DMA2_STREAM6 = 69,
+    /// DMA2 Stream 7
+    DMA2_STREAM7 = 70,
+    /// USART6
+    USART6 = 71,
+    /// I2C3 event
+    I2C3_EV = 72,
+    /// I2C3 error
+    I2C3_ER = 73,
+    /// USB OTG HS End Point 1 Out
+    OTG_HS_EP1_OUT = 74,
+    /// USB OTG HS End Point 1 In
+    OTG_HS_EP1_IN = 75,
+    /// USB OTG HS Wakeup through EXTI
+    OTG_HS_WKUP = 76,
+    /// USB OTG HS
+    OTG_HS = 77,
+    /// DCMI
+    #[cfg(feature = "dcmi")]
+    DCMI = 78,
+    /// CRYP crypto
+    #[cfg(feature = "cryp")]
+    CRYP = 79,
+    /// Hash and Rng
+    HASH_RNG = 80,
+    /// FPU
+    FPU = 81,
+    /// UART7
+    #[cfg(feature = "stm32f42x")]
+    UART7 = 82,
+    /// UART8
+    #[cfg(feature = "stm32f42x")]
+    UART8 = 83,
+    /// SPI4
+    #[cfg(feature = "stm32f42x")]
+    SPI4 = 84,
+    /// SPI5
+    #[cfg(feature = "stm32f42x")]
+    SPI5 = 85,
+    /// SPI6
+    #[cfg(feature = "stm32f42x")]
+    SPI6 = 86,
+    /// SAI1
+    #[cfg(feature = "stm32f42x")]
+    SAI1 = 87,
+    /// LTDC
+    #[cfg(feature = "ltdc")]
+    LTDC = 88,
+    /// LTDC error
+    #[cfg(feature = "ltdc")]
+    LTDC_ER = 89,
+    /// DMA2D
+    #[cfg(feature = "stm32f42x")]
+    DMA2D = 90,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_in_slot_order() {
+        for (index, slot) in SLOTS.iter().enumerate() {
+            assert_eq!(*slot, index as u32);
+        }
+    }
+
+    fn on_tim7() {}
+
+    interrupt!(TIM7, on_tim7);
+
+    #[test]
+    fn bound_handler_is_callable() {
+        TIM7();
+        assert_eq!(Interrupt::TIM7 as u32, 55);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod bit_band;
 pub mod constants;
+#[macro_use]
+pub mod interrupts;
 pub mod regs;
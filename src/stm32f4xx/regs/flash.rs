@@ -2,6 +2,7 @@
 
 //! FLASH registers
 
+use ::volatile_reg8::*;
 use ::volatile_reg32::*;
 use super::constants::FLASH_R_BASE;
 
@@ -11,11 +12,14 @@ pub struct FlashRegs{
     /// FLASH access control register
     pub acr     : VolatileReg32,
 
+    /// Byte 0 of FLASH_ACR (latency), for byte-wide latency updates
+    pub acr_byte0   : VolatileReg8,
+
     /// FLASH key register
-    pub keyr    : VolatileReg32,
+    pub keyr    : WriteOnlyReg32,
 
     /// FLASH option key register
-    pub optkeyr : VolatileReg32,
+    pub optkeyr : WriteOnlyReg32,
 
     /// FLASH status register
     pub sr      : VolatileReg32,
@@ -29,13 +33,16 @@ pub struct FlashRegs{
 
 impl FlashRegs {
 
-    pub fn init() -> FlashRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> FlashRegs {
         let flash_base: *mut u32 = FLASH_R_BASE as *mut u32;
 
         let flash = FlashRegs {
             acr     : VolatileReg32::new(flash_base),
-            keyr    : VolatileReg32::new_offset(flash_base, 1),
-            optkeyr : VolatileReg32::new_offset(flash_base, 2),
+            acr_byte0   : VolatileReg8::new(FLASH_ACR_BYTE0_ADDRESS as *mut u8),
+            keyr    : WriteOnlyReg32::new_offset(flash_base, 1),
+            optkeyr : WriteOnlyReg32::new_offset(flash_base, 2),
             sr      : VolatileReg32::new_offset(flash_base, 3),
             cr      : VolatileReg32::new_offset(flash_base, 4),
             optcr   : VolatileReg32::new_offset(flash_base, 5),
@@ -116,3 +123,75 @@ pub const FLASH_OPTCR_N_WRP_8       : u32   = 0x01000000;
 pub const FLASH_OPTCR_N_WRP_9       : u32   = 0x02000000;
 pub const FLASH_OPTCR_N_WRP_10      : u32   = 0x04000000;
 pub const FLASH_OPTCR_N_WRP_11      : u32   = 0x08000000;
+
+/// Typed fields of the FLASH_ACR register
+pub mod acr {
+    field_enum! {
+        /// Latency, number of wait states
+        pub enum Latency [0, 3] {
+            Ws0     = 0,
+            Ws1     = 1,
+            Ws2     = 2,
+            Ws3     = 3,
+            Ws4     = 4,
+            Ws5     = 5,
+            Ws6     = 6,
+            Ws7     = 7,
+        }
+    }
+    field_flag! { /// Prefetch enable
+                  pub struct PrftEn [8]; }
+    field_flag! { /// Instruction cache enable
+                  pub struct IcEn [9]; }
+    field_flag! { /// Data cache enable
+                  pub struct DcEn [10]; }
+    field_flag! { /// Instruction cache reset
+                  pub struct IcRst [11]; }
+    field_flag! { /// Data cache reset
+                  pub struct DcRst [12]; }
+}
+
+/// Typed fields of the FLASH_SR register
+pub mod sr {
+    field_flag! { /// End of operation
+                  pub struct Eop [0]; }
+    field_flag! { /// Operation error
+                  pub struct Sop [1]; }
+    field_flag! { /// Write protection error
+                  pub struct WrpErr [4]; }
+    field_flag! { /// Programming alignment error
+                  pub struct PgaErr [5]; }
+    field_flag! { /// Programming parallelism error
+                  pub struct PgpErr [6]; }
+    field_flag! { /// Programming sequence error
+                  pub struct PgsErr [7]; }
+    field_flag! { /// Busy
+                  pub struct Bsy [16]; }
+}
+
+/// Typed fields of the FLASH_CR register
+pub mod cr {
+    field_flag! { /// Programming
+                  pub struct Pg [0]; }
+    field_flag! { /// Sector Erase
+                  pub struct Ser [1]; }
+    field_flag! { /// Mass Erase
+                  pub struct Mer [2]; }
+    field_bits! { /// Sector number
+                  pub struct Snb [3, 4]; }
+    field_enum! {
+        /// Program size
+        pub enum Psize [8, 2] {
+            X8      = 0,
+            X16     = 1,
+            X32     = 2,
+            X64     = 3,
+        }
+    }
+    field_flag! { /// Start
+                  pub struct Strt [16]; }
+    field_flag! { /// End of operation interrupt enable
+                  pub struct EopIe [24]; }
+    field_flag! { /// Lock
+                  pub struct Lock [31]; }
+}
@@ -0,0 +1,1410 @@
+#![allow(dead_code)]
+
+//! Flexible static memory controller registers
+//!
+//! Generated by tools/svd2regs from STM32F40x.svd, do not edit.
+
+use ::volatile_reg32::*;
+
+pub const FSMC_BASE : u32   = 0xA0000000;
+
+
+pub struct FsmcRegs
+{
+    /// SRAM/NOR-Flash chip-select control register 1
+    pub bcr1  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select timing register 1
+    pub btr1  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select control register 2
+    pub bcr2  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select timing register 2
+    pub btr2  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select control register 3
+    pub bcr3  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select timing register 3
+    pub btr3  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select control register 4
+    pub bcr4  : VolatileReg32,
+
+    /// SRAM/NOR-Flash chip-select timing register 4
+    pub btr4  : VolatileReg32,
+
+    /// PC Card/NAND Flash control register 2
+    pub pcr2  : VolatileReg32,
+
+    /// FIFO status and interrupt register 2
+    pub sr2   : VolatileReg32,
+
+    /// Common memory space timing register 2
+    pub pmem2 : VolatileReg32,
+
+    /// Attribute memory space timing register 2
+    pub patt2 : VolatileReg32,
+
+    /// ECC result register 2
+    pub eccr2 : ReadOnlyReg32,
+
+    /// PC Card/NAND Flash control register 3
+    pub pcr3  : VolatileReg32,
+
+    /// FIFO status and interrupt register 3
+    pub sr3   : VolatileReg32,
+
+    /// Common memory space timing register 3
+    pub pmem3 : VolatileReg32,
+
+    /// Attribute memory space timing register 3
+    pub patt3 : VolatileReg32,
+
+    /// ECC result register 3
+    pub eccr3 : ReadOnlyReg32,
+
+    /// PC Card/NAND Flash control register 4
+    pub pcr4  : VolatileReg32,
+
+    /// FIFO status and interrupt register 4
+    pub sr4   : VolatileReg32,
+
+    /// Common memory space timing register 4
+    pub pmem4 : VolatileReg32,
+
+    /// Attribute memory space timing register 4
+    pub patt4 : VolatileReg32,
+
+    /// I/O space timing register 4
+    pub pio4  : VolatileReg32,
+
+    /// SRAM/NOR-Flash write timing register 1
+    pub bwtr1 : VolatileReg32,
+
+    /// SRAM/NOR-Flash write timing register 2
+    pub bwtr2 : VolatileReg32,
+
+    /// SRAM/NOR-Flash write timing register 3
+    pub bwtr3 : VolatileReg32,
+
+    /// SRAM/NOR-Flash write timing register 4
+    pub bwtr4 : VolatileReg32,
+}
+
+impl FsmcRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> FsmcRegs {
+        let fsmc_base: *mut u32 = FSMC_BASE as *mut u32;
+
+        FsmcRegs {
+            bcr1  : VolatileReg32::new(fsmc_base),
+            btr1  : VolatileReg32::new_offset(fsmc_base, 1),
+            bcr2  : VolatileReg32::new_offset(fsmc_base, 2),
+            btr2  : VolatileReg32::new_offset(fsmc_base, 3),
+            bcr3  : VolatileReg32::new_offset(fsmc_base, 4),
+            btr3  : VolatileReg32::new_offset(fsmc_base, 5),
+            bcr4  : VolatileReg32::new_offset(fsmc_base, 6),
+            btr4  : VolatileReg32::new_offset(fsmc_base, 7),
+            pcr2  : VolatileReg32::new_offset(fsmc_base, 24),
+            sr2   : VolatileReg32::new_offset(fsmc_base, 25),
+            pmem2 : VolatileReg32::new_offset(fsmc_base, 26),
+            patt2 : VolatileReg32::new_offset(fsmc_base, 27),
+            eccr2 : ReadOnlyReg32::new_offset(fsmc_base, 29),
+            pcr3  : VolatileReg32::new_offset(fsmc_base, 32),
+            sr3   : VolatileReg32::new_offset(fsmc_base, 33),
+            pmem3 : VolatileReg32::new_offset(fsmc_base, 34),
+            patt3 : VolatileReg32::new_offset(fsmc_base, 35),
+            eccr3 : ReadOnlyReg32::new_offset(fsmc_base, 37),
+            pcr4  : VolatileReg32::new_offset(fsmc_base, 40),
+            sr4   : VolatileReg32::new_offset(fsmc_base, 41),
+            pmem4 : VolatileReg32::new_offset(fsmc_base, 42),
+            patt4 : VolatileReg32::new_offset(fsmc_base, 43),
+            pio4  : VolatileReg32::new_offset(fsmc_base, 44),
+            bwtr1 : VolatileReg32::new_offset(fsmc_base, 65),
+            bwtr2 : VolatileReg32::new_offset(fsmc_base, 67),
+            bwtr3 : VolatileReg32::new_offset(fsmc_base, 69),
+            bwtr4 : VolatileReg32::new_offset(fsmc_base, 71),
+        }
+    }
+}
+
+// Bit definition for FSMC_BCR1 register
+pub const FSMC_BCR1_MBKEN     : u32   = 0x00000001;   // Memory bank enable bit
+pub const FSMC_BCR1_MUXEN     : u32   = 0x00000002;   // Address/data multiplexing enable bit
+pub const FSMC_BCR1_MTYP      : u32   = 0x0000000C;   // Memory type
+pub const FSMC_BCR1_MTYP_0    : u32   = 0x00000004;   // Bit 0
+pub const FSMC_BCR1_MTYP_1    : u32   = 0x00000008;   // Bit 1
+pub const FSMC_BCR1_MWID      : u32   = 0x00000030;   // Memory data bus width
+pub const FSMC_BCR1_MWID_0    : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BCR1_MWID_1    : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BCR1_FACCEN    : u32   = 0x00000040;   // Flash access enable
+pub const FSMC_BCR1_BURSTEN   : u32   = 0x00000100;   // Burst enable bit
+pub const FSMC_BCR1_WAITPOL   : u32   = 0x00000200;   // Wait signal polarity bit
+pub const FSMC_BCR1_WRAPMOD   : u32   = 0x00000400;   // Wrapped burst mode support
+pub const FSMC_BCR1_WAITCFG   : u32   = 0x00000800;   // Wait timing configuration
+pub const FSMC_BCR1_WREN      : u32   = 0x00001000;   // Write enable bit
+pub const FSMC_BCR1_WAITEN    : u32   = 0x00002000;   // Wait enable bit
+pub const FSMC_BCR1_EXTMOD    : u32   = 0x00004000;   // Extended mode enable
+pub const FSMC_BCR1_ASYNCWAIT : u32   = 0x00008000;   // Wait signal during asynchronous transfers
+pub const FSMC_BCR1_CBURSTRW  : u32   = 0x00080000;   // Write burst enable
+
+// Bit definition for FSMC_BTR1 register
+pub const FSMC_BTR1_ADDSET    : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BTR1_ADDSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BTR1_ADDSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BTR1_ADDSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BTR1_ADDSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BTR1_ADDHLD    : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BTR1_ADDHLD_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BTR1_ADDHLD_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BTR1_ADDHLD_2  : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BTR1_ADDHLD_3  : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BTR1_DATAST    : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BTR1_DATAST_0  : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BTR1_DATAST_1  : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BTR1_DATAST_2  : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BTR1_DATAST_3  : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BTR1_DATAST_4  : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BTR1_DATAST_5  : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BTR1_DATAST_6  : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BTR1_DATAST_7  : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BTR1_BUSTURN   : u32   = 0x000F0000;   // Bus turnaround phase duration
+pub const FSMC_BTR1_BUSTURN_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_BTR1_BUSTURN_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_BTR1_BUSTURN_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_BTR1_BUSTURN_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_BTR1_CLKDIV    : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BTR1_CLKDIV_0  : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BTR1_CLKDIV_1  : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BTR1_CLKDIV_2  : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BTR1_CLKDIV_3  : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BTR1_DATLAT    : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BTR1_DATLAT_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BTR1_DATLAT_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BTR1_DATLAT_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BTR1_DATLAT_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BTR1_ACCMOD    : u32   = 0x30000000;   // Access mode
+pub const FSMC_BTR1_ACCMOD_0  : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BTR1_ACCMOD_1  : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BCR2 register
+pub const FSMC_BCR2_MBKEN     : u32   = 0x00000001;   // Memory bank enable bit
+pub const FSMC_BCR2_MUXEN     : u32   = 0x00000002;   // Address/data multiplexing enable bit
+pub const FSMC_BCR2_MTYP      : u32   = 0x0000000C;   // Memory type
+pub const FSMC_BCR2_MTYP_0    : u32   = 0x00000004;   // Bit 0
+pub const FSMC_BCR2_MTYP_1    : u32   = 0x00000008;   // Bit 1
+pub const FSMC_BCR2_MWID      : u32   = 0x00000030;   // Memory data bus width
+pub const FSMC_BCR2_MWID_0    : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BCR2_MWID_1    : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BCR2_FACCEN    : u32   = 0x00000040;   // Flash access enable
+pub const FSMC_BCR2_BURSTEN   : u32   = 0x00000100;   // Burst enable bit
+pub const FSMC_BCR2_WAITPOL   : u32   = 0x00000200;   // Wait signal polarity bit
+pub const FSMC_BCR2_WRAPMOD   : u32   = 0x00000400;   // Wrapped burst mode support
+pub const FSMC_BCR2_WAITCFG   : u32   = 0x00000800;   // Wait timing configuration
+pub const FSMC_BCR2_WREN      : u32   = 0x00001000;   // Write enable bit
+pub const FSMC_BCR2_WAITEN    : u32   = 0x00002000;   // Wait enable bit
+pub const FSMC_BCR2_EXTMOD    : u32   = 0x00004000;   // Extended mode enable
+pub const FSMC_BCR2_ASYNCWAIT : u32   = 0x00008000;   // Wait signal during asynchronous transfers
+pub const FSMC_BCR2_CBURSTRW  : u32   = 0x00080000;   // Write burst enable
+
+// Bit definition for FSMC_BTR2 register
+pub const FSMC_BTR2_ADDSET    : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BTR2_ADDSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BTR2_ADDSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BTR2_ADDSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BTR2_ADDSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BTR2_ADDHLD    : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BTR2_ADDHLD_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BTR2_ADDHLD_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BTR2_ADDHLD_2  : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BTR2_ADDHLD_3  : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BTR2_DATAST    : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BTR2_DATAST_0  : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BTR2_DATAST_1  : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BTR2_DATAST_2  : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BTR2_DATAST_3  : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BTR2_DATAST_4  : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BTR2_DATAST_5  : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BTR2_DATAST_6  : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BTR2_DATAST_7  : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BTR2_BUSTURN   : u32   = 0x000F0000;   // Bus turnaround phase duration
+pub const FSMC_BTR2_BUSTURN_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_BTR2_BUSTURN_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_BTR2_BUSTURN_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_BTR2_BUSTURN_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_BTR2_CLKDIV    : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BTR2_CLKDIV_0  : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BTR2_CLKDIV_1  : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BTR2_CLKDIV_2  : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BTR2_CLKDIV_3  : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BTR2_DATLAT    : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BTR2_DATLAT_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BTR2_DATLAT_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BTR2_DATLAT_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BTR2_DATLAT_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BTR2_ACCMOD    : u32   = 0x30000000;   // Access mode
+pub const FSMC_BTR2_ACCMOD_0  : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BTR2_ACCMOD_1  : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BCR3 register
+pub const FSMC_BCR3_MBKEN     : u32   = 0x00000001;   // Memory bank enable bit
+pub const FSMC_BCR3_MUXEN     : u32   = 0x00000002;   // Address/data multiplexing enable bit
+pub const FSMC_BCR3_MTYP      : u32   = 0x0000000C;   // Memory type
+pub const FSMC_BCR3_MTYP_0    : u32   = 0x00000004;   // Bit 0
+pub const FSMC_BCR3_MTYP_1    : u32   = 0x00000008;   // Bit 1
+pub const FSMC_BCR3_MWID      : u32   = 0x00000030;   // Memory data bus width
+pub const FSMC_BCR3_MWID_0    : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BCR3_MWID_1    : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BCR3_FACCEN    : u32   = 0x00000040;   // Flash access enable
+pub const FSMC_BCR3_BURSTEN   : u32   = 0x00000100;   // Burst enable bit
+pub const FSMC_BCR3_WAITPOL   : u32   = 0x00000200;   // Wait signal polarity bit
+pub const FSMC_BCR3_WRAPMOD   : u32   = 0x00000400;   // Wrapped burst mode support
+pub const FSMC_BCR3_WAITCFG   : u32   = 0x00000800;   // Wait timing configuration
+pub const FSMC_BCR3_WREN      : u32   = 0x00001000;   // Write enable bit
+pub const FSMC_BCR3_WAITEN    : u32   = 0x00002000;   // Wait enable bit
+pub const FSMC_BCR3_EXTMOD    : u32   = 0x00004000;   // Extended mode enable
+pub const FSMC_BCR3_ASYNCWAIT : u32   = 0x00008000;   // Wait signal during asynchronous transfers
+pub const FSMC_BCR3_CBURSTRW  : u32   = 0x00080000;   // Write burst enable
+
+// Bit definition for FSMC_BTR3 register
+pub const FSMC_BTR3_ADDSET    : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BTR3_ADDSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BTR3_ADDSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BTR3_ADDSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BTR3_ADDSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BTR3_ADDHLD    : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BTR3_ADDHLD_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BTR3_ADDHLD_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BTR3_ADDHLD_2  : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BTR3_ADDHLD_3  : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BTR3_DATAST    : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BTR3_DATAST_0  : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BTR3_DATAST_1  : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BTR3_DATAST_2  : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BTR3_DATAST_3  : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BTR3_DATAST_4  : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BTR3_DATAST_5  : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BTR3_DATAST_6  : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BTR3_DATAST_7  : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BTR3_BUSTURN   : u32   = 0x000F0000;   // Bus turnaround phase duration
+pub const FSMC_BTR3_BUSTURN_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_BTR3_BUSTURN_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_BTR3_BUSTURN_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_BTR3_BUSTURN_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_BTR3_CLKDIV    : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BTR3_CLKDIV_0  : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BTR3_CLKDIV_1  : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BTR3_CLKDIV_2  : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BTR3_CLKDIV_3  : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BTR3_DATLAT    : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BTR3_DATLAT_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BTR3_DATLAT_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BTR3_DATLAT_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BTR3_DATLAT_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BTR3_ACCMOD    : u32   = 0x30000000;   // Access mode
+pub const FSMC_BTR3_ACCMOD_0  : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BTR3_ACCMOD_1  : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BCR4 register
+pub const FSMC_BCR4_MBKEN     : u32   = 0x00000001;   // Memory bank enable bit
+pub const FSMC_BCR4_MUXEN     : u32   = 0x00000002;   // Address/data multiplexing enable bit
+pub const FSMC_BCR4_MTYP      : u32   = 0x0000000C;   // Memory type
+pub const FSMC_BCR4_MTYP_0    : u32   = 0x00000004;   // Bit 0
+pub const FSMC_BCR4_MTYP_1    : u32   = 0x00000008;   // Bit 1
+pub const FSMC_BCR4_MWID      : u32   = 0x00000030;   // Memory data bus width
+pub const FSMC_BCR4_MWID_0    : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BCR4_MWID_1    : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BCR4_FACCEN    : u32   = 0x00000040;   // Flash access enable
+pub const FSMC_BCR4_BURSTEN   : u32   = 0x00000100;   // Burst enable bit
+pub const FSMC_BCR4_WAITPOL   : u32   = 0x00000200;   // Wait signal polarity bit
+pub const FSMC_BCR4_WRAPMOD   : u32   = 0x00000400;   // Wrapped burst mode support
+pub const FSMC_BCR4_WAITCFG   : u32   = 0x00000800;   // Wait timing configuration
+pub const FSMC_BCR4_WREN      : u32   = 0x00001000;   // Write enable bit
+pub const FSMC_BCR4_WAITEN    : u32   = 0x00002000;   // Wait enable bit
+pub const FSMC_BCR4_EXTMOD    : u32   = 0x00004000;   // Extended mode enable
+pub const FSMC_BCR4_ASYNCWAIT : u32   = 0x00008000;   // Wait signal during asynchronous transfers
+pub const FSMC_BCR4_CBURSTRW  : u32   = 0x00080000;   // Write burst enable
+
+// Bit definition for FSMC_BTR4 register
+pub const FSMC_BTR4_ADDSET    : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BTR4_ADDSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BTR4_ADDSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BTR4_ADDSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BTR4_ADDSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BTR4_ADDHLD    : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BTR4_ADDHLD_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BTR4_ADDHLD_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BTR4_ADDHLD_2  : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BTR4_ADDHLD_3  : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BTR4_DATAST    : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BTR4_DATAST_0  : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BTR4_DATAST_1  : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BTR4_DATAST_2  : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BTR4_DATAST_3  : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BTR4_DATAST_4  : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BTR4_DATAST_5  : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BTR4_DATAST_6  : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BTR4_DATAST_7  : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BTR4_BUSTURN   : u32   = 0x000F0000;   // Bus turnaround phase duration
+pub const FSMC_BTR4_BUSTURN_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_BTR4_BUSTURN_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_BTR4_BUSTURN_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_BTR4_BUSTURN_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_BTR4_CLKDIV    : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BTR4_CLKDIV_0  : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BTR4_CLKDIV_1  : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BTR4_CLKDIV_2  : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BTR4_CLKDIV_3  : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BTR4_DATLAT    : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BTR4_DATLAT_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BTR4_DATLAT_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BTR4_DATLAT_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BTR4_DATLAT_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BTR4_ACCMOD    : u32   = 0x30000000;   // Access mode
+pub const FSMC_BTR4_ACCMOD_0  : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BTR4_ACCMOD_1  : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_PCR2 register
+pub const FSMC_PCR2_PWAITEN : u32   = 0x00000002;   // Wait feature enable bit
+pub const FSMC_PCR2_PBKEN   : u32   = 0x00000004;   // PC Card/NAND Flash memory bank enable bit
+pub const FSMC_PCR2_PTYP    : u32   = 0x00000008;   // Memory type
+pub const FSMC_PCR2_PWID    : u32   = 0x00000030;   // Data bus width
+pub const FSMC_PCR2_PWID_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_PCR2_PWID_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_PCR2_ECCEN   : u32   = 0x00000040;   // ECC computation logic enable bit
+pub const FSMC_PCR2_TCLR    : u32   = 0x00001E00;   // CLE to RE delay
+pub const FSMC_PCR2_TCLR_0  : u32   = 0x00000200;   // Bit 0
+pub const FSMC_PCR2_TCLR_1  : u32   = 0x00000400;   // Bit 1
+pub const FSMC_PCR2_TCLR_2  : u32   = 0x00000800;   // Bit 2
+pub const FSMC_PCR2_TCLR_3  : u32   = 0x00001000;   // Bit 3
+pub const FSMC_PCR2_TAR     : u32   = 0x0001E000;   // ALE to RE delay
+pub const FSMC_PCR2_TAR_0   : u32   = 0x00002000;   // Bit 0
+pub const FSMC_PCR2_TAR_1   : u32   = 0x00004000;   // Bit 1
+pub const FSMC_PCR2_TAR_2   : u32   = 0x00008000;   // Bit 2
+pub const FSMC_PCR2_TAR_3   : u32   = 0x00010000;   // Bit 3
+pub const FSMC_PCR2_ECCPS   : u32   = 0x000E0000;   // ECC page size
+pub const FSMC_PCR2_ECCPS_0 : u32   = 0x00020000;   // Bit 0
+pub const FSMC_PCR2_ECCPS_1 : u32   = 0x00040000;   // Bit 1
+pub const FSMC_PCR2_ECCPS_2 : u32   = 0x00080000;   // Bit 2
+
+// Bit definition for FSMC_SR2 register
+pub const FSMC_SR2_IRS   : u32   = 0x00000001;   // Interrupt rising edge status
+pub const FSMC_SR2_ILS   : u32   = 0x00000002;   // Interrupt high-level status
+pub const FSMC_SR2_IFS   : u32   = 0x00000004;   // Interrupt falling edge status
+pub const FSMC_SR2_IREN  : u32   = 0x00000008;   // Interrupt rising edge detection enable bit
+pub const FSMC_SR2_ILEN  : u32   = 0x00000010;   // Interrupt high-level detection enable bit
+pub const FSMC_SR2_IFEN  : u32   = 0x00000020;   // Interrupt falling edge detection enable bit
+pub const FSMC_SR2_FEMPT : u32   = 0x00000040;   // FIFO empty
+
+// Bit definition for FSMC_PMEM2 register
+pub const FSMC_PMEM2_MEMSET    : u32   = 0x000000FF;   // Common memory setup time
+pub const FSMC_PMEM2_MEMSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PMEM2_MEMSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PMEM2_MEMSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PMEM2_MEMSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PMEM2_MEMSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PMEM2_MEMSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PMEM2_MEMSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PMEM2_MEMSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PMEM2_MEMWAIT   : u32   = 0x0000FF00;   // Common memory wait time
+pub const FSMC_PMEM2_MEMWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PMEM2_MEMWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PMEM2_MEMWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PMEM2_MEMWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PMEM2_MEMWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PMEM2_MEMWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PMEM2_MEMWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PMEM2_MEMWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PMEM2_MEMHOLD   : u32   = 0x00FF0000;   // Common memory hold time
+pub const FSMC_PMEM2_MEMHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PMEM2_MEMHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PMEM2_MEMHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PMEM2_MEMHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PMEM2_MEMHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PMEM2_MEMHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PMEM2_MEMHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PMEM2_MEMHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PMEM2_MEMHIZ    : u32   = 0xFF000000;   // Common memory databus HiZ time
+pub const FSMC_PMEM2_MEMHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PMEM2_MEMHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PMEM2_MEMHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PMEM2_MEMHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PMEM2_MEMHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PMEM2_MEMHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PMEM2_MEMHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PMEM2_MEMHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_PATT2 register
+pub const FSMC_PATT2_ATTSET    : u32   = 0x000000FF;   // Attribute memory setup time
+pub const FSMC_PATT2_ATTSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PATT2_ATTSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PATT2_ATTSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PATT2_ATTSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PATT2_ATTSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PATT2_ATTSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PATT2_ATTSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PATT2_ATTSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PATT2_ATTWAIT   : u32   = 0x0000FF00;   // Attribute memory wait time
+pub const FSMC_PATT2_ATTWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PATT2_ATTWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PATT2_ATTWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PATT2_ATTWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PATT2_ATTWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PATT2_ATTWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PATT2_ATTWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PATT2_ATTWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PATT2_ATTHOLD   : u32   = 0x00FF0000;   // Attribute memory hold time
+pub const FSMC_PATT2_ATTHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PATT2_ATTHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PATT2_ATTHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PATT2_ATTHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PATT2_ATTHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PATT2_ATTHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PATT2_ATTHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PATT2_ATTHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PATT2_ATTHIZ    : u32   = 0xFF000000;   // Attribute memory databus HiZ time
+pub const FSMC_PATT2_ATTHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PATT2_ATTHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PATT2_ATTHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PATT2_ATTHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PATT2_ATTHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PATT2_ATTHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PATT2_ATTHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PATT2_ATTHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_ECCR2 register
+pub const FSMC_ECCR2_ECC    : u32   = 0xFFFFFFFF;   // ECC result
+pub const FSMC_ECCR2_ECC_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_ECCR2_ECC_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_ECCR2_ECC_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_ECCR2_ECC_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_ECCR2_ECC_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_ECCR2_ECC_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_ECCR2_ECC_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_ECCR2_ECC_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_ECCR2_ECC_8  : u32   = 0x00000100;   // Bit 8
+pub const FSMC_ECCR2_ECC_9  : u32   = 0x00000200;   // Bit 9
+pub const FSMC_ECCR2_ECC_10 : u32   = 0x00000400;   // Bit 10
+pub const FSMC_ECCR2_ECC_11 : u32   = 0x00000800;   // Bit 11
+pub const FSMC_ECCR2_ECC_12 : u32   = 0x00001000;   // Bit 12
+pub const FSMC_ECCR2_ECC_13 : u32   = 0x00002000;   // Bit 13
+pub const FSMC_ECCR2_ECC_14 : u32   = 0x00004000;   // Bit 14
+pub const FSMC_ECCR2_ECC_15 : u32   = 0x00008000;   // Bit 15
+pub const FSMC_ECCR2_ECC_16 : u32   = 0x00010000;   // Bit 16
+pub const FSMC_ECCR2_ECC_17 : u32   = 0x00020000;   // Bit 17
+pub const FSMC_ECCR2_ECC_18 : u32   = 0x00040000;   // Bit 18
+pub const FSMC_ECCR2_ECC_19 : u32   = 0x00080000;   // Bit 19
+pub const FSMC_ECCR2_ECC_20 : u32   = 0x00100000;   // Bit 20
+pub const FSMC_ECCR2_ECC_21 : u32   = 0x00200000;   // Bit 21
+pub const FSMC_ECCR2_ECC_22 : u32   = 0x00400000;   // Bit 22
+pub const FSMC_ECCR2_ECC_23 : u32   = 0x00800000;   // Bit 23
+pub const FSMC_ECCR2_ECC_24 : u32   = 0x01000000;   // Bit 24
+pub const FSMC_ECCR2_ECC_25 : u32   = 0x02000000;   // Bit 25
+pub const FSMC_ECCR2_ECC_26 : u32   = 0x04000000;   // Bit 26
+pub const FSMC_ECCR2_ECC_27 : u32   = 0x08000000;   // Bit 27
+pub const FSMC_ECCR2_ECC_28 : u32   = 0x10000000;   // Bit 28
+pub const FSMC_ECCR2_ECC_29 : u32   = 0x20000000;   // Bit 29
+pub const FSMC_ECCR2_ECC_30 : u32   = 0x40000000;   // Bit 30
+pub const FSMC_ECCR2_ECC_31 : u32   = 0x80000000;   // Bit 31
+
+// Bit definition for FSMC_PCR3 register
+pub const FSMC_PCR3_PWAITEN : u32   = 0x00000002;   // Wait feature enable bit
+pub const FSMC_PCR3_PBKEN   : u32   = 0x00000004;   // PC Card/NAND Flash memory bank enable bit
+pub const FSMC_PCR3_PTYP    : u32   = 0x00000008;   // Memory type
+pub const FSMC_PCR3_PWID    : u32   = 0x00000030;   // Data bus width
+pub const FSMC_PCR3_PWID_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_PCR3_PWID_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_PCR3_ECCEN   : u32   = 0x00000040;   // ECC computation logic enable bit
+pub const FSMC_PCR3_TCLR    : u32   = 0x00001E00;   // CLE to RE delay
+pub const FSMC_PCR3_TCLR_0  : u32   = 0x00000200;   // Bit 0
+pub const FSMC_PCR3_TCLR_1  : u32   = 0x00000400;   // Bit 1
+pub const FSMC_PCR3_TCLR_2  : u32   = 0x00000800;   // Bit 2
+pub const FSMC_PCR3_TCLR_3  : u32   = 0x00001000;   // Bit 3
+pub const FSMC_PCR3_TAR     : u32   = 0x0001E000;   // ALE to RE delay
+pub const FSMC_PCR3_TAR_0   : u32   = 0x00002000;   // Bit 0
+pub const FSMC_PCR3_TAR_1   : u32   = 0x00004000;   // Bit 1
+pub const FSMC_PCR3_TAR_2   : u32   = 0x00008000;   // Bit 2
+pub const FSMC_PCR3_TAR_3   : u32   = 0x00010000;   // Bit 3
+pub const FSMC_PCR3_ECCPS   : u32   = 0x000E0000;   // ECC page size
+pub const FSMC_PCR3_ECCPS_0 : u32   = 0x00020000;   // Bit 0
+pub const FSMC_PCR3_ECCPS_1 : u32   = 0x00040000;   // Bit 1
+pub const FSMC_PCR3_ECCPS_2 : u32   = 0x00080000;   // Bit 2
+
+// Bit definition for FSMC_SR3 register
+pub const FSMC_SR3_IRS   : u32   = 0x00000001;   // Interrupt rising edge status
+pub const FSMC_SR3_ILS   : u32   = 0x00000002;   // Interrupt high-level status
+pub const FSMC_SR3_IFS   : u32   = 0x00000004;   // Interrupt falling edge status
+pub const FSMC_SR3_IREN  : u32   = 0x00000008;   // Interrupt rising edge detection enable bit
+pub const FSMC_SR3_ILEN  : u32   = 0x00000010;   // Interrupt high-level detection enable bit
+pub const FSMC_SR3_IFEN  : u32   = 0x00000020;   // Interrupt falling edge detection enable bit
+pub const FSMC_SR3_FEMPT : u32   = 0x00000040;   // FIFO empty
+
+// Bit definition for FSMC_PMEM3 register
+pub const FSMC_PMEM3_MEMSET    : u32   = 0x000000FF;   // Common memory setup time
+pub const FSMC_PMEM3_MEMSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PMEM3_MEMSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PMEM3_MEMSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PMEM3_MEMSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PMEM3_MEMSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PMEM3_MEMSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PMEM3_MEMSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PMEM3_MEMSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PMEM3_MEMWAIT   : u32   = 0x0000FF00;   // Common memory wait time
+pub const FSMC_PMEM3_MEMWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PMEM3_MEMWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PMEM3_MEMWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PMEM3_MEMWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PMEM3_MEMWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PMEM3_MEMWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PMEM3_MEMWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PMEM3_MEMWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PMEM3_MEMHOLD   : u32   = 0x00FF0000;   // Common memory hold time
+pub const FSMC_PMEM3_MEMHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PMEM3_MEMHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PMEM3_MEMHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PMEM3_MEMHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PMEM3_MEMHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PMEM3_MEMHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PMEM3_MEMHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PMEM3_MEMHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PMEM3_MEMHIZ    : u32   = 0xFF000000;   // Common memory databus HiZ time
+pub const FSMC_PMEM3_MEMHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PMEM3_MEMHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PMEM3_MEMHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PMEM3_MEMHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PMEM3_MEMHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PMEM3_MEMHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PMEM3_MEMHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PMEM3_MEMHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_PATT3 register
+pub const FSMC_PATT3_ATTSET    : u32   = 0x000000FF;   // Attribute memory setup time
+pub const FSMC_PATT3_ATTSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PATT3_ATTSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PATT3_ATTSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PATT3_ATTSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PATT3_ATTSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PATT3_ATTSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PATT3_ATTSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PATT3_ATTSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PATT3_ATTWAIT   : u32   = 0x0000FF00;   // Attribute memory wait time
+pub const FSMC_PATT3_ATTWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PATT3_ATTWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PATT3_ATTWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PATT3_ATTWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PATT3_ATTWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PATT3_ATTWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PATT3_ATTWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PATT3_ATTWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PATT3_ATTHOLD   : u32   = 0x00FF0000;   // Attribute memory hold time
+pub const FSMC_PATT3_ATTHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PATT3_ATTHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PATT3_ATTHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PATT3_ATTHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PATT3_ATTHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PATT3_ATTHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PATT3_ATTHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PATT3_ATTHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PATT3_ATTHIZ    : u32   = 0xFF000000;   // Attribute memory databus HiZ time
+pub const FSMC_PATT3_ATTHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PATT3_ATTHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PATT3_ATTHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PATT3_ATTHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PATT3_ATTHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PATT3_ATTHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PATT3_ATTHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PATT3_ATTHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_ECCR3 register
+pub const FSMC_ECCR3_ECC    : u32   = 0xFFFFFFFF;   // ECC result
+pub const FSMC_ECCR3_ECC_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_ECCR3_ECC_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_ECCR3_ECC_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_ECCR3_ECC_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_ECCR3_ECC_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_ECCR3_ECC_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_ECCR3_ECC_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_ECCR3_ECC_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_ECCR3_ECC_8  : u32   = 0x00000100;   // Bit 8
+pub const FSMC_ECCR3_ECC_9  : u32   = 0x00000200;   // Bit 9
+pub const FSMC_ECCR3_ECC_10 : u32   = 0x00000400;   // Bit 10
+pub const FSMC_ECCR3_ECC_11 : u32   = 0x00000800;   // Bit 11
+pub const FSMC_ECCR3_ECC_12 : u32   = 0x00001000;   // Bit 12
+pub const FSMC_ECCR3_ECC_13 : u32   = 0x00002000;   // Bit 13
+pub const FSMC_ECCR3_ECC_14 : u32   = 0x00004000;   // Bit 14
+pub const FSMC_ECCR3_ECC_15 : u32   = 0x00008000;   // Bit 15
+pub const FSMC_ECCR3_ECC_16 : u32   = 0x00010000;   // Bit 16
+pub const FSMC_ECCR3_ECC_17 : u32   = 0x00020000;   // Bit 17
+pub const FSMC_ECCR3_ECC_18 : u32   = 0x00040000;   // Bit 18
+pub const FSMC_ECCR3_ECC_19 : u32   = 0x00080000;   // Bit 19
+pub const FSMC_ECCR3_ECC_20 : u32   = 0x00100000;   // Bit 20
+pub const FSMC_ECCR3_ECC_21 : u32   = 0x00200000;   // Bit 21
+pub const FSMC_ECCR3_ECC_22 : u32   = 0x00400000;   // Bit 22
+pub const FSMC_ECCR3_ECC_23 : u32   = 0x00800000;   // Bit 23
+pub const FSMC_ECCR3_ECC_24 : u32   = 0x01000000;   // Bit 24
+pub const FSMC_ECCR3_ECC_25 : u32   = 0x02000000;   // Bit 25
+pub const FSMC_ECCR3_ECC_26 : u32   = 0x04000000;   // Bit 26
+pub const FSMC_ECCR3_ECC_27 : u32   = 0x08000000;   // Bit 27
+pub const FSMC_ECCR3_ECC_28 : u32   = 0x10000000;   // Bit 28
+pub const FSMC_ECCR3_ECC_29 : u32   = 0x20000000;   // Bit 29
+pub const FSMC_ECCR3_ECC_30 : u32   = 0x40000000;   // Bit 30
+pub const FSMC_ECCR3_ECC_31 : u32   = 0x80000000;   // Bit 31
+
+// Bit definition for FSMC_PCR4 register
+pub const FSMC_PCR4_PWAITEN : u32   = 0x00000002;   // Wait feature enable bit
+pub const FSMC_PCR4_PBKEN   : u32   = 0x00000004;   // PC Card/NAND Flash memory bank enable bit
+pub const FSMC_PCR4_PTYP    : u32   = 0x00000008;   // Memory type
+pub const FSMC_PCR4_PWID    : u32   = 0x00000030;   // Data bus width
+pub const FSMC_PCR4_PWID_0  : u32   = 0x00000010;   // Bit 0
+pub const FSMC_PCR4_PWID_1  : u32   = 0x00000020;   // Bit 1
+pub const FSMC_PCR4_ECCEN   : u32   = 0x00000040;   // ECC computation logic enable bit
+pub const FSMC_PCR4_TCLR    : u32   = 0x00001E00;   // CLE to RE delay
+pub const FSMC_PCR4_TCLR_0  : u32   = 0x00000200;   // Bit 0
+pub const FSMC_PCR4_TCLR_1  : u32   = 0x00000400;   // Bit 1
+pub const FSMC_PCR4_TCLR_2  : u32   = 0x00000800;   // Bit 2
+pub const FSMC_PCR4_TCLR_3  : u32   = 0x00001000;   // Bit 3
+pub const FSMC_PCR4_TAR     : u32   = 0x0001E000;   // ALE to RE delay
+pub const FSMC_PCR4_TAR_0   : u32   = 0x00002000;   // Bit 0
+pub const FSMC_PCR4_TAR_1   : u32   = 0x00004000;   // Bit 1
+pub const FSMC_PCR4_TAR_2   : u32   = 0x00008000;   // Bit 2
+pub const FSMC_PCR4_TAR_3   : u32   = 0x00010000;   // Bit 3
+pub const FSMC_PCR4_ECCPS   : u32   = 0x000E0000;   // ECC page size
+pub const FSMC_PCR4_ECCPS_0 : u32   = 0x00020000;   // Bit 0
+pub const FSMC_PCR4_ECCPS_1 : u32   = 0x00040000;   // Bit 1
+pub const FSMC_PCR4_ECCPS_2 : u32   = 0x00080000;   // Bit 2
+
+// Bit definition for FSMC_SR4 register
+pub const FSMC_SR4_IRS   : u32   = 0x00000001;   // Interrupt rising edge status
+pub const FSMC_SR4_ILS   : u32   = 0x00000002;   // Interrupt high-level status
+pub const FSMC_SR4_IFS   : u32   = 0x00000004;   // Interrupt falling edge status
+pub const FSMC_SR4_IREN  : u32   = 0x00000008;   // Interrupt rising edge detection enable bit
+pub const FSMC_SR4_ILEN  : u32   = 0x00000010;   // Interrupt high-level detection enable bit
+pub const FSMC_SR4_IFEN  : u32   = 0x00000020;   // Interrupt falling edge detection enable bit
+pub const FSMC_SR4_FEMPT : u32   = 0x00000040;   // FIFO empty
+
+// Bit definition for FSMC_PMEM4 register
+pub const FSMC_PMEM4_MEMSET    : u32   = 0x000000FF;   // Common memory setup time
+pub const FSMC_PMEM4_MEMSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PMEM4_MEMSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PMEM4_MEMSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PMEM4_MEMSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PMEM4_MEMSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PMEM4_MEMSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PMEM4_MEMSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PMEM4_MEMSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PMEM4_MEMWAIT   : u32   = 0x0000FF00;   // Common memory wait time
+pub const FSMC_PMEM4_MEMWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PMEM4_MEMWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PMEM4_MEMWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PMEM4_MEMWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PMEM4_MEMWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PMEM4_MEMWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PMEM4_MEMWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PMEM4_MEMWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PMEM4_MEMHOLD   : u32   = 0x00FF0000;   // Common memory hold time
+pub const FSMC_PMEM4_MEMHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PMEM4_MEMHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PMEM4_MEMHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PMEM4_MEMHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PMEM4_MEMHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PMEM4_MEMHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PMEM4_MEMHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PMEM4_MEMHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PMEM4_MEMHIZ    : u32   = 0xFF000000;   // Common memory databus HiZ time
+pub const FSMC_PMEM4_MEMHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PMEM4_MEMHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PMEM4_MEMHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PMEM4_MEMHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PMEM4_MEMHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PMEM4_MEMHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PMEM4_MEMHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PMEM4_MEMHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_PATT4 register
+pub const FSMC_PATT4_ATTSET    : u32   = 0x000000FF;   // Attribute memory setup time
+pub const FSMC_PATT4_ATTSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PATT4_ATTSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PATT4_ATTSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PATT4_ATTSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PATT4_ATTSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PATT4_ATTSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PATT4_ATTSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PATT4_ATTSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PATT4_ATTWAIT   : u32   = 0x0000FF00;   // Attribute memory wait time
+pub const FSMC_PATT4_ATTWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PATT4_ATTWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PATT4_ATTWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PATT4_ATTWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PATT4_ATTWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PATT4_ATTWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PATT4_ATTWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PATT4_ATTWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PATT4_ATTHOLD   : u32   = 0x00FF0000;   // Attribute memory hold time
+pub const FSMC_PATT4_ATTHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PATT4_ATTHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PATT4_ATTHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PATT4_ATTHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PATT4_ATTHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PATT4_ATTHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PATT4_ATTHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PATT4_ATTHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PATT4_ATTHIZ    : u32   = 0xFF000000;   // Attribute memory databus HiZ time
+pub const FSMC_PATT4_ATTHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PATT4_ATTHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PATT4_ATTHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PATT4_ATTHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PATT4_ATTHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PATT4_ATTHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PATT4_ATTHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PATT4_ATTHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_PIO4 register
+pub const FSMC_PIO4_IOSET    : u32   = 0x000000FF;   // I/O setup time
+pub const FSMC_PIO4_IOSET_0  : u32   = 0x00000001;   // Bit 0
+pub const FSMC_PIO4_IOSET_1  : u32   = 0x00000002;   // Bit 1
+pub const FSMC_PIO4_IOSET_2  : u32   = 0x00000004;   // Bit 2
+pub const FSMC_PIO4_IOSET_3  : u32   = 0x00000008;   // Bit 3
+pub const FSMC_PIO4_IOSET_4  : u32   = 0x00000010;   // Bit 4
+pub const FSMC_PIO4_IOSET_5  : u32   = 0x00000020;   // Bit 5
+pub const FSMC_PIO4_IOSET_6  : u32   = 0x00000040;   // Bit 6
+pub const FSMC_PIO4_IOSET_7  : u32   = 0x00000080;   // Bit 7
+pub const FSMC_PIO4_IOWAIT   : u32   = 0x0000FF00;   // I/O wait time
+pub const FSMC_PIO4_IOWAIT_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_PIO4_IOWAIT_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_PIO4_IOWAIT_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_PIO4_IOWAIT_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_PIO4_IOWAIT_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_PIO4_IOWAIT_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_PIO4_IOWAIT_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_PIO4_IOWAIT_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_PIO4_IOHOLD   : u32   = 0x00FF0000;   // I/O hold time
+pub const FSMC_PIO4_IOHOLD_0 : u32   = 0x00010000;   // Bit 0
+pub const FSMC_PIO4_IOHOLD_1 : u32   = 0x00020000;   // Bit 1
+pub const FSMC_PIO4_IOHOLD_2 : u32   = 0x00040000;   // Bit 2
+pub const FSMC_PIO4_IOHOLD_3 : u32   = 0x00080000;   // Bit 3
+pub const FSMC_PIO4_IOHOLD_4 : u32   = 0x00100000;   // Bit 4
+pub const FSMC_PIO4_IOHOLD_5 : u32   = 0x00200000;   // Bit 5
+pub const FSMC_PIO4_IOHOLD_6 : u32   = 0x00400000;   // Bit 6
+pub const FSMC_PIO4_IOHOLD_7 : u32   = 0x00800000;   // Bit 7
+pub const FSMC_PIO4_IOHIZ    : u32   = 0xFF000000;   // I/O databus HiZ time
+pub const FSMC_PIO4_IOHIZ_0  : u32   = 0x01000000;   // Bit 0
+pub const FSMC_PIO4_IOHIZ_1  : u32   = 0x02000000;   // Bit 1
+pub const FSMC_PIO4_IOHIZ_2  : u32   = 0x04000000;   // Bit 2
+pub const FSMC_PIO4_IOHIZ_3  : u32   = 0x08000000;   // Bit 3
+pub const FSMC_PIO4_IOHIZ_4  : u32   = 0x10000000;   // Bit 4
+pub const FSMC_PIO4_IOHIZ_5  : u32   = 0x20000000;   // Bit 5
+pub const FSMC_PIO4_IOHIZ_6  : u32   = 0x40000000;   // Bit 6
+pub const FSMC_PIO4_IOHIZ_7  : u32   = 0x80000000;   // Bit 7
+
+// Bit definition for FSMC_BWTR1 register
+pub const FSMC_BWTR1_ADDSET   : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BWTR1_ADDSET_0 : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BWTR1_ADDSET_1 : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BWTR1_ADDSET_2 : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BWTR1_ADDSET_3 : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BWTR1_ADDHLD   : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BWTR1_ADDHLD_0 : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BWTR1_ADDHLD_1 : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BWTR1_ADDHLD_2 : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BWTR1_ADDHLD_3 : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BWTR1_DATAST   : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BWTR1_DATAST_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BWTR1_DATAST_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BWTR1_DATAST_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BWTR1_DATAST_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BWTR1_DATAST_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BWTR1_DATAST_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BWTR1_DATAST_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BWTR1_DATAST_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BWTR1_CLKDIV   : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BWTR1_CLKDIV_0 : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BWTR1_CLKDIV_1 : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BWTR1_CLKDIV_2 : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BWTR1_CLKDIV_3 : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BWTR1_DATLAT   : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BWTR1_DATLAT_0 : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BWTR1_DATLAT_1 : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BWTR1_DATLAT_2 : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BWTR1_DATLAT_3 : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BWTR1_ACCMOD   : u32   = 0x30000000;   // Access mode
+pub const FSMC_BWTR1_ACCMOD_0 : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BWTR1_ACCMOD_1 : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BWTR2 register
+pub const FSMC_BWTR2_ADDSET   : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BWTR2_ADDSET_0 : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BWTR2_ADDSET_1 : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BWTR2_ADDSET_2 : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BWTR2_ADDSET_3 : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BWTR2_ADDHLD   : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BWTR2_ADDHLD_0 : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BWTR2_ADDHLD_1 : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BWTR2_ADDHLD_2 : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BWTR2_ADDHLD_3 : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BWTR2_DATAST   : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BWTR2_DATAST_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BWTR2_DATAST_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BWTR2_DATAST_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BWTR2_DATAST_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BWTR2_DATAST_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BWTR2_DATAST_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BWTR2_DATAST_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BWTR2_DATAST_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BWTR2_CLKDIV   : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BWTR2_CLKDIV_0 : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BWTR2_CLKDIV_1 : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BWTR2_CLKDIV_2 : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BWTR2_CLKDIV_3 : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BWTR2_DATLAT   : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BWTR2_DATLAT_0 : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BWTR2_DATLAT_1 : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BWTR2_DATLAT_2 : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BWTR2_DATLAT_3 : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BWTR2_ACCMOD   : u32   = 0x30000000;   // Access mode
+pub const FSMC_BWTR2_ACCMOD_0 : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BWTR2_ACCMOD_1 : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BWTR3 register
+pub const FSMC_BWTR3_ADDSET   : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BWTR3_ADDSET_0 : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BWTR3_ADDSET_1 : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BWTR3_ADDSET_2 : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BWTR3_ADDSET_3 : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BWTR3_ADDHLD   : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BWTR3_ADDHLD_0 : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BWTR3_ADDHLD_1 : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BWTR3_ADDHLD_2 : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BWTR3_ADDHLD_3 : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BWTR3_DATAST   : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BWTR3_DATAST_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BWTR3_DATAST_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BWTR3_DATAST_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BWTR3_DATAST_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BWTR3_DATAST_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BWTR3_DATAST_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BWTR3_DATAST_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BWTR3_DATAST_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BWTR3_CLKDIV   : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BWTR3_CLKDIV_0 : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BWTR3_CLKDIV_1 : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BWTR3_CLKDIV_2 : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BWTR3_CLKDIV_3 : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BWTR3_DATLAT   : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BWTR3_DATLAT_0 : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BWTR3_DATLAT_1 : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BWTR3_DATLAT_2 : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BWTR3_DATLAT_3 : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BWTR3_ACCMOD   : u32   = 0x30000000;   // Access mode
+pub const FSMC_BWTR3_ACCMOD_0 : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BWTR3_ACCMOD_1 : u32   = 0x20000000;   // Bit 1
+
+// Bit definition for FSMC_BWTR4 register
+pub const FSMC_BWTR4_ADDSET   : u32   = 0x0000000F;   // Address setup phase duration
+pub const FSMC_BWTR4_ADDSET_0 : u32   = 0x00000001;   // Bit 0
+pub const FSMC_BWTR4_ADDSET_1 : u32   = 0x00000002;   // Bit 1
+pub const FSMC_BWTR4_ADDSET_2 : u32   = 0x00000004;   // Bit 2
+pub const FSMC_BWTR4_ADDSET_3 : u32   = 0x00000008;   // Bit 3
+pub const FSMC_BWTR4_ADDHLD   : u32   = 0x000000F0;   // Address-hold phase duration
+pub const FSMC_BWTR4_ADDHLD_0 : u32   = 0x00000010;   // Bit 0
+pub const FSMC_BWTR4_ADDHLD_1 : u32   = 0x00000020;   // Bit 1
+pub const FSMC_BWTR4_ADDHLD_2 : u32   = 0x00000040;   // Bit 2
+pub const FSMC_BWTR4_ADDHLD_3 : u32   = 0x00000080;   // Bit 3
+pub const FSMC_BWTR4_DATAST   : u32   = 0x0000FF00;   // Data-phase duration
+pub const FSMC_BWTR4_DATAST_0 : u32   = 0x00000100;   // Bit 0
+pub const FSMC_BWTR4_DATAST_1 : u32   = 0x00000200;   // Bit 1
+pub const FSMC_BWTR4_DATAST_2 : u32   = 0x00000400;   // Bit 2
+pub const FSMC_BWTR4_DATAST_3 : u32   = 0x00000800;   // Bit 3
+pub const FSMC_BWTR4_DATAST_4 : u32   = 0x00001000;   // Bit 4
+pub const FSMC_BWTR4_DATAST_5 : u32   = 0x00002000;   // Bit 5
+pub const FSMC_BWTR4_DATAST_6 : u32   = 0x00004000;   // Bit 6
+pub const FSMC_BWTR4_DATAST_7 : u32   = 0x00008000;   // Bit 7
+pub const FSMC_BWTR4_CLKDIV   : u32   = 0x00F00000;   // Clock divide ratio
+pub const FSMC_BWTR4_CLKDIV_0 : u32   = 0x00100000;   // Bit 0
+pub const FSMC_BWTR4_CLKDIV_1 : u32   = 0x00200000;   // Bit 1
+pub const FSMC_BWTR4_CLKDIV_2 : u32   = 0x00400000;   // Bit 2
+pub const FSMC_BWTR4_CLKDIV_3 : u32   = 0x00800000;   // Bit 3
+pub const FSMC_BWTR4_DATLAT   : u32   = 0x0F000000;   // Data latency
+pub const FSMC_BWTR4_DATLAT_0 : u32   = 0x01000000;   // Bit 0
+pub const FSMC_BWTR4_DATLAT_1 : u32   = 0x02000000;   // Bit 1
+pub const FSMC_BWTR4_DATLAT_2 : u32   = 0x04000000;   // Bit 2
+pub const FSMC_BWTR4_DATLAT_3 : u32   = 0x08000000;   // Bit 3
+pub const FSMC_BWTR4_ACCMOD   : u32   = 0x30000000;   // Access mode
+pub const FSMC_BWTR4_ACCMOD_0 : u32   = 0x10000000;   // Bit 0
+pub const FSMC_BWTR4_ACCMOD_1 : u32   = 0x20000000;   // Bit 1
+
+/// Typed fields of the FSMC_BCR1 register
+pub mod bcr1 {
+    field_flag! { /// Memory bank enable bit
+                  pub struct Mbken [0]; }
+    field_flag! { /// Address/data multiplexing enable bit
+                  pub struct Muxen [1]; }
+    field_bits! { /// Memory type
+                  pub struct Mtyp [2, 2]; }
+    field_bits! { /// Memory data bus width
+                  pub struct Mwid [4, 2]; }
+    field_flag! { /// Flash access enable
+                  pub struct Faccen [6]; }
+    field_flag! { /// Burst enable bit
+                  pub struct Bursten [8]; }
+    field_flag! { /// Wait signal polarity bit
+                  pub struct Waitpol [9]; }
+    field_flag! { /// Wrapped burst mode support
+                  pub struct Wrapmod [10]; }
+    field_flag! { /// Wait timing configuration
+                  pub struct Waitcfg [11]; }
+    field_flag! { /// Write enable bit
+                  pub struct Wren [12]; }
+    field_flag! { /// Wait enable bit
+                  pub struct Waiten [13]; }
+    field_flag! { /// Extended mode enable
+                  pub struct Extmod [14]; }
+    field_flag! { /// Wait signal during asynchronous transfers
+                  pub struct Asyncwait [15]; }
+    field_flag! { /// Write burst enable
+                  pub struct Cburstrw [19]; }
+}
+
+/// Typed fields of the FSMC_BTR1 register
+pub mod btr1 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Bus turnaround phase duration
+                  pub struct Busturn [16, 4]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BCR2 register
+pub mod bcr2 {
+    field_flag! { /// Memory bank enable bit
+                  pub struct Mbken [0]; }
+    field_flag! { /// Address/data multiplexing enable bit
+                  pub struct Muxen [1]; }
+    field_bits! { /// Memory type
+                  pub struct Mtyp [2, 2]; }
+    field_bits! { /// Memory data bus width
+                  pub struct Mwid [4, 2]; }
+    field_flag! { /// Flash access enable
+                  pub struct Faccen [6]; }
+    field_flag! { /// Burst enable bit
+                  pub struct Bursten [8]; }
+    field_flag! { /// Wait signal polarity bit
+                  pub struct Waitpol [9]; }
+    field_flag! { /// Wrapped burst mode support
+                  pub struct Wrapmod [10]; }
+    field_flag! { /// Wait timing configuration
+                  pub struct Waitcfg [11]; }
+    field_flag! { /// Write enable bit
+                  pub struct Wren [12]; }
+    field_flag! { /// Wait enable bit
+                  pub struct Waiten [13]; }
+    field_flag! { /// Extended mode enable
+                  pub struct Extmod [14]; }
+    field_flag! { /// Wait signal during asynchronous transfers
+                  pub struct Asyncwait [15]; }
+    field_flag! { /// Write burst enable
+                  pub struct Cburstrw [19]; }
+}
+
+/// Typed fields of the FSMC_BTR2 register
+pub mod btr2 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Bus turnaround phase duration
+                  pub struct Busturn [16, 4]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BCR3 register
+pub mod bcr3 {
+    field_flag! { /// Memory bank enable bit
+                  pub struct Mbken [0]; }
+    field_flag! { /// Address/data multiplexing enable bit
+                  pub struct Muxen [1]; }
+    field_bits! { /// Memory type
+                  pub struct Mtyp [2, 2]; }
+    field_bits! { /// Memory data bus width
+                  pub struct Mwid [4, 2]; }
+    field_flag! { /// Flash access enable
+                  pub struct Faccen [6]; }
+    field_flag! { /// Burst enable bit
+                  pub struct Bursten [8]; }
+    field_flag! { /// Wait signal polarity bit
+                  pub struct Waitpol [9]; }
+    field_flag! { /// Wrapped burst mode support
+                  pub struct Wrapmod [10]; }
+    field_flag! { /// Wait timing configuration
+                  pub struct Waitcfg [11]; }
+    field_flag! { /// Write enable bit
+                  pub struct Wren [12]; }
+    field_flag! { /// Wait enable bit
+                  pub struct Waiten [13]; }
+    field_flag! { /// Extended mode enable
+                  pub struct Extmod [14]; }
+    field_flag! { /// Wait signal during asynchronous transfers
+                  pub struct Asyncwait [15]; }
+    field_flag! { /// Write burst enable
+                  pub struct Cburstrw [19]; }
+}
+
+/// Typed fields of the FSMC_BTR3 register
+pub mod btr3 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Bus turnaround phase duration
+                  pub struct Busturn [16, 4]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BCR4 register
+pub mod bcr4 {
+    field_flag! { /// Memory bank enable bit
+                  pub struct Mbken [0]; }
+    field_flag! { /// Address/data multiplexing enable bit
+                  pub struct Muxen [1]; }
+    field_bits! { /// Memory type
+                  pub struct Mtyp [2, 2]; }
+    field_bits! { /// Memory data bus width
+                  pub struct Mwid [4, 2]; }
+    field_flag! { /// Flash access enable
+                  pub struct Faccen [6]; }
+    field_flag! { /// Burst enable bit
+                  pub struct Bursten [8]; }
+    field_flag! { /// Wait signal polarity bit
+                  pub struct Waitpol [9]; }
+    field_flag! { /// Wrapped burst mode support
+                  pub struct Wrapmod [10]; }
+    field_flag! { /// Wait timing configuration
+                  pub struct Waitcfg [11]; }
+    field_flag! { /// Write enable bit
+                  pub struct Wren [12]; }
+    field_flag! { /// Wait enable bit
+                  pub struct Waiten [13]; }
+    field_flag! { /// Extended mode enable
+                  pub struct Extmod [14]; }
+    field_flag! { /// Wait signal during asynchronous transfers
+                  pub struct Asyncwait [15]; }
+    field_flag! { /// Write burst enable
+                  pub struct Cburstrw [19]; }
+}
+
+/// Typed fields of the FSMC_BTR4 register
+pub mod btr4 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Bus turnaround phase duration
+                  pub struct Busturn [16, 4]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_PCR2 register
+pub mod pcr2 {
+    field_flag! { /// Wait feature enable bit
+                  pub struct Pwaiten [1]; }
+    field_flag! { /// PC Card/NAND Flash memory bank enable bit
+                  pub struct Pbken [2]; }
+    field_flag! { /// Memory type
+                  pub struct Ptyp [3]; }
+    field_bits! { /// Data bus width
+                  pub struct Pwid [4, 2]; }
+    field_flag! { /// ECC computation logic enable bit
+                  pub struct Eccen [6]; }
+    field_bits! { /// CLE to RE delay
+                  pub struct Tclr [9, 4]; }
+    field_bits! { /// ALE to RE delay
+                  pub struct Tar [13, 4]; }
+    field_bits! { /// ECC page size
+                  pub struct Eccps [17, 3]; }
+}
+
+/// Typed fields of the FSMC_SR2 register
+pub mod sr2 {
+    field_flag! { /// Interrupt rising edge status
+                  pub struct Irs [0]; }
+    field_flag! { /// Interrupt high-level status
+                  pub struct Ils [1]; }
+    field_flag! { /// Interrupt falling edge status
+                  pub struct Ifs [2]; }
+    field_flag! { /// Interrupt rising edge detection enable bit
+                  pub struct Iren [3]; }
+    field_flag! { /// Interrupt high-level detection enable bit
+                  pub struct Ilen [4]; }
+    field_flag! { /// Interrupt falling edge detection enable bit
+                  pub struct Ifen [5]; }
+    field_flag! { /// FIFO empty
+                  pub struct Fempt [6]; }
+}
+
+/// Typed fields of the FSMC_PMEM2 register
+pub mod pmem2 {
+    field_bits! { /// Common memory setup time
+                  pub struct Memset [0, 8]; }
+    field_bits! { /// Common memory wait time
+                  pub struct Memwait [8, 8]; }
+    field_bits! { /// Common memory hold time
+                  pub struct Memhold [16, 8]; }
+    field_bits! { /// Common memory databus HiZ time
+                  pub struct Memhiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_PATT2 register
+pub mod patt2 {
+    field_bits! { /// Attribute memory setup time
+                  pub struct Attset [0, 8]; }
+    field_bits! { /// Attribute memory wait time
+                  pub struct Attwait [8, 8]; }
+    field_bits! { /// Attribute memory hold time
+                  pub struct Atthold [16, 8]; }
+    field_bits! { /// Attribute memory databus HiZ time
+                  pub struct Atthiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_ECCR2 register
+pub mod eccr2 {
+    field_bits! { /// ECC result
+                  pub struct Ecc [0, 32]; }
+}
+
+/// Typed fields of the FSMC_PCR3 register
+pub mod pcr3 {
+    field_flag! { /// Wait feature enable bit
+                  pub struct Pwaiten [1]; }
+    field_flag! { /// PC Card/NAND Flash memory bank enable bit
+                  pub struct Pbken [2]; }
+    field_flag! { /// Memory type
+                  pub struct Ptyp [3]; }
+    field_bits! { /// Data bus width
+                  pub struct Pwid [4, 2]; }
+    field_flag! { /// ECC computation logic enable bit
+                  pub struct Eccen [6]; }
+    field_bits! { /// CLE to RE delay
+                  pub struct Tclr [9, 4]; }
+    field_bits! { /// ALE to RE delay
+                  pub struct Tar [13, 4]; }
+    field_bits! { /// ECC page size
+                  pub struct Eccps [17, 3]; }
+}
+
+/// Typed fields of the FSMC_SR3 register
+pub mod sr3 {
+    field_flag! { /// Interrupt rising edge status
+                  pub struct Irs [0]; }
+    field_flag! { /// Interrupt high-level status
+                  pub struct Ils [1]; }
+    field_flag! { /// Interrupt falling edge status
+                  pub struct Ifs [2]; }
+    field_flag! { /// Interrupt rising edge detection enable bit
+                  pub struct Iren [3]; }
+    field_flag! { /// Interrupt high-level detection enable bit
+                  pub struct Ilen [4]; }
+    field_flag! { /// Interrupt falling edge detection enable bit
+                  pub struct Ifen [5]; }
+    field_flag! { /// FIFO empty
+                  pub struct Fempt [6]; }
+}
+
+/// Typed fields of the FSMC_PMEM3 register
+pub mod pmem3 {
+    field_bits! { /// Common memory setup time
+                  pub struct Memset [0, 8]; }
+    field_bits! { /// Common memory wait time
+                  pub struct Memwait [8, 8]; }
+    field_bits! { /// Common memory hold time
+                  pub struct Memhold [16, 8]; }
+    field_bits! { /// Common memory databus HiZ time
+                  pub struct Memhiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_PATT3 register
+pub mod patt3 {
+    field_bits! { /// Attribute memory setup time
+                  pub struct Attset [0, 8]; }
+    field_bits! { /// Attribute memory wait time
+                  pub struct Attwait [8, 8]; }
+    field_bits! { /// Attribute memory hold time
+                  pub struct Atthold [16, 8]; }
+    field_bits! { /// Attribute memory databus HiZ time
+                  pub struct Atthiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_ECCR3 register
+pub mod eccr3 {
+    field_bits! { /// ECC result
+                  pub struct Ecc [0, 32]; }
+}
+
+/// Typed fields of the FSMC_PCR4 register
+pub mod pcr4 {
+    field_flag! { /// Wait feature enable bit
+                  pub struct Pwaiten [1]; }
+    field_flag! { /// PC Card/NAND Flash memory bank enable bit
+                  pub struct Pbken [2]; }
+    field_flag! { /// Memory type
+                  pub struct Ptyp [3]; }
+    field_bits! { /// Data bus width
+                  pub struct Pwid [4, 2]; }
+    field_flag! { /// ECC computation logic enable bit
+                  pub struct Eccen [6]; }
+    field_bits! { /// CLE to RE delay
+                  pub struct Tclr [9, 4]; }
+    field_bits! { /// ALE to RE delay
+                  pub struct Tar [13, 4]; }
+    field_bits! { /// ECC page size
+                  pub struct Eccps [17, 3]; }
+}
+
+/// Typed fields of the FSMC_SR4 register
+pub mod sr4 {
+    field_flag! { /// Interrupt rising edge status
+                  pub struct Irs [0]; }
+    field_flag! { /// Interrupt high-level status
+                  pub struct Ils [1]; }
+    field_flag! { /// Interrupt falling edge status
+                  pub struct Ifs [2]; }
+    field_flag! { /// Interrupt rising edge detection enable bit
+                  pub struct Iren [3]; }
+    field_flag! { /// Interrupt high-level detection enable bit
+                  pub struct Ilen [4]; }
+    field_flag! { /// Interrupt falling edge detection enable bit
+                  pub struct Ifen [5]; }
+    field_flag! { /// FIFO empty
+                  pub struct Fempt [6]; }
+}
+
+/// Typed fields of the FSMC_PMEM4 register
+pub mod pmem4 {
+    field_bits! { /// Common memory setup time
+                  pub struct Memset [0, 8]; }
+    field_bits! { /// Common memory wait time
+                  pub struct Memwait [8, 8]; }
+    field_bits! { /// Common memory hold time
+                  pub struct Memhold [16, 8]; }
+    field_bits! { /// Common memory databus HiZ time
+                  pub struct Memhiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_PATT4 register
+pub mod patt4 {
+    field_bits! { /// Attribute memory setup time
+                  pub struct Attset [0, 8]; }
+    field_bits! { /// Attribute memory wait time
+                  pub struct Attwait [8, 8]; }
+    field_bits! { /// Attribute memory hold time
+                  pub struct Atthold [16, 8]; }
+    field_bits! { /// Attribute memory databus HiZ time
+                  pub struct Atthiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_PIO4 register
+pub mod pio4 {
+    field_bits! { /// I/O setup time
+                  pub struct Ioset [0, 8]; }
+    field_bits! { /// I/O wait time
+                  pub struct Iowait [8, 8]; }
+    field_bits! { /// I/O hold time
+                  pub struct Iohold [16, 8]; }
+    field_bits! { /// I/O databus HiZ time
+                  pub struct Iohiz [24, 8]; }
+}
+
+/// Typed fields of the FSMC_BWTR1 register
+pub mod bwtr1 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BWTR2 register
+pub mod bwtr2 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BWTR3 register
+pub mod bwtr3 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
+
+/// Typed fields of the FSMC_BWTR4 register
+pub mod bwtr4 {
+    field_bits! { /// Address setup phase duration
+                  pub struct Addset [0, 4]; }
+    field_bits! { /// Address-hold phase duration
+                  pub struct Addhld [4, 4]; }
+    field_bits! { /// Data-phase duration
+                  pub struct Datast [8, 8]; }
+    field_bits! { /// Clock divide ratio
+                  pub struct Clkdiv [20, 4]; }
+    field_bits! { /// Data latency
+                  pub struct Datlat [24, 4]; }
+    field_bits! { /// Access mode
+                  pub struct Accmod [28, 2]; }
+}
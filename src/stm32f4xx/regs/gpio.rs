@@ -13,6 +13,10 @@ use super::constants::{ GPIOA_BASE,
                         GPIOH_BASE,
                         GPIOI_BASE,
                         };
+#[cfg(feature = "stm32f42x")]
+use super::constants::{ GPIOJ_BASE,
+                        GPIOK_BASE,
+                        };
 
 pub struct GpioRegs {
     /// GPIO port mode register,
@@ -27,13 +31,13 @@ pub struct GpioRegs {
     pub pupdr   : VolatileReg32,
 
     /// GPIO port input data register,
-    pub idr     : VolatileReg32,
+    pub idr     : ReadOnlyReg32,
 
     /// GPIO port output data register,
     pub odr     : VolatileReg32,
 
     /// GPIO port bit set/reset register,
-    pub bsrr   : VolatileReg32,
+    pub bsrr   : WriteOnlyReg32,
 
     /// GPIO port configuration lock register,
     pub lckr    : VolatileReg32,
@@ -43,8 +47,12 @@ pub struct GpioRegs {
 
     /// GPIO alternate function registers,
     pub afrh    : VolatileReg32,
+
+    /// Port the registers belong to
+    pub port    : Port,
 }
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum Port {
     GpioA,
     GpioB,
@@ -55,10 +63,16 @@ pub enum Port {
     GpioG,
     GpioH,
     GpioI,
+    #[cfg(feature = "stm32f42x")]
+    GpioJ,
+    #[cfg(feature = "stm32f42x")]
+    GpioK,
 }
 
 impl GpioRegs {
-    pub fn init(port: &Port) -> GpioRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init(port: &Port) -> GpioRegs {
         let gpio_base: *mut u32 = match port {
             &Port::GpioA => GPIOA_BASE,
             &Port::GpioB => GPIOB_BASE,
@@ -69,6 +83,10 @@ impl GpioRegs {
             &Port::GpioG => GPIOG_BASE,
             &Port::GpioH => GPIOH_BASE,
             &Port::GpioI => GPIOI_BASE,
+            #[cfg(feature = "stm32f42x")]
+            &Port::GpioJ => GPIOJ_BASE,
+            #[cfg(feature = "stm32f42x")]
+            &Port::GpioK => GPIOK_BASE,
         } as *mut u32;
 
         let gpio = GpioRegs {
@@ -76,12 +94,13 @@ impl GpioRegs {
             otyper  : VolatileReg32::new_offset(gpio_base, 1),
             ospeedr : VolatileReg32::new_offset(gpio_base, 2),
             pupdr   : VolatileReg32::new_offset(gpio_base, 3),
-            idr     : VolatileReg32::new_offset(gpio_base, 4),
+            idr     : ReadOnlyReg32::new_offset(gpio_base, 4),
             odr     : VolatileReg32::new_offset(gpio_base, 5),
-            bsrr    : VolatileReg32::new_offset(gpio_base, 6),
+            bsrr    : WriteOnlyReg32::new_offset(gpio_base, 6),
             lckr    : VolatileReg32::new_offset(gpio_base, 7),
             afrl    : VolatileReg32::new_offset(gpio_base, 8),
             afrh    : VolatileReg32::new_offset(gpio_base, 9),
+            port    : *port,
         };
 
         gpio
@@ -371,3 +390,99 @@ pub const GPIO_BSRR_BR_13           : u32   = 0x20000000;
 pub const GPIO_BSRR_BR_14           : u32   = 0x40000000;
 pub const GPIO_BSRR_BR_15           : u32   = 0x80000000;
 
+
+// Typed fields, one field per pin: use `W::set_at(pin, ...)`
+
+/// Typed fields of the GPIO_MODER register
+pub mod moder {
+    field_enum! {
+        /// Port x configuration bits
+        pub enum PinMode [0, 2] {
+            Input   = 0x00,
+            Output  = 0x01,
+            AltFunc = 0x02,
+            Analog  = 0x03,
+        }
+    }
+}
+
+/// Typed fields of the GPIO_OTYPER register
+pub mod otyper {
+    field_enum! {
+        /// Port x output type
+        pub enum PinOutputType [0, 1] {
+            PushPull    = 0x00,
+            OpenDrain   = 0x01,
+        }
+    }
+}
+
+/// Typed fields of the GPIO_OSPEEDR register
+pub mod ospeedr {
+    field_enum! {
+        /// Port x output speed
+        pub enum PinSpeed [0, 2] {
+            Low         = 0x00,
+            Medium      = 0x01,
+            High        = 0x02,
+            VeryHigh    = 0x03,
+        }
+    }
+}
+
+/// Typed fields of the GPIO_PUPDR register
+pub mod pupdr {
+    field_enum! {
+        /// Port x pull-up/pull-down
+        pub enum PinPullUpDown [0, 2] {
+            NoPullUpDown    = 0x00,
+            PullUp          = 0x01,
+            PullDown        = 0x02,
+        }
+    }
+}
+
+/// Typed fields of the GPIO_IDR register
+pub mod idr {
+    field_flag! { /// Port input data
+                  pub struct Idr [0]; }
+}
+
+/// Typed fields of the GPIO_ODR register
+pub mod odr {
+    field_flag! { /// Port output data
+                  pub struct Odr [0]; }
+}
+
+/// Typed fields of the GPIO_BSRR register
+pub mod bsrr {
+    field_flag! { /// Port x set bit
+                  pub struct Bs [0]; }
+    field_flag! { /// Port x reset bit
+                  pub struct Br [16]; }
+}
+
+/// Typed fields of the GPIO_AFRL/GPIO_AFRH registers
+pub mod afr {
+    field_enum! {
+        /// Alternate function selection for port x pin
+        pub enum AltFunc [0, 4] {
+            Af0     = 0,
+            Af1     = 1,
+            Af2     = 2,
+            Af3     = 3,
+            Af4     = 4,
+            Af5     = 5,
+            Af6     = 6,
+            Af7     = 7,
+            Af8     = 8,
+            Af9     = 9,
+            Af10    = 10,
+            Af11    = 11,
+            Af12    = 12,
+            Af13    = 13,
+            Af14    = 14,
+            Af15    = 15,
+        }
+    }
+}
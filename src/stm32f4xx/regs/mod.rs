@@ -1,9 +1,11 @@
 pub use super::constants;
 
 pub mod flash;
+pub mod fsmc;
 pub mod gpio;
 pub mod pwr;
 pub mod rcc;
 pub mod spi;
 pub mod tim;
+pub mod usart;
 
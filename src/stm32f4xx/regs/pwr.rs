@@ -17,7 +17,9 @@ pub struct PwrRegs
 
 impl PwrRegs {
 
-    pub fn init() -> PwrRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> PwrRegs {
         let pwr_base: *mut u32 = PWR_BASE as *mut u32;
 
         let pwr = PwrRegs {
@@ -55,7 +57,14 @@ pub const PWR_CR_PLS_LEV7   : u32   = 0x000000E0;   // PVD level 7
 
 pub const PWR_CR_DBP        : u32   = 0x00000100;   // Disable Backup Domain write protection
 pub const PWR_CR_FPDS       : u32   = 0x00000200;   // Flash power down in Stop mode
+#[cfg(feature = "stm32f40x")]
 pub const PWR_CR_VOS        : u32   = 0x00004000;   // Regulator voltage scaling output selection
+#[cfg(feature = "stm32f42x")]
+pub const PWR_CR_VOS        : u32   = 0x0000C000;   // VOS[1:0] bits (Regulator voltage scaling output selection)
+#[cfg(feature = "stm32f42x")]
+pub const PWR_CR_VOS_0      : u32   = 0x00004000;   // Bit 0
+#[cfg(feature = "stm32f42x")]
+pub const PWR_CR_VOS_1      : u32   = 0x00008000;   // Bit 1
 
 // Bit definition for PWR_CSR register
 pub const PWR_CSR_WUF       : u32   = 0x00000001;   // Wakeup Flag
@@ -65,3 +74,69 @@ pub const PWR_CSR_BRR       : u32   = 0x00000008;   // Backup regulator ready
 pub const PWR_CSR_EWUP      : u32   = 0x00000100;   // Enable WKUP pin
 pub const PWR_CSR_BRE       : u32   = 0x00000200;   // Backup regulator enable
 pub const PWR_CSR_VOSRDY    : u32   = 0x00004000;   // Regulator voltage scaling output selection ready
+
+/// Typed fields of the PWR_CR register
+pub mod cr {
+    field_flag! { /// Low-Power Deepsleep
+                  pub struct Lpds [0]; }
+    field_flag! { /// Power Down Deepsleep
+                  pub struct Pdds [1]; }
+    field_flag! { /// Clear Wakeup Flag
+                  pub struct Cwuf [2]; }
+    field_flag! { /// Clear Standby Flag
+                  pub struct Csbf [3]; }
+    field_flag! { /// Power Voltage Detector Enable
+                  pub struct Pvde [4]; }
+    field_enum! {
+        /// PVD Level Selection
+        pub enum Pls [5, 3] {
+            Lev0    = 0,
+            Lev1    = 1,
+            Lev2    = 2,
+            Lev3    = 3,
+            Lev4    = 4,
+            Lev5    = 5,
+            Lev6    = 6,
+            Lev7    = 7,
+        }
+    }
+    field_flag! { /// Disable Backup Domain write protection
+                  pub struct Dbp [8]; }
+    field_flag! { /// Flash power down in Stop mode
+                  pub struct Fpds [9]; }
+    #[cfg(feature = "stm32f40x")]
+    field_enum! {
+        /// Regulator voltage scaling output selection
+        pub enum Vos [14, 1] {
+            Scale2  = 0,
+            Scale1  = 1,
+        }
+    }
+    #[cfg(feature = "stm32f42x")]
+    field_enum! {
+        /// Regulator voltage scaling output selection
+        pub enum Vos [14, 2] {
+            Scale3  = 1,
+            Scale2  = 2,
+            Scale1  = 3,
+        }
+    }
+}
+
+/// Typed fields of the PWR_CSR register
+pub mod csr {
+    field_flag! { /// Wakeup Flag
+                  pub struct Wuf [0]; }
+    field_flag! { /// Standby Flag
+                  pub struct Sbf [1]; }
+    field_flag! { /// PVD Output
+                  pub struct Pvdo [2]; }
+    field_flag! { /// Backup regulator ready
+                  pub struct Brr [3]; }
+    field_flag! { /// Enable WKUP pin
+                  pub struct Ewup [8]; }
+    field_flag! { /// Backup regulator enable
+                  pub struct Bre [9]; }
+    field_flag! { /// Regulator voltage scaling output selection ready
+                  pub struct VosRdy [14]; }
+}
@@ -79,7 +79,9 @@ pub struct RccRegs {
 
 impl RccRegs {
 
-    pub fn init() -> RccRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init() -> RccRegs {
 
         let rcc_base: *mut u32 = RCC_BASE as *mut u32;
 
@@ -312,17 +314,22 @@ pub const RCC_AHB1RSTR_GPIOIRST         : u32   = 0x00000100;
 pub const RCC_AHB1RSTR_CRCRST           : u32   = 0x00001000;
 pub const RCC_AHB1RSTR_DMA1RST          : u32   = 0x00200000;
 pub const RCC_AHB1RSTR_DMA2RST          : u32   = 0x00400000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1RSTR_ETHMACRST        : u32   = 0x02000000;
 pub const RCC_AHB1RSTR_OTGHRST          : u32   = 0x10000000;
 
 // Bit definition for RCC_AHB2RSTR register
+#[cfg(feature = "dcmi")]
 pub const RCC_AHB2RSTR_DCMIRST          : u32   = 0x00000001;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2RSTR_CRYPRST          : u32   = 0x00000010;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2RSTR_HSAHRST          : u32   = 0x00000020;
 pub const RCC_AHB2RSTR_RNGRST           : u32   = 0x00000040;
 pub const RCC_AHB2RSTR_OTGFSRST         : u32   = 0x00000080;
 
 // Bit definition for RCC_AHB3RSTR register
+#[cfg(feature = "stm32f40x")]
 pub const RCC_AHB3RSTR_FSMCRST          : u32   = 0x00000001;
 
 // Bit definition for RCC_APB1RSTR register
@@ -373,27 +380,43 @@ pub const RCC_AHB1ENR_GPIOFEN           : u32   = 0x00000020;
 pub const RCC_AHB1ENR_GPIOGEN           : u32   = 0x00000040;
 pub const RCC_AHB1ENR_GPIOHEN           : u32   = 0x00000080;
 pub const RCC_AHB1ENR_GPIOIEN           : u32   = 0x00000100;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_AHB1ENR_GPIOJEN           : u32   = 0x00000200;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_AHB1ENR_GPIOKEN           : u32   = 0x00000400;
 pub const RCC_AHB1ENR_CRCEN             : u32   = 0x00001000;
 pub const RCC_AHB1ENR_BKPSRAMEN         : u32   = 0x00040000;
 pub const RCC_AHB1ENR_CCMDATARAMEN      : u32   = 0x00100000;
 pub const RCC_AHB1ENR_DMA1EN            : u32   = 0x00200000;
 pub const RCC_AHB1ENR_DMA2EN            : u32   = 0x00400000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_AHB1ENR_DMA2DEN           : u32   = 0x00800000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1ENR_ETHMACEN          : u32   = 0x02000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1ENR_ETHMACTXEN        : u32   = 0x04000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1ENR_ETHMACRXEN        : u32   = 0x08000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1ENR_ETHMACPTPEN       : u32   = 0x10000000;
 pub const RCC_AHB1ENR_OTGHSEN           : u32   = 0x20000000;
 pub const RCC_AHB1ENR_OTGHSULPIEN       : u32   = 0x40000000;
 
 // Bit definition for RCC_AHB2ENR register
+#[cfg(feature = "dcmi")]
 pub const RCC_AHB2ENR_DCMIEN            : u32   = 0x00000001;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2ENR_CRYPEN            : u32   = 0x00000010;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2ENR_HASHEN            : u32   = 0x00000020;
 pub const RCC_AHB2ENR_RNGEN             : u32   = 0x00000040;
 pub const RCC_AHB2ENR_OTGFSEN           : u32   = 0x00000080;
 
 // Bit definition for RCC_AHB3ENR register
+#[cfg(feature = "stm32f40x")]
 pub const RCC_AHB3ENR_FSMCEN            : u32   = 0x00000001;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_AHB3ENR_FMCEN             : u32   = 0x00000001;
 
 // Bit definition for RCC_APB1ENR register
 pub const RCC_APB1ENR_TIM2EN            : u32   = 0x00000001;
@@ -419,6 +442,10 @@ pub const RCC_APB1ENR_CAN1EN            : u32   = 0x02000000;
 pub const RCC_APB1ENR_CAN2EN            : u32   = 0x04000000;
 pub const RCC_APB1ENR_PWREN             : u32   = 0x10000000;
 pub const RCC_APB1ENR_DACEN             : u32   = 0x20000000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB1ENR_UART7EN           : u32   = 0x40000000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB1ENR_UART8EN           : u32   = 0x80000000;
 
 // Bit definition for RCC_APB2ENR register
 pub const RCC_APB2ENR_TIM1EN            : u32   = 0x00000001;
@@ -434,6 +461,16 @@ pub const RCC_APB2ENR_SYSCFGEN          : u32   = 0x00004000;
 pub const RCC_APB2ENR_TIM11EN           : u32   = 0x00040000;
 pub const RCC_APB2ENR_TIM10EN           : u32   = 0x00020000;
 pub const RCC_APB2ENR_TIM9EN            : u32   = 0x00010000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB2ENR_SPI4EN            : u32   = 0x00002000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB2ENR_SPI5EN            : u32   = 0x00100000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB2ENR_SPI6EN            : u32   = 0x00200000;
+#[cfg(feature = "stm32f42x")]
+pub const RCC_APB2ENR_SAI1EN            : u32   = 0x00400000;
+#[cfg(feature = "ltdc")]
+pub const RCC_APB2ENR_LTDCEN            : u32   = 0x04000000;
 
 // Bit definition for RCC_AHB1LPENR register
 pub const RCC_AHB1LPENR_GPIOALPEN       : u32   = 0x00000001;
@@ -452,21 +489,29 @@ pub const RCC_AHB1LPENR_SRAM2LPEN       : u32   = 0x00020000;
 pub const RCC_AHB1LPENR_BKPSRAMLPEN     : u32   = 0x00040000;
 pub const RCC_AHB1LPENR_DMA1LPEN        : u32   = 0x00200000;
 pub const RCC_AHB1LPENR_DMA2LPEN        : u32   = 0x00400000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1LPENR_ETHMACLPEN      : u32   = 0x02000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1LPENR_ETHMACTXLPEN    : u32   = 0x04000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1LPENR_ETHMACRXLPEN    : u32   = 0x08000000;
+#[cfg(feature = "eth")]
 pub const RCC_AHB1LPENR_ETHMACPTPLPEN   : u32   = 0x10000000;
 pub const RCC_AHB1LPENR_OTGHSLPEN       : u32   = 0x20000000;
 pub const RCC_AHB1LPENR_OTGHSULPILPEN   : u32   = 0x40000000;
 
 // Bit definition for RCC_AHB2LPENR register
+#[cfg(feature = "dcmi")]
 pub const RCC_AHB2LPENR_DCMILPEN        : u32   = 0x00000001;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2LPENR_CRYPLPEN        : u32   = 0x00000010;
+#[cfg(feature = "cryp")]
 pub const RCC_AHB2LPENR_HASHLPEN        : u32   = 0x00000020;
 pub const RCC_AHB2LPENR_RNGLPEN         : u32   = 0x00000040;
 pub const RCC_AHB2LPENR_OTGFSLPEN       : u32   = 0x00000080;
 
 // Bit definition for RCC_AHB3LPENR register
+#[cfg(feature = "stm32f40x")]
 pub const RCC_AHB3LPENR_FSMCLPEN        : u32   = 0x00000001;
 
 // Bit definition for RCC_APB1LPENR register
@@ -542,3 +587,190 @@ pub const RCC_SSCGR_SSCGEN              : u32   = 0x80000000;
 // Bit definition for RCC_PLLI2SCFGR register
 pub const RCC_PLLI2SCFGR_PLLI2SN        : u32   = 0x00007FC0;
 pub const RCC_PLLI2SCFGR_PLLI2SR        : u32   = 0x70000000;
+
+/// Typed fields of the RCC_CR register
+pub mod cr {
+    field_flag! { /// Internal high-speed clock enable
+                  pub struct HsiOn [0]; }
+    field_flag! { /// Internal high-speed clock ready flag
+                  pub struct HsiRdy [1]; }
+    field_bits! { /// Internal high-speed clock trimming
+                  pub struct HsiTrim [3, 5]; }
+    field_bits! { /// Internal high-speed clock calibration
+                  pub struct HsiCal [8, 8]; }
+    field_flag! { /// HSE clock enable
+                  pub struct HseOn [16]; }
+    field_flag! { /// HSE clock ready flag
+                  pub struct HseRdy [17]; }
+    field_flag! { /// HSE clock bypass
+                  pub struct HseByp [18]; }
+    field_flag! { /// Clock security system enable
+                  pub struct CssOn [19]; }
+    field_flag! { /// Main PLL enable
+                  pub struct PllOn [24]; }
+    field_flag! { /// Main PLL clock ready flag
+                  pub struct PllRdy [25]; }
+    field_flag! { /// PLLI2S enable
+                  pub struct PllI2sOn [26]; }
+    field_flag! { /// PLLI2S clock ready flag
+                  pub struct PllI2sRdy [27]; }
+}
+
+/// Typed fields of the RCC_PLLCFGR register
+pub mod pllcfgr {
+    field_bits! { /// Division factor for the main PLL input clock (2..63)
+                  pub struct PllM [0, 6]; }
+    field_bits! { /// Main PLL multiplication factor for VCO (50..432)
+                  pub struct PllN [6, 9]; }
+    field_enum! {
+        /// Main PLL division factor for main system clock
+        pub enum PllP [16, 2] {
+            Div2 = 0,
+            Div4 = 1,
+            Div6 = 2,
+            Div8 = 3,
+        }
+    }
+    field_enum! {
+        /// Main PLL and PLLI2S entry clock source
+        pub enum PllSrc [22, 1] {
+            Hsi = 0,
+            Hse = 1,
+        }
+    }
+    field_bits! { /// Main PLL division factor for USB OTG FS, SDIO and RNG (2..15)
+                  pub struct PllQ [24, 4]; }
+}
+
+/// Typed fields of the RCC_CFGR register
+pub mod cfgr {
+    field_enum! {
+        /// System clock switch
+        pub enum Sw [0, 2] {
+            Hsi = 0,
+            Hse = 1,
+            Pll = 2,
+        }
+    }
+    field_enum! {
+        /// System clock switch status
+        pub enum Sws [2, 2] {
+            Hsi = 0,
+            Hse = 1,
+            Pll = 2,
+        }
+    }
+    field_enum! {
+        /// AHB prescaler
+        pub enum Hpre [4, 4] {
+            Div1    = 0,
+            Div2    = 8,
+            Div4    = 9,
+            Div8    = 10,
+            Div16   = 11,
+            Div64   = 12,
+            Div128  = 13,
+            Div256  = 14,
+            Div512  = 15,
+        }
+    }
+    field_enum! {
+        /// APB low-speed prescaler (APB1)
+        pub enum Ppre1 [10, 3] {
+            Div1    = 0,
+            Div2    = 4,
+            Div4    = 5,
+            Div8    = 6,
+            Div16   = 7,
+        }
+    }
+    field_enum! {
+        /// APB high-speed prescaler (APB2)
+        pub enum Ppre2 [13, 3] {
+            Div1    = 0,
+            Div2    = 4,
+            Div4    = 5,
+            Div8    = 6,
+            Div16   = 7,
+        }
+    }
+    field_bits! { /// HSE division factor for RTC clock
+                  pub struct RtcPre [16, 5]; }
+    field_enum! {
+        /// Microcontroller clock output 1
+        pub enum Mco1 [21, 2] {
+            Hsi = 0,
+            Lse = 1,
+            Hse = 2,
+            Pll = 3,
+        }
+    }
+    field_enum! {
+        /// I2S clock selection
+        pub enum I2sSrc [23, 1] {
+            PllI2s  = 0,
+            I2sCkin = 1,
+        }
+    }
+    field_enum! {
+        /// MCO1 prescaler
+        pub enum Mco1Pre [24, 3] {
+            Div1    = 0,
+            Div2    = 4,
+            Div3    = 5,
+            Div4    = 6,
+            Div5    = 7,
+        }
+    }
+    field_enum! {
+        /// MCO2 prescaler
+        pub enum Mco2Pre [27, 3] {
+            Div1    = 0,
+            Div2    = 4,
+            Div3    = 5,
+            Div4    = 6,
+            Div5    = 7,
+        }
+    }
+    field_enum! {
+        /// Microcontroller clock output 2
+        pub enum Mco2 [30, 2] {
+            SysClk  = 0,
+            PllI2s  = 1,
+            Hse     = 2,
+            Pll     = 3,
+        }
+    }
+}
+
+/// Typed fields of the RCC_CSR register
+pub mod csr {
+    field_flag! { /// Internal low-speed oscillator enable
+                  pub struct LsiOn [0]; }
+    field_flag! { /// Internal low-speed oscillator ready
+                  pub struct LsiRdy [1]; }
+    field_flag! { /// Remove reset flag
+                  pub struct Rmvf [24]; }
+    field_flag! { /// BOR reset flag
+                  pub struct BorRstF [25]; }
+    field_flag! { /// PIN reset flag
+                  pub struct PadRstF [26]; }
+    field_flag! { /// POR/PDR reset flag
+                  pub struct PorRstF [27]; }
+    field_flag! { /// Software reset flag
+                  pub struct SftRstF [28]; }
+    field_flag! { /// Independent watchdog reset flag
+                  pub struct WdgRstF [29]; }
+    field_flag! { /// Window watchdog reset flag
+                  pub struct WwdgRstF [30]; }
+    field_flag! { /// Low-power reset flag
+                  pub struct LpwrRstF [31]; }
+}
+
+/// Typed fields of the RCC_PLLI2SCFGR register
+pub mod plli2scfgr {
+    field_bits! { /// PLLI2S multiplication factor for VCO (50..432)
+                  pub struct PllI2sN [6, 9]; }
+    field_bits! { /// PLLI2S division factor for I2S clocks (2..7)
+                  pub struct PllI2sR [28, 3]; }
+}
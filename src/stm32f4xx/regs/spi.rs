@@ -2,6 +2,8 @@
 
 //! Serial Peripheral Interface registers
 
+use ::volatile_reg8::*;
+use ::volatile_reg16::*;
 use ::volatile_reg32::*;
 use super::constants::{ I2S2_EXT_BASE,
                         I2S3_EXT_BASE,
@@ -9,9 +11,14 @@ use super::constants::{ I2S2_EXT_BASE,
                         SPI2_BASE,
                         SPI3_BASE,
                         };
+#[cfg(feature = "stm32f42x")]
+use super::constants::{ SPI4_BASE,
+                        SPI5_BASE,
+                        SPI6_BASE,
+                        };
 
 
-struct SpiRegs
+pub struct SpiRegs
 {
     /// SPI control register 1 (not used in I2S mode)
     pub cr1     : VolatileReg32,
@@ -22,17 +29,20 @@ struct SpiRegs
     /// SPI status register
     pub sr      : VolatileReg32,
 
-    /// SPI data register
-    pub dr      : VolatileReg32,
+    /// SPI data register, 16-bit wide
+    pub dr      : VolatileReg16,
+
+    /// SPI data register accessed as a byte, for 8-bit frames
+    pub dr8     : VolatileReg8,
 
     /// SPI CRC polynomial register (not used in I2S mode)
     pub crcpr   : VolatileReg32,
 
     /// SPI RX CRC register (not used in I2S mode)
-    pub rxcrcr  : VolatileReg32,
+    pub rxcrcr  : ReadOnlyReg32,
 
     /// SPI TX CRC register (not used in I2S mode)
-    pub txcrcr  : VolatileReg32,
+    pub txcrcr  : ReadOnlyReg32,
 
     /// SPI_I2S configuration register
     pub i2scfgr : VolatileReg32,
@@ -47,26 +57,41 @@ pub enum SpiInst {
     Spi1,
     Spi2,
     Spi3,
+    #[cfg(feature = "stm32f42x")]
+    Spi4,
+    #[cfg(feature = "stm32f42x")]
+    Spi5,
+    #[cfg(feature = "stm32f42x")]
+    Spi6,
 }
 
 impl SpiRegs {
-    pub fn init(inst: SpiInst) -> SpiRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init(inst: SpiInst) -> SpiRegs {
         let spi_base: *mut u32 = match inst {
             SpiInst::I2s2ext    => I2S2_EXT_BASE,
             SpiInst::I2s3ext    => I2S3_EXT_BASE,
             SpiInst::Spi1       => SPI1_BASE,
             SpiInst::Spi2       => SPI2_BASE,
             SpiInst::Spi3       => SPI3_BASE,
+            #[cfg(feature = "stm32f42x")]
+            SpiInst::Spi4       => SPI4_BASE,
+            #[cfg(feature = "stm32f42x")]
+            SpiInst::Spi5       => SPI5_BASE,
+            #[cfg(feature = "stm32f42x")]
+            SpiInst::Spi6       => SPI6_BASE,
         } as *mut u32;
 
         let spi = SpiRegs {
             cr1     : VolatileReg32::new(spi_base),
             cr2     : VolatileReg32::new_offset(spi_base, 1),
             sr      : VolatileReg32::new_offset(spi_base, 2),
-            dr      : VolatileReg32::new_offset(spi_base, 3),
+            dr      : VolatileReg16::new(spi_base.wrapping_offset(3) as *mut u16),
+            dr8     : VolatileReg8::new(spi_base.wrapping_offset(3) as *mut u8),
             crcpr   : VolatileReg32::new_offset(spi_base, 4),
-            rxcrcr  : VolatileReg32::new_offset(spi_base, 5),
-            txcrcr  : VolatileReg32::new_offset(spi_base, 6),
+            rxcrcr  : ReadOnlyReg32::new_offset(spi_base, 5),
+            txcrcr  : ReadOnlyReg32::new_offset(spi_base, 6),
             i2scfgr : VolatileReg32::new_offset(spi_base, 7),
             i2spr   : VolatileReg32::new_offset(spi_base, 8),
         };
@@ -154,3 +179,162 @@ pub const SPI_I2SPR_I2SDIV          : u32   = 0x000000FF;   // I2S Linear presca
 pub const SPI_I2SPR_ODD             : u32   = 0x00000100;   // Odd factor for the prescaler
 pub const SPI_I2SPR_MCKOE           : u32   = 0x00000200;   // Master Clock Output Enable
 
+
+/// Typed fields of the SPI_CR1 register
+pub mod cr1 {
+    field_flag! { /// Clock Phase
+                  pub struct Cpha [0]; }
+    field_flag! { /// Clock Polarity
+                  pub struct Cpol [1]; }
+    field_flag! { /// Master Selection
+                  pub struct Mstr [2]; }
+    field_enum! {
+        /// Baud Rate Control
+        pub enum Br [3, 3] {
+            Div2    = 0,
+            Div4    = 1,
+            Div8    = 2,
+            Div16   = 3,
+            Div32   = 4,
+            Div64   = 5,
+            Div128  = 6,
+            Div256  = 7,
+        }
+    }
+    field_flag! { /// SPI Enable
+                  pub struct Spe [6]; }
+    field_flag! { /// Frame Format
+                  pub struct LsbFirst [7]; }
+    field_flag! { /// Internal slave select
+                  pub struct Ssi [8]; }
+    field_flag! { /// Software slave management
+                  pub struct Ssm [9]; }
+    field_flag! { /// Receive only
+                  pub struct RxOnly [10]; }
+    field_enum! {
+        /// Data Frame Format
+        pub enum Dff [11, 1] {
+            Bits8   = 0,
+            Bits16  = 1,
+        }
+    }
+    field_flag! { /// Transmit CRC next
+                  pub struct CrcNext [12]; }
+    field_flag! { /// Hardware CRC calculation enable
+                  pub struct CrcEn [13]; }
+    field_flag! { /// Output enable in bidirectional mode
+                  pub struct BidiOe [14]; }
+    field_flag! { /// Bidirectional data mode enable
+                  pub struct BidiMode [15]; }
+}
+
+/// Typed fields of the SPI_CR2 register
+pub mod cr2 {
+    field_flag! { /// Rx Buffer DMA Enable
+                  pub struct RxDmaEn [0]; }
+    field_flag! { /// Tx Buffer DMA Enable
+                  pub struct TxDmaEn [1]; }
+    field_flag! { /// SS Output Enable
+                  pub struct Ssoe [2]; }
+    field_flag! { /// Error Interrupt Enable
+                  pub struct ErrIe [5]; }
+    field_flag! { /// RX buffer Not Empty Interrupt Enable
+                  pub struct RxneIe [6]; }
+    field_flag! { /// Tx buffer Empty Interrupt Enable
+                  pub struct TxeIe [7]; }
+}
+
+/// Typed fields of the SPI_SR register
+pub mod sr {
+    field_flag! { /// Receive buffer Not Empty
+                  pub struct Rxne [0]; }
+    field_flag! { /// Transmit buffer Empty
+                  pub struct Txe [1]; }
+    field_flag! { /// Channel side
+                  pub struct ChSide [2]; }
+    field_flag! { /// Underrun flag
+                  pub struct Udr [3]; }
+    field_flag! { /// CRC Error flag
+                  pub struct CrcErr [4]; }
+    field_flag! { /// Mode fault
+                  pub struct Modf [5]; }
+    field_flag! { /// Overrun flag
+                  pub struct Ovr [6]; }
+    field_flag! { /// Busy flag
+                  pub struct Bsy [7]; }
+}
+
+/// Typed fields of the SPI_I2SCFGR register
+pub mod i2scfgr {
+    field_enum! {
+        /// Channel length (number of bits per audio channel)
+        pub enum ChLen [0, 1] {
+            Bits16  = 0,
+            Bits32  = 1,
+        }
+    }
+    field_enum! {
+        /// Data length to be transferred
+        pub enum DatLen [1, 2] {
+            Bits16  = 0,
+            Bits24  = 1,
+            Bits32  = 2,
+        }
+    }
+    field_flag! { /// Steady state clock polarity
+                  pub struct CkPol [3]; }
+    field_enum! {
+        /// I2S standard selection
+        pub enum I2sStd [4, 2] {
+            Philips = 0,
+            Msb     = 1,
+            Lsb     = 2,
+            Pcm     = 3,
+        }
+    }
+    field_flag! { /// PCM frame synchronization
+                  pub struct PcmSync [7]; }
+    field_enum! {
+        /// I2S configuration mode
+        pub enum I2sCfg [8, 2] {
+            SlaveTx     = 0,
+            SlaveRx     = 1,
+            MasterTx    = 2,
+            MasterRx    = 3,
+        }
+    }
+    field_flag! { /// I2S Enable
+                  pub struct I2se [10]; }
+    field_flag! { /// I2S mode selection
+                  pub struct I2sMod [11]; }
+}
+
+/// Typed fields of the SPI_I2SPR register
+pub mod i2spr {
+    field_bits! { /// I2S Linear prescaler (2..255)
+                  pub struct I2sDiv [0, 8]; }
+    field_flag! { /// Odd factor for the prescaler
+                  pub struct Odd [8]; }
+    field_flag! { /// Master Clock Output Enable
+                  pub struct Mckoe [9]; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mock_regs;
+
+    #[test]
+    fn dr8_is_a_byte_access() {
+        mock_regs::reset();
+        let spi = unsafe { SpiRegs::init(SpiInst::Spi2) };
+
+        mock_regs::start_trace();
+        spi.dr8.set(0x5A);
+        let trace = mock_regs::take_trace();
+
+        assert_eq!(trace.len(), 1);
+        assert_eq!(trace[0].address, spi.dr.address());
+        assert_eq!(spi.dr.get(), 0x005A);
+    }
+}
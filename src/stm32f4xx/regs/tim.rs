@@ -37,7 +37,7 @@ pub struct TimRegs
     pub sr      : VolatileReg32,
 
     /// TIM event generation register
-    pub egr     : VolatileReg32,
+    pub egr     : WriteOnlyReg32,
 
     /// TIM capture/compare mode register 1
     pub ccmr1   : VolatileReg32,
@@ -103,7 +103,9 @@ pub enum TimInst {
 }
 
 impl TimRegs {
-    pub fn init(tim_inst: TimInst) -> TimRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init(tim_inst: TimInst) -> TimRegs {
         let tim_base = match tim_inst {
             TimInst::TIM1   => TIM1_BASE,
             TimInst::TIM2   => TIM2_BASE,
@@ -127,7 +129,7 @@ impl TimRegs {
             smcr    : VolatileReg32::new_offset(tim_base, 2),
             dier    : VolatileReg32::new_offset(tim_base, 3),
             sr      : VolatileReg32::new_offset(tim_base, 4),
-            egr     : VolatileReg32::new_offset(tim_base, 5),
+            egr     : WriteOnlyReg32::new_offset(tim_base, 5),
             ccmr1   : VolatileReg32::new_offset(tim_base, 6),
             ccmr2   : VolatileReg32::new_offset(tim_base, 7),
             ccer    : VolatileReg32::new_offset(tim_base, 8),
@@ -443,3 +445,78 @@ pub const TIM_OR_ITR1_RMP       : u32   = 0x00000C00;       // ITR1_RMP[1:0] bit
 pub const TIM_OR_ITR1_RMP_0     : u32   = 0x00000400;       // Bit 0
 pub const TIM_OR_ITR1_RMP_1     : u32   = 0x00000800;       // Bit 1
 
+
+/// Typed fields of the TIM_CR1 register
+pub mod cr1 {
+    field_flag! { /// Counter enable
+                  pub struct Cen [0]; }
+    field_flag! { /// Update disable
+                  pub struct Udis [1]; }
+    field_flag! { /// Update request source
+                  pub struct Urs [2]; }
+    field_flag! { /// One pulse mode
+                  pub struct Opm [3]; }
+    field_flag! { /// Direction
+                  pub struct Dir [4]; }
+    field_enum! {
+        /// Center-aligned mode selection
+        pub enum Cms [5, 2] {
+            Edge        = 0,
+            Center1     = 1,
+            Center2     = 2,
+            Center3     = 3,
+        }
+    }
+    field_flag! { /// Auto-reload preload enable
+                  pub struct Arpe [7]; }
+    field_enum! {
+        /// Clock division
+        pub enum Ckd [8, 2] {
+            Div1        = 0,
+            Div2        = 1,
+            Div4        = 2,
+        }
+    }
+}
+
+/// Typed fields of the TIM_DIER register
+pub mod dier {
+    field_flag! { /// Update interrupt enable
+                  pub struct Uie [0]; }
+    field_flag! { /// Capture/Compare 1 interrupt enable
+                  pub struct Cc1Ie [1]; }
+    field_flag! { /// Capture/Compare 2 interrupt enable
+                  pub struct Cc2Ie [2]; }
+    field_flag! { /// Capture/Compare 3 interrupt enable
+                  pub struct Cc3Ie [3]; }
+    field_flag! { /// Capture/Compare 4 interrupt enable
+                  pub struct Cc4Ie [4]; }
+    field_flag! { /// Update DMA request enable
+                  pub struct Ude [8]; }
+}
+
+/// Typed fields of the TIM_SR register
+pub mod sr {
+    field_flag! { /// Update interrupt Flag
+                  pub struct Uif [0]; }
+    field_flag! { /// Capture/Compare 1 interrupt Flag
+                  pub struct Cc1If [1]; }
+    field_flag! { /// Capture/Compare 2 interrupt Flag
+                  pub struct Cc2If [2]; }
+    field_flag! { /// Capture/Compare 3 interrupt Flag
+                  pub struct Cc3If [3]; }
+    field_flag! { /// Capture/Compare 4 interrupt Flag
+                  pub struct Cc4If [4]; }
+}
+
+/// Typed fields of the TIM_EGR register
+pub mod egr {
+    field_flag! { /// Update Generation
+                  pub struct Ug [0]; }
+}
+
+/// Typed fields of the TIM_PSC register
+pub mod psc {
+    field_bits! { /// Prescaler value
+                  pub struct Psc [0, 16]; }
+}
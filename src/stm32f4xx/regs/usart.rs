@@ -0,0 +1,315 @@
+#![allow(dead_code)]
+
+//! Universal synchronous asynchronous receiver transmitter registers
+//!
+//! Generated by tools/svd2regs from STM32F40x.svd, do not edit.
+
+use ::volatile_reg32::*;
+
+pub const USART1_BASE : u32   = 0x40011000;
+pub const USART2_BASE : u32   = 0x40004400;
+pub const USART3_BASE : u32   = 0x40004800;
+pub const UART4_BASE  : u32   = 0x40004C00;
+pub const UART5_BASE  : u32   = 0x40005000;
+pub const USART6_BASE : u32   = 0x40011400;
+
+
+pub struct UsartRegs
+{
+    /// Status register
+    pub sr   : VolatileReg32,
+
+    /// Data register
+    pub dr   : VolatileReg32,
+
+    /// Baud rate register
+    pub brr  : VolatileReg32,
+
+    /// Control register 1
+    pub cr1  : VolatileReg32,
+
+    /// Control register 2
+    pub cr2  : VolatileReg32,
+
+    /// Control register 3
+    pub cr3  : VolatileReg32,
+
+    /// Guard time and prescaler register
+    pub gtpr : VolatileReg32,
+}
+
+pub enum UsartInst {
+    Usart1,
+    Usart2,
+    Usart3,
+    Uart4,
+    Uart5,
+    Usart6,
+}
+
+impl UsartRegs {
+    /// The caller must make sure the block is not accessed through another
+    /// instance, drivers get it from `Peripherals::take()` instead
+    pub unsafe fn init(inst: UsartInst) -> UsartRegs {
+        let usart_base: *mut u32 = match inst {
+            UsartInst::Usart1 => USART1_BASE,
+            UsartInst::Usart2 => USART2_BASE,
+            UsartInst::Usart3 => USART3_BASE,
+            UsartInst::Uart4  => UART4_BASE,
+            UsartInst::Uart5  => UART5_BASE,
+            UsartInst::Usart6 => USART6_BASE,
+        } as *mut u32;
+
+        UsartRegs {
+            sr   : VolatileReg32::new(usart_base),
+            dr   : VolatileReg32::new_offset(usart_base, 1),
+            brr  : VolatileReg32::new_offset(usart_base, 2),
+            cr1  : VolatileReg32::new_offset(usart_base, 3),
+            cr2  : VolatileReg32::new_offset(usart_base, 4),
+            cr3  : VolatileReg32::new_offset(usart_base, 5),
+            gtpr : VolatileReg32::new_offset(usart_base, 6),
+        }
+    }
+}
+
+// Bit definition for USART_SR register
+pub const USART_SR_PE   : u32   = 0x00000001;   // Parity error
+pub const USART_SR_FE   : u32   = 0x00000002;   // Framing error
+pub const USART_SR_NF   : u32   = 0x00000004;   // Noise detected flag
+pub const USART_SR_ORE  : u32   = 0x00000008;   // Overrun error
+pub const USART_SR_IDLE : u32   = 0x00000010;   // IDLE line detected
+pub const USART_SR_RXNE : u32   = 0x00000020;   // Read data register not empty
+pub const USART_SR_TC   : u32   = 0x00000040;   // Transmission complete
+pub const USART_SR_TXE  : u32   = 0x00000080;   // Transmit data register empty
+pub const USART_SR_LBD  : u32   = 0x00000100;   // LIN break detection flag
+pub const USART_SR_CTS  : u32   = 0x00000200;   // CTS flag
+
+// Bit definition for USART_DR register
+pub const USART_DR_DR   : u32   = 0x000001FF;   // Data value
+pub const USART_DR_DR_0 : u32   = 0x00000001;   // Bit 0
+pub const USART_DR_DR_1 : u32   = 0x00000002;   // Bit 1
+pub const USART_DR_DR_2 : u32   = 0x00000004;   // Bit 2
+pub const USART_DR_DR_3 : u32   = 0x00000008;   // Bit 3
+pub const USART_DR_DR_4 : u32   = 0x00000010;   // Bit 4
+pub const USART_DR_DR_5 : u32   = 0x00000020;   // Bit 5
+pub const USART_DR_DR_6 : u32   = 0x00000040;   // Bit 6
+pub const USART_DR_DR_7 : u32   = 0x00000080;   // Bit 7
+pub const USART_DR_DR_8 : u32   = 0x00000100;   // Bit 8
+
+// Bit definition for USART_BRR register
+pub const USART_BRR_DIV_FRACTION    : u32   = 0x0000000F;   // Fraction of USARTDIV
+pub const USART_BRR_DIV_FRACTION_0  : u32   = 0x00000001;   // Bit 0
+pub const USART_BRR_DIV_FRACTION_1  : u32   = 0x00000002;   // Bit 1
+pub const USART_BRR_DIV_FRACTION_2  : u32   = 0x00000004;   // Bit 2
+pub const USART_BRR_DIV_FRACTION_3  : u32   = 0x00000008;   // Bit 3
+pub const USART_BRR_DIV_MANTISSA    : u32   = 0x0000FFF0;   // Mantissa of USARTDIV
+pub const USART_BRR_DIV_MANTISSA_0  : u32   = 0x00000010;   // Bit 0
+pub const USART_BRR_DIV_MANTISSA_1  : u32   = 0x00000020;   // Bit 1
+pub const USART_BRR_DIV_MANTISSA_2  : u32   = 0x00000040;   // Bit 2
+pub const USART_BRR_DIV_MANTISSA_3  : u32   = 0x00000080;   // Bit 3
+pub const USART_BRR_DIV_MANTISSA_4  : u32   = 0x00000100;   // Bit 4
+pub const USART_BRR_DIV_MANTISSA_5  : u32   = 0x00000200;   // Bit 5
+pub const USART_BRR_DIV_MANTISSA_6  : u32   = 0x00000400;   // Bit 6
+pub const USART_BRR_DIV_MANTISSA_7  : u32   = 0x00000800;   // Bit 7
+pub const USART_BRR_DIV_MANTISSA_8  : u32   = 0x00001000;   // Bit 8
+pub const USART_BRR_DIV_MANTISSA_9  : u32   = 0x00002000;   // Bit 9
+pub const USART_BRR_DIV_MANTISSA_10 : u32   = 0x00004000;   // Bit 10
+pub const USART_BRR_DIV_MANTISSA_11 : u32   = 0x00008000;   // Bit 11
+
+// Bit definition for USART_CR1 register
+pub const USART_CR1_SBK    : u32   = 0x00000001;   // Send break
+pub const USART_CR1_RWU    : u32   = 0x00000002;   // Receiver wakeup
+pub const USART_CR1_RE     : u32   = 0x00000004;   // Receiver enable
+pub const USART_CR1_TE     : u32   = 0x00000008;   // Transmitter enable
+pub const USART_CR1_IDLEIE : u32   = 0x00000010;   // IDLE interrupt enable
+pub const USART_CR1_RXNEIE : u32   = 0x00000020;   // RXNE interrupt enable
+pub const USART_CR1_TCIE   : u32   = 0x00000040;   // Transmission complete interrupt enable
+pub const USART_CR1_TXEIE  : u32   = 0x00000080;   // TXE interrupt enable
+pub const USART_CR1_PEIE   : u32   = 0x00000100;   // PE interrupt enable
+pub const USART_CR1_PS     : u32   = 0x00000200;   // Parity selection
+pub const USART_CR1_PCE    : u32   = 0x00000400;   // Parity control enable
+pub const USART_CR1_WAKE   : u32   = 0x00000800;   // Wakeup method
+pub const USART_CR1_M      : u32   = 0x00001000;   // Word length
+pub const USART_CR1_UE     : u32   = 0x00002000;   // USART enable
+pub const USART_CR1_OVER8  : u32   = 0x00008000;   // Oversampling mode
+
+// Bit definition for USART_CR2 register
+pub const USART_CR2_ADD    : u32   = 0x0000000F;   // Address of the USART node
+pub const USART_CR2_ADD_0  : u32   = 0x00000001;   // Bit 0
+pub const USART_CR2_ADD_1  : u32   = 0x00000002;   // Bit 1
+pub const USART_CR2_ADD_2  : u32   = 0x00000004;   // Bit 2
+pub const USART_CR2_ADD_3  : u32   = 0x00000008;   // Bit 3
+pub const USART_CR2_LBDL   : u32   = 0x00000020;   // LIN break detection length
+pub const USART_CR2_LBDIE  : u32   = 0x00000040;   // LIN break detection interrupt enable
+pub const USART_CR2_LBCL   : u32   = 0x00000100;   // Last bit clock pulse
+pub const USART_CR2_CPHA   : u32   = 0x00000200;   // Clock phase
+pub const USART_CR2_CPOL   : u32   = 0x00000400;   // Clock polarity
+pub const USART_CR2_CLKEN  : u32   = 0x00000800;   // Clock enable
+pub const USART_CR2_STOP   : u32   = 0x00003000;   // STOP bits
+pub const USART_CR2_STOP_0 : u32   = 0x00001000;   // Bit 0
+pub const USART_CR2_STOP_1 : u32   = 0x00002000;   // Bit 1
+pub const USART_CR2_LINEN  : u32   = 0x00004000;   // LIN mode enable
+
+// Bit definition for USART_CR3 register
+pub const USART_CR3_EIE    : u32   = 0x00000001;   // Error interrupt enable
+pub const USART_CR3_IREN   : u32   = 0x00000002;   // IrDA mode enable
+pub const USART_CR3_IRLP   : u32   = 0x00000004;   // IrDA low-power
+pub const USART_CR3_HDSEL  : u32   = 0x00000008;   // Half-duplex selection
+pub const USART_CR3_NACK   : u32   = 0x00000010;   // Smartcard NACK enable
+pub const USART_CR3_SCEN   : u32   = 0x00000020;   // Smartcard mode enable
+pub const USART_CR3_DMAR   : u32   = 0x00000040;   // DMA enable receiver
+pub const USART_CR3_DMAT   : u32   = 0x00000080;   // DMA enable transmitter
+pub const USART_CR3_RTSE   : u32   = 0x00000100;   // RTS enable
+pub const USART_CR3_CTSE   : u32   = 0x00000200;   // CTS enable
+pub const USART_CR3_CTSIE  : u32   = 0x00000400;   // CTS interrupt enable
+pub const USART_CR3_ONEBIT : u32   = 0x00000800;   // One sample bit method enable
+
+// Bit definition for USART_GTPR register
+pub const USART_GTPR_PSC   : u32   = 0x000000FF;   // Prescaler value
+pub const USART_GTPR_PSC_0 : u32   = 0x00000001;   // Bit 0
+pub const USART_GTPR_PSC_1 : u32   = 0x00000002;   // Bit 1
+pub const USART_GTPR_PSC_2 : u32   = 0x00000004;   // Bit 2
+pub const USART_GTPR_PSC_3 : u32   = 0x00000008;   // Bit 3
+pub const USART_GTPR_PSC_4 : u32   = 0x00000010;   // Bit 4
+pub const USART_GTPR_PSC_5 : u32   = 0x00000020;   // Bit 5
+pub const USART_GTPR_PSC_6 : u32   = 0x00000040;   // Bit 6
+pub const USART_GTPR_PSC_7 : u32   = 0x00000080;   // Bit 7
+pub const USART_GTPR_GT    : u32   = 0x0000FF00;   // Guard time value
+pub const USART_GTPR_GT_0  : u32   = 0x00000100;   // Bit 0
+pub const USART_GTPR_GT_1  : u32   = 0x00000200;   // Bit 1
+pub const USART_GTPR_GT_2  : u32   = 0x00000400;   // Bit 2
+pub const USART_GTPR_GT_3  : u32   = 0x00000800;   // Bit 3
+pub const USART_GTPR_GT_4  : u32   = 0x00001000;   // Bit 4
+pub const USART_GTPR_GT_5  : u32   = 0x00002000;   // Bit 5
+pub const USART_GTPR_GT_6  : u32   = 0x00004000;   // Bit 6
+pub const USART_GTPR_GT_7  : u32   = 0x00008000;   // Bit 7
+
+/// Typed fields of the USART_SR register
+pub mod sr {
+    field_flag! { /// Parity error
+                  pub struct Pe [0]; }
+    field_flag! { /// Framing error
+                  pub struct Fe [1]; }
+    field_flag! { /// Noise detected flag
+                  pub struct Nf [2]; }
+    field_flag! { /// Overrun error
+                  pub struct Ore [3]; }
+    field_flag! { /// IDLE line detected
+                  pub struct Idle [4]; }
+    field_flag! { /// Read data register not empty
+                  pub struct Rxne [5]; }
+    field_flag! { /// Transmission complete
+                  pub struct Tc [6]; }
+    field_flag! { /// Transmit data register empty
+                  pub struct Txe [7]; }
+    field_flag! { /// LIN break detection flag
+                  pub struct Lbd [8]; }
+    field_flag! { /// CTS flag
+                  pub struct Cts [9]; }
+}
+
+/// Typed fields of the USART_DR register
+pub mod dr {
+    field_bits! { /// Data value
+                  pub struct Dr [0, 9]; }
+}
+
+/// Typed fields of the USART_BRR register
+pub mod brr {
+    field_bits! { /// Fraction of USARTDIV
+                  pub struct DivFraction [0, 4]; }
+    field_bits! { /// Mantissa of USARTDIV
+                  pub struct DivMantissa [4, 12]; }
+}
+
+/// Typed fields of the USART_CR1 register
+pub mod cr1 {
+    field_flag! { /// Send break
+                  pub struct Sbk [0]; }
+    field_flag! { /// Receiver wakeup
+                  pub struct Rwu [1]; }
+    field_flag! { /// Receiver enable
+                  pub struct Re [2]; }
+    field_flag! { /// Transmitter enable
+                  pub struct Te [3]; }
+    field_flag! { /// IDLE interrupt enable
+                  pub struct Idleie [4]; }
+    field_flag! { /// RXNE interrupt enable
+                  pub struct Rxneie [5]; }
+    field_flag! { /// Transmission complete interrupt enable
+                  pub struct Tcie [6]; }
+    field_flag! { /// TXE interrupt enable
+                  pub struct Txeie [7]; }
+    field_flag! { /// PE interrupt enable
+                  pub struct Peie [8]; }
+    field_flag! { /// Parity selection
+                  pub struct Ps [9]; }
+    field_flag! { /// Parity control enable
+                  pub struct Pce [10]; }
+    field_flag! { /// Wakeup method
+                  pub struct Wake [11]; }
+    field_flag! { /// Word length
+                  pub struct M [12]; }
+    field_flag! { /// USART enable
+                  pub struct Ue [13]; }
+    field_flag! { /// Oversampling mode
+                  pub struct Over8 [15]; }
+}
+
+/// Typed fields of the USART_CR2 register
+pub mod cr2 {
+    field_bits! { /// Address of the USART node
+                  pub struct Add [0, 4]; }
+    field_flag! { /// LIN break detection length
+                  pub struct Lbdl [5]; }
+    field_flag! { /// LIN break detection interrupt enable
+                  pub struct Lbdie [6]; }
+    field_flag! { /// Last bit clock pulse
+                  pub struct Lbcl [8]; }
+    field_flag! { /// Clock phase
+                  pub struct Cpha [9]; }
+    field_flag! { /// Clock polarity
+                  pub struct Cpol [10]; }
+    field_flag! { /// Clock enable
+                  pub struct Clken [11]; }
+    field_bits! { /// STOP bits
+                  pub struct Stop [12, 2]; }
+    field_flag! { /// LIN mode enable
+                  pub struct Linen [14]; }
+}
+
+/// Typed fields of the USART_CR3 register
+pub mod cr3 {
+    field_flag! { /// Error interrupt enable
+                  pub struct Eie [0]; }
+    field_flag! { /// IrDA mode enable
+                  pub struct Iren [1]; }
+    field_flag! { /// IrDA low-power
+                  pub struct Irlp [2]; }
+    field_flag! { /// Half-duplex selection
+                  pub struct Hdsel [3]; }
+    field_flag! { /// Smartcard NACK enable
+                  pub struct Nack [4]; }
+    field_flag! { /// Smartcard mode enable
+                  pub struct Scen [5]; }
+    field_flag! { /// DMA enable receiver
+                  pub struct Dmar [6]; }
+    field_flag! { /// DMA enable transmitter
+                  pub struct Dmat [7]; }
+    field_flag! { /// RTS enable
+                  pub struct Rtse [8]; }
+    field_flag! { /// CTS enable
+                  pub struct Ctse [9]; }
+    field_flag! { /// CTS interrupt enable
+                  pub struct Ctsie [10]; }
+    field_flag! { /// One sample bit method enable
+                  pub struct Onebit [11]; }
+}
+
+/// Typed fields of the USART_GTPR register
+pub mod gtpr {
+    field_bits! { /// Prescaler value
+                  pub struct Psc [0, 8]; }
+    field_bits! { /// Guard time value
+                  pub struct Gt [8, 8]; }
+}
@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+//! Operations common to the 8, 16 and 32-bit volatile registers, for code
+//! which does not care about the register width
+
+use ::volatile_reg8::VolatileReg8;
+use ::volatile_reg16::VolatileReg16;
+use ::volatile_reg32::VolatileReg32;
+
+pub trait VolatileReg {
+    /// Integer type as wide as the register
+    type Value: Copy;
+
+    fn address(&self) -> u32;
+    fn get(&self) -> Self::Value;
+    fn set(&self, value: Self::Value);
+    fn bit_or(&self, value: Self::Value);
+    fn bit_and(&self, value: Self::Value);
+    fn bit_xor(&self, value: Self::Value);
+}
+
+macro_rules! impl_volatile_reg {
+    ($reg:ident, $value:ty) => {
+        impl VolatileReg for $reg {
+            type Value = $value;
+
+            fn address(&self) -> u32 { $reg::address(self) }
+            fn get(&self) -> $value { $reg::get(self) }
+            fn set(&self, value: $value) { $reg::set(self, value) }
+            fn bit_or(&self, value: $value) { $reg::bit_or(self, value) }
+            fn bit_and(&self, value: $value) { $reg::bit_and(self, value) }
+            fn bit_xor(&self, value: $value) { $reg::bit_xor(self, value) }
+        }
+    }
+}
+
+impl_volatile_reg!(VolatileReg8, u8);
+impl_volatile_reg!(VolatileReg16, u16);
+impl_volatile_reg!(VolatileReg32, u32);
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+//! 16-bit wide volatile register
+
+use ::core_cm4::interrupt::free;
+#[cfg(not(test))]
+use core::intrinsics::volatile_store;
+#[cfg(not(test))]
+use core::intrinsics::volatile_load;
+
+pub struct VolatileReg16 {
+    value: *mut u16
+}
+
+impl VolatileReg16 {
+    pub fn new(value: *mut u16) -> VolatileReg16 {
+        VolatileReg16 {
+            value,
+        }
+    }
+
+    pub fn new_offset(value: *mut u16, offset: u32) -> VolatileReg16 {
+        VolatileReg16 {
+            value: value.wrapping_offset(offset as isize),
+        }
+    }
+
+    /// Address of the register
+    pub fn address(&self) -> u32 {
+        self.value as usize as u32
+    }
+
+    #[cfg(not(test))]
+    pub fn get(&self) -> u16 {
+        unsafe {
+            volatile_load(self.value)
+        }
+    }
+
+    #[cfg(not(test))]
+    pub fn set(&self, value: u16) {
+        unsafe {
+            volatile_store(self.value, value);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get(&self) -> u16 {
+        ::mock_regs::read_sized(self.address(), 2) as u16
+    }
+
+    #[cfg(test)]
+    pub fn set(&self, value: u16) {
+        ::mock_regs::write_sized(self.address(), 2, value as u32);
+    }
+
+    pub fn bit_or(&self, value: u16) {
+        free(|| {
+            let mut temp: u16 = self.get();
+            temp |= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn bit_and(&self, value: u16) {
+        free(|| {
+            let mut temp: u16 = self.get();
+            temp &= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn bit_xor(&self, value: u16) {
+        free(|| {
+            let mut temp: u16 = self.get();
+            temp ^= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn add(&self, value: u16) {
+        free(|| {
+            let mut temp: u16 = self.get();
+            temp += value;
+            self.set(temp);
+        })
+    }
+
+    pub fn sub(&self, value: u16) {
+        free(|| {
+            let mut temp: u16 = self.get();
+            temp -= value;
+            self.set(temp);
+        })
+    }
+}
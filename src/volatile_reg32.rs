@@ -1,8 +1,17 @@
 #![allow(dead_code)]
 
+use ::core_cm4::interrupt::free;
+#[cfg(not(test))]
 use core::intrinsics::volatile_store;
+#[cfg(not(test))]
 use core::intrinsics::volatile_load;
+use ::reg_field::{R, W};
 
+/// Read-write 32-bit register
+///
+/// The read-modify-write operations (`modify`, `bit_or`, `add`, ...) run with
+/// interrupts masked, so an interrupt handler changing the same register
+/// between the load and the store cannot lose its update.
 pub struct VolatileReg32 {
     value: *mut u32
 }
@@ -16,50 +25,171 @@ impl VolatileReg32 {
 
     pub fn new_offset(value: *mut u32, offset: u32) -> VolatileReg32 {
         VolatileReg32 {
-            value: unsafe { value.offset(offset as isize) },
+            value: value.wrapping_offset(offset as isize),
         }
     }
 
+    /// Address of the register
+    pub fn address(&self) -> u32 {
+        self.value as usize as u32
+    }
+
+    #[cfg(not(test))]
     pub fn get(&self) -> u32 {
         unsafe {
             volatile_load(self.value)
         }
     }
 
+    #[cfg(not(test))]
     pub fn set(&self, value: u32) {
         unsafe {
             volatile_store(self.value, value);
         }
     }
 
+    #[cfg(test)]
+    pub fn get(&self) -> u32 {
+        ::mock_regs::read(self.address())
+    }
+
+    #[cfg(test)]
+    pub fn set(&self, value: u32) {
+        ::mock_regs::write(self.address(), value);
+    }
+
     pub fn bit_or(&self, value: u32) {
-        let mut temp: u32 = self.get();
-        temp |= value;
-        self.set(temp);
+        free(|| {
+            let mut temp: u32 = self.get();
+            temp |= value;
+            self.set(temp);
+        })
     }
 
     pub fn bit_and(&self, value: u32) {
-        let mut temp: u32 = self.get();
-        temp &= value;
-        self.set(temp);
+        free(|| {
+            let mut temp: u32 = self.get();
+            temp &= value;
+            self.set(temp);
+        })
     }
 
     pub fn bit_xor(&self, value: u32) {
-        let mut temp: u32 = self.get();
-        temp ^= value;
-        self.set(temp);
+        free(|| {
+            let mut temp: u32 = self.get();
+            temp ^= value;
+            self.set(temp);
+        })
     }
 
     pub fn add(&self, value: u32) {
-        let mut temp: u32 = self.get();
-        temp += value;
-        self.set(temp);
+        free(|| {
+            let mut temp: u32 = self.get();
+            temp += value;
+            self.set(temp);
+        })
     }
 
     pub fn sub(&self, value: u32) {
-        let mut temp: u32 = self.get();
-        temp -= value;
-        self.set(temp);
+        free(|| {
+            let mut temp: u32 = self.get();
+            temp -= value;
+            self.set(temp);
+        })
+    }
+
+    /// Returns a snapshot of the register for reading fields
+    pub fn read(&self) -> R {
+        R::new(self.get())
+    }
+
+    /// Writes the fields set by `f`, all other bits are written as zero
+    pub fn write<F>(&self, f: F)
+        where F: FnOnce(&mut W) -> &mut W
+    {
+        let mut w = W::new(0);
+        f(&mut w);
+        self.set(w.bits());
     }
+
+    /// Read-modify-write: only the fields set by `f` are changed
+    pub fn modify<F>(&self, f: F)
+        where F: for<'w> FnOnce(&R, &'w mut W) -> &'w mut W
+    {
+        free(|| {
+            let r = R::new(self.get());
+            let mut w = W::new(r.bits());
+            f(&r, &mut w);
+            self.set(w.bits());
+        })
+    }
+}
+
+/// Read-only 32-bit register, e.g. an identification or input data register
+pub struct ReadOnlyReg32 {
+    reg: VolatileReg32
 }
 
+impl ReadOnlyReg32 {
+    pub fn new(value: *mut u32) -> ReadOnlyReg32 {
+        ReadOnlyReg32 {
+            reg: VolatileReg32::new(value),
+        }
+    }
+
+    pub fn new_offset(value: *mut u32, offset: u32) -> ReadOnlyReg32 {
+        ReadOnlyReg32 {
+            reg: VolatileReg32::new_offset(value, offset),
+        }
+    }
+
+    /// Address of the register
+    pub fn address(&self) -> u32 {
+        self.reg.address()
+    }
+
+    pub fn get(&self) -> u32 {
+        self.reg.get()
+    }
+
+    /// Returns a snapshot of the register for reading fields
+    pub fn read(&self) -> R {
+        self.reg.read()
+    }
+}
+
+/// Write-only 32-bit register, e.g. a set/reset or key register, which has
+/// no meaningful value to read back and so cannot be read-modify-written
+pub struct WriteOnlyReg32 {
+    reg: VolatileReg32
+}
+
+impl WriteOnlyReg32 {
+    pub fn new(value: *mut u32) -> WriteOnlyReg32 {
+        WriteOnlyReg32 {
+            reg: VolatileReg32::new(value),
+        }
+    }
+
+    pub fn new_offset(value: *mut u32, offset: u32) -> WriteOnlyReg32 {
+        WriteOnlyReg32 {
+            reg: VolatileReg32::new_offset(value, offset),
+        }
+    }
+
+    /// Address of the register
+    pub fn address(&self) -> u32 {
+        self.reg.address()
+    }
+
+    pub fn set(&self, value: u32) {
+        self.reg.set(value)
+    }
+
+    /// Writes the fields set by `f`, all other bits are written as zero
+    pub fn write<F>(&self, f: F)
+        where F: FnOnce(&mut W) -> &mut W
+    {
+        self.reg.write(f)
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+//! 8-bit wide volatile register
+
+use ::core_cm4::interrupt::free;
+#[cfg(not(test))]
+use core::intrinsics::volatile_store;
+#[cfg(not(test))]
+use core::intrinsics::volatile_load;
+
+pub struct VolatileReg8 {
+    value: *mut u8
+}
+
+impl VolatileReg8 {
+    pub fn new(value: *mut u8) -> VolatileReg8 {
+        VolatileReg8 {
+            value,
+        }
+    }
+
+    pub fn new_offset(value: *mut u8, offset: u32) -> VolatileReg8 {
+        VolatileReg8 {
+            value: value.wrapping_offset(offset as isize),
+        }
+    }
+
+    /// Address of the register
+    pub fn address(&self) -> u32 {
+        self.value as usize as u32
+    }
+
+    #[cfg(not(test))]
+    pub fn get(&self) -> u8 {
+        unsafe {
+            volatile_load(self.value)
+        }
+    }
+
+    #[cfg(not(test))]
+    pub fn set(&self, value: u8) {
+        unsafe {
+            volatile_store(self.value, value);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get(&self) -> u8 {
+        ::mock_regs::read_sized(self.address(), 1) as u8
+    }
+
+    #[cfg(test)]
+    pub fn set(&self, value: u8) {
+        ::mock_regs::write_sized(self.address(), 1, value as u32);
+    }
+
+    pub fn bit_or(&self, value: u8) {
+        free(|| {
+            let mut temp: u8 = self.get();
+            temp |= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn bit_and(&self, value: u8) {
+        free(|| {
+            let mut temp: u8 = self.get();
+            temp &= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn bit_xor(&self, value: u8) {
+        free(|| {
+            let mut temp: u8 = self.get();
+            temp ^= value;
+            self.set(temp);
+        })
+    }
+
+    pub fn add(&self, value: u8) {
+        free(|| {
+            let mut temp: u8 = self.get();
+            temp += value;
+            self.set(temp);
+        })
+    }
+
+    pub fn sub(&self, value: u8) {
+        free(|| {
+            let mut temp: u8 = self.get();
+            temp -= value;
+            self.set(temp);
+        })
+    }
+}
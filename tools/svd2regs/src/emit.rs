@@ -0,0 +1,364 @@
+//! Writes the register modules in the style of the hand-written ones under
+//! `src/stm32f4xx/regs`: base addresses, a register block struct with an
+//! `init` constructor, bit mask constants and typed field modules.
+//!
+//! All peripherals of one SVD group go into one module. Peripherals sharing
+//! a register layout share one struct, selected by an `Inst` enum when there
+//! is more than one of them (like `SpiRegs`/`SpiInst`). When a group has
+//! several layouts (e.g. advanced and basic timers), every layout is named
+//! after its first peripheral (`Tim1Regs`, `Tim6Regs`).
+
+use std::fmt::Write;
+
+use svd::{Access, Device, Peripheral, Register};
+
+const KEYWORDS: &[&str] = &[
+    "as", "box", "break", "const", "continue", "crate", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+    "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+/// One register block struct and the peripherals using it
+struct Layout<'a> {
+    /// Name the struct, constants and field modules are derived from
+    name        : String,
+    instances   : Vec<&'a Peripheral>,
+}
+
+/// Upper camel case of an SVD name: `USART_CR1` -> `UsartCr1`
+pub fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mut chars = s.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            let rest: String = chars.as_str().to_lowercase();
+            format!("{}{}", first, rest)
+        })
+        .collect()
+}
+
+/// Field name of a register in the block struct
+fn register_ident(layout: &str, register: &Register) -> String {
+    let name = register.name.to_lowercase();
+    if KEYWORDS.contains(&&name[..]) {
+        format!("{}_{}", layout.to_lowercase(), name)
+    } else {
+        name
+    }
+}
+
+fn same_layout(a: &Peripheral, b: &Peripheral) -> bool {
+    a.registers.len() == b.registers.len() &&
+        a.registers.iter().zip(b.registers.iter())
+                   .all(|(x, y)| x.name == y.name && x.offset == y.offset)
+}
+
+fn layouts<'a>(group: &str, peripherals: &[&'a Peripheral]) -> Vec<Layout<'a>> {
+    let mut layouts: Vec<Layout<'a>> = Vec::new();
+
+    for &p in peripherals {
+        match layouts.iter_mut().find(|l| same_layout(l.instances[0], p)) {
+            Some(layout) => layout.instances.push(p),
+            None => layouts.push(Layout { name: p.name.clone(), instances: vec![p] }),
+        }
+    }
+
+    if layouts.len() == 1 {
+        layouts[0].name = group.to_string();
+    }
+    layouts
+}
+
+/// Names of the groups of the device, in SVD order
+pub fn groups(device: &Device) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for p in &device.peripherals {
+        if !groups.contains(&p.group) {
+            groups.push(p.group.clone());
+        }
+    }
+    groups
+}
+
+/// Source of the register module of `group`
+pub fn module(device: &Device, svd_name: &str, group: &str) -> String {
+    let peripherals: Vec<&Peripheral> =
+        device.peripherals.iter().filter(|p| p.group == group).collect();
+    let layouts = layouts(group, &peripherals);
+    let mut out = String::new();
+
+    let title = match peripherals[0].description.trim_end_matches('.') {
+        "" => group.to_string(),
+        d => d.to_string(),
+    };
+    writeln!(out, "#![allow(dead_code)]\n").unwrap();
+    writeln!(out, "//! {} registers", title).unwrap();
+    writeln!(out, "//!").unwrap();
+    writeln!(out, "//! Generated by tools/svd2regs from {}, do not edit.\n", svd_name).unwrap();
+    let sizes: Vec<u32> = peripherals.iter()
+                                     .flat_map(|p| p.registers.iter())
+                                     .map(module_width)
+                                     .collect();
+    for width in &[8, 16, 32] {
+        if sizes.contains(width) {
+            writeln!(out, "use ::volatile_reg{}::*;", width).unwrap();
+        }
+    }
+    writeln!(out).unwrap();
+
+    let bases: Vec<(String, u32)> =
+        peripherals.iter().map(|p| (format!("{}_BASE", p.name), p.base)).collect();
+    let width = bases.iter().map(|b| b.0.len()).max().unwrap_or(0);
+    for &(ref name, base) in &bases {
+        writeln!(out, "pub const {:w$} : u32   = 0x{:08X};", name, base, w = width).unwrap();
+    }
+
+    for layout in &layouts {
+        block(&mut out, layout);
+    }
+    for layout in &layouts {
+        constants(&mut out, layout);
+    }
+    for layout in &layouts {
+        if layouts.len() == 1 {
+            fields(&mut out, layout, "");
+        } else {
+            writeln!(out, "\n/// Typed fields of the {} registers", layout.name).unwrap();
+            writeln!(out, "pub mod {} {{", layout.name.to_lowercase()).unwrap();
+            fields(&mut out, layout, "    ");
+            writeln!(out, "}}").unwrap();
+        }
+    }
+
+    out
+}
+
+/// Width of the `volatile_reg` module the register type comes from
+fn module_width(register: &Register) -> u32 {
+    match (register.access, register.size) {
+        (Access::ReadWrite, 8) => 8,
+        (Access::ReadWrite, 16) => 16,
+        _ => 32,
+    }
+}
+
+/// `svd::device` lets read-only and write-only registers through only when
+/// they are 32 bits wide
+fn register_type(register: &Register) -> &'static str {
+    match (register.access, register.size) {
+        (Access::ReadOnly, _) => "ReadOnlyReg32",
+        (Access::WriteOnly, _) => "WriteOnlyReg32",
+        (Access::ReadWrite, 8) => "VolatileReg8",
+        (Access::ReadWrite, 16) => "VolatileReg16",
+        (Access::ReadWrite, _) => "VolatileReg32",
+    }
+}
+
+fn block(out: &mut String, layout: &Layout) {
+    let camel = camel_case(&layout.name);
+    let lower = layout.name.to_lowercase();
+    let regs = &layout.instances[0].registers;
+    let width = regs.iter().map(|r| register_ident(&layout.name, r).len()).max().unwrap_or(0);
+
+    writeln!(out, "\n\npub struct {}Regs\n{{", camel).unwrap();
+    for (i, r) in regs.iter().enumerate() {
+        if i != 0 {
+            writeln!(out).unwrap();
+        }
+        if !r.description.is_empty() {
+            writeln!(out, "    /// {}", r.description).unwrap();
+        }
+        writeln!(out, "    pub {:w$} : {},", register_ident(&layout.name, r), register_type(r),
+                 w = width).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    let multiple = layout.instances.len() > 1;
+    if multiple {
+        writeln!(out, "\npub enum {}Inst {{", camel).unwrap();
+        for p in &layout.instances {
+            writeln!(out, "    {},", camel_case(&p.name)).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    writeln!(out, "\nimpl {}Regs {{", camel).unwrap();
+    writeln!(out, "    /// The caller must make sure the block is not accessed through another").unwrap();
+    writeln!(out, "    /// instance, drivers get it from `Peripherals::take()` instead").unwrap();
+    if multiple {
+        writeln!(out, "    pub unsafe fn init(inst: {}Inst) -> {}Regs {{", camel, camel).unwrap();
+        writeln!(out, "        let {}_base: *mut u32 = match inst {{", lower).unwrap();
+        let w = layout.instances.iter().map(|p| p.name.len()).max().unwrap_or(0) + camel.len() + 6;
+        for p in &layout.instances {
+            let arm = format!("{}Inst::{}", camel, camel_case(&p.name));
+            writeln!(out, "            {:w$} => {}_BASE,", arm, p.name, w = w).unwrap();
+        }
+        writeln!(out, "        }} as *mut u32;\n").unwrap();
+    } else {
+        writeln!(out, "    pub unsafe fn init() -> {}Regs {{", camel).unwrap();
+        writeln!(out, "        let {}_base: *mut u32 = {}_BASE as *mut u32;\n",
+                 lower, layout.instances[0].name).unwrap();
+    }
+
+    // The block itself is the return value, clippy flags a `let` binding
+    writeln!(out, "        {}Regs {{", camel).unwrap();
+    for r in regs {
+        let ty = register_type(r);
+        let ctor = match r.size {
+            8 | 16 if r.access == Access::ReadWrite =>
+                format!("{}::new(({}_base as *mut u8).wrapping_offset(0x{:02X}) as *mut u{})",
+                        ty, lower, r.offset, r.size),
+            _ if r.offset == 0 => format!("{}::new({}_base)", ty, lower),
+            // Word index, 32 bit registers are word aligned
+            _ => format!("{}::new_offset({}_base, {})", ty, lower, r.offset / 4),
+        };
+        writeln!(out, "            {:w$} : {},", register_ident(&layout.name, r), ctor,
+                 w = width).unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn constants(out: &mut String, layout: &Layout) {
+    for r in &layout.instances[0].registers {
+        if r.fields.is_empty() {
+            continue;
+        }
+
+        // SVD names are mixed case at times, e.g. DIV_Mantissa in USART_BRR
+        let prefix = format!("{}_{}", layout.name, r.name).to_uppercase();
+        let mut lines: Vec<(String, u32, String)> = Vec::new();
+        for f in &r.fields {
+            let mask = (!0u32 >> (32 - f.width)) << f.offset;
+            let name = format!("{}_{}", prefix, f.name.to_uppercase());
+            lines.push((name.clone(), mask, f.description.clone()));
+            if f.width > 1 {
+                for bit in 0..f.width {
+                    lines.push((format!("{}_{}", name, bit), 1 << (f.offset + bit),
+                                format!("Bit {}", bit)));
+                }
+            }
+        }
+
+        let width = lines.iter().map(|l| l.0.len()).max().unwrap_or(0);
+        writeln!(out, "\n// Bit definition for {} register", prefix).unwrap();
+        for (name, mask, description) in lines {
+            if description.is_empty() {
+                writeln!(out, "pub const {:w$} : u32   = 0x{:08X};", name, mask, w = width).unwrap();
+            } else {
+                writeln!(out, "pub const {:w$} : u32   = 0x{:08X};   // {}",
+                         name, mask, description, w = width).unwrap();
+            }
+        }
+    }
+}
+
+fn fields(out: &mut String, layout: &Layout, indent: &str) {
+    for r in &layout.instances[0].registers {
+        if r.fields.is_empty() {
+            continue;
+        }
+
+        writeln!(out, "\n{}/// Typed fields of the {}_{} register", indent, layout.name, r.name).unwrap();
+        writeln!(out, "{}pub mod {} {{", indent, register_ident(&layout.name, r)).unwrap();
+        let mut seen: Vec<String> = Vec::new();
+        for f in &r.fields {
+            let name = camel_case(&f.name);
+            if seen.contains(&name) {
+                continue;
+            }
+            let (kind, range) = if f.width == 1 {
+                ("field_flag!", format!("[{}]", f.offset))
+            } else {
+                ("field_bits!", format!("[{}, {}]", f.offset, f.width))
+            };
+            if f.description.is_empty() {
+                writeln!(out, "{}    {} {{ pub struct {} {}; }}", indent, kind, name, range).unwrap();
+            } else {
+                writeln!(out, "{}    {} {{ /// {}", indent, kind, f.description).unwrap();
+                writeln!(out, "{}                  pub struct {} {}; }}", indent, name, range).unwrap();
+            }
+            seen.push(name);
+        }
+        writeln!(out, "{}}}", indent).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svd;
+    use xml;
+
+    const SVD: &str = "
+        <device><name>TEST</name><size>32</size>
+          <peripherals>
+            <peripheral>
+              <name>USART1</name><groupName>USART</groupName>
+              <description>Universal synchronous asynchronous receiver transmitter</description>
+              <baseAddress>0x40011000</baseAddress>
+              <registers>
+                <register><name>SR</name><description>Status register</description>
+                  <addressOffset>0x0</addressOffset><access>read-only</access>
+                  <fields>
+                    <field><name>PE</name><description>Parity error</description>
+                      <bitOffset>0</bitOffset><bitWidth>1</bitWidth></field>
+                  </fields>
+                </register>
+                <register><name>DR</name><addressOffset>0x4</addressOffset>
+                  <fields><field><name>DR</name><bitOffset>0</bitOffset><bitWidth>2</bitWidth></field></fields>
+                </register>
+                <register><name>BRR</name><addressOffset>0x8</addressOffset>
+                  <fields><field><name>DIV_Mantissa</name><bitOffset>4</bitOffset><bitWidth>1</bitWidth></field></fields>
+                </register>
+              </registers>
+            </peripheral>
+            <peripheral derivedFrom=\"USART1\"><name>USART2</name>
+              <baseAddress>0x40004400</baseAddress></peripheral>
+            <peripheral><name>TIM6</name><baseAddress>0x40001000</baseAddress>
+              <registers>
+                <register><name>CR1</name><addressOffset>0x0</addressOffset></register>
+              </registers>
+            </peripheral>
+          </peripherals>
+        </device>";
+
+    fn device() -> Device {
+        svd::device(&xml::parse(SVD).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(camel_case("USART_CR1"), "UsartCr1");
+        assert_eq!(camel_case("TXEIE"), "Txeie");
+        assert_eq!(groups(&device()), ["USART", "TIM"]);
+    }
+
+    #[test]
+    fn shared_layout_gets_an_instance_enum() {
+        let module = module(&device(), "test.svd", "USART");
+
+        assert!(module.contains("pub const USART2_BASE : u32   = 0x40004400;"));
+        assert!(module.contains("    pub sr  : ReadOnlyReg32,"));
+        assert!(module.contains("pub enum UsartInst {\n    Usart1,\n    Usart2,\n}"));
+        assert!(module.contains("            UsartInst::Usart2 => USART2_BASE,"));
+        assert!(module.contains("            dr  : VolatileReg32::new_offset(usart_base, 1),"));
+        assert!(module.contains("pub const USART_SR_PE : u32   = 0x00000001;   // Parity error"));
+        assert!(module.contains("pub const USART_DR_DR_1 : u32   = 0x00000002;   // Bit 1"));
+        assert!(module.contains("    field_flag! { /// Parity error\n                  pub struct Pe [0]; }"));
+        assert!(module.contains("    field_bits! { pub struct Dr [0, 2]; }"));
+        assert!(module.contains("pub const USART_BRR_DIV_MANTISSA : u32   = 0x00000010;"));
+        assert!(module.contains("    field_flag! { pub struct DivMantissa [4]; }"));
+    }
+
+    #[test]
+    fn single_instance_has_plain_init() {
+        let module = module(&device(), "test.svd", "TIM");
+
+        assert!(module.contains("pub unsafe fn init() -> TimRegs {"));
+        assert!(module.contains("let tim_base: *mut u32 = TIM6_BASE as *mut u32;"));
+    }
+}
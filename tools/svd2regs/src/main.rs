@@ -0,0 +1,110 @@
+//! Generates `stm32f4xx::regs` modules from a CMSIS SVD file
+//!
+//! svd2regs <device.svd> <output dir> [--force] [GROUP...]
+//!
+//! Writes one `<group>.rs` per peripheral group (all groups when none are
+//! given) and prints the `pub mod` lines to add to `regs/mod.rs`. Existing
+//! files are kept unless `--force` is given, so the hand-written modules are
+//! not overwritten by accident.
+
+mod emit;
+mod svd;
+mod xml;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process;
+
+fn run(args: &[String]) -> Result<(), String> {
+    let force = args.iter().any(|a| a == "--force");
+    let args: Vec<&String> = args.iter().filter(|a| *a != "--force").collect();
+    if args.len() < 2 {
+        return Err("usage: svd2regs <device.svd> <output dir> [--force] [GROUP...]".to_string());
+    }
+
+    let svd_path = Path::new(args[0]);
+    let out_dir = Path::new(args[1]);
+
+    let mut text = String::new();
+    File::open(svd_path).and_then(|mut f| f.read_to_string(&mut text))
+                        .map_err(|e| format!("{}: {}", svd_path.display(), e))?;
+    let root = xml::parse(&text).map_err(|e| format!("{}: {}", svd_path.display(), e))?;
+    let device = svd::device(&root)?;
+
+    let all = emit::groups(&device);
+    let groups: Vec<String> = if args.len() > 2 {
+        args[2..].iter().map(|g| g.to_uppercase()).collect()
+    } else {
+        all.clone()
+    };
+
+    let svd_name = svd_path.file_name().map(|n| n.to_string_lossy().into_owned())
+                           .unwrap_or(device.name.clone());
+
+    for group in &groups {
+        if !all.contains(group) {
+            return Err(format!("{} has no peripheral group {}", device.name, group));
+        }
+
+        let module = group.to_lowercase();
+        let path = out_dir.join(format!("{}.rs", module));
+        if path.exists() && !force {
+            eprintln!("{} exists, skipped", path.display());
+            continue;
+        }
+
+        File::create(&path)
+            .and_then(|mut f| f.write_all(emit::module(&device, &svd_name, group).as_bytes()))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("pub mod {};", module);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(e) = run(&args) {
+        eprintln!("svd2regs: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The device description the checked-in modules are generated from
+    const SVD: &str = include_str!("../../../svd/STM32F40x.svd");
+
+    fn device() -> svd::Device {
+        svd::device(&xml::parse(SVD).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn checked_in_svd_is_read() {
+        let device = device();
+
+        assert_eq!(device.name, "STM32F40x");
+        assert_eq!(emit::groups(&device), ["USART", "FSMC"]);
+        let uart4 = device.peripherals.iter().find(|p| p.name == "UART4").unwrap();
+        assert_eq!((uart4.base, uart4.registers.len()), (0x40004C00, 7));
+    }
+
+    #[test]
+    fn usart_module_is_up_to_date() {
+        assert!(emit::module(&device(), "STM32F40x.svd", "USART") ==
+                include_str!("../../../src/stm32f4xx/regs/usart.rs"),
+                "regenerate src/stm32f4xx/regs/usart.rs with `cargo regs`");
+    }
+
+    #[test]
+    fn fsmc_module_is_up_to_date() {
+        assert!(emit::module(&device(), "STM32F40x.svd", "FSMC") ==
+                include_str!("../../../src/stm32f4xx/regs/fsmc.rs"),
+                "regenerate src/stm32f4xx/regs/fsmc.rs with `cargo regs`");
+    }
+}
@@ -0,0 +1,332 @@
+//! Device model read from a CMSIS SVD file
+//!
+//! Only what the register modules need is kept: peripherals with their base
+//! address, registers with offset, size and access, and bit fields.
+//! `derivedFrom` peripherals get a copy of the registers of their base and
+//! `dim` registers are expanded into one register per index.
+//!
+//! Registers the `volatile_reg` modules have no type for are rejected:
+//! read-only and write-only registers must be 32 bits wide, and every
+//! register must be aligned to its size.
+
+use xml::Element;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Access {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name        : String,
+    pub description : String,
+    pub offset      : u32,
+    pub width       : u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Register {
+    pub name        : String,
+    pub description : String,
+    pub offset      : u32,
+    pub size        : u32,
+    pub access      : Access,
+    pub fields      : Vec<Field>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Peripheral {
+    pub name        : String,
+    pub group       : String,
+    pub description : String,
+    pub base        : u32,
+    pub registers   : Vec<Register>,
+}
+
+pub struct Device {
+    pub name        : String,
+    pub peripherals : Vec<Peripheral>,
+}
+
+/// Register properties inherited from the device and peripheral levels
+#[derive(Clone, Copy)]
+struct Defaults {
+    size    : u32,
+    access  : Access,
+}
+
+/// Parses an SVD number: decimal, `0x` hex or `#` binary
+pub fn parse_number(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    let result = if let Some(hex) = text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16)
+    } else if let Some(binary) = text.strip_prefix('#') {
+        u32::from_str_radix(binary, 2)
+    } else {
+        text.parse::<u32>()
+    };
+    result.map_err(|_| format!("`{}` is not a number", text))
+}
+
+fn number(element: &Element, name: &str) -> Result<Option<u32>, String> {
+    match element.child_text(name) {
+        Some(text) => parse_number(text).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn required(element: &Element, name: &str) -> Result<String, String> {
+    element.child_text(name)
+           .map(|t| t.to_string())
+           .ok_or(format!("<{}> without <{}>", element.name, name))
+}
+
+/// Collapses the line breaks and indentation SVD descriptions are full of
+fn description(element: &Element) -> String {
+    element.child_text("description")
+           .unwrap_or("")
+           .split_whitespace()
+           .collect::<Vec<_>>()
+           .join(" ")
+}
+
+fn access(element: &Element, default: Access) -> Access {
+    match element.child_text("access") {
+        Some("read-only") => Access::ReadOnly,
+        Some("write-only") | Some("writeOnce") => Access::WriteOnly,
+        Some(_) => Access::ReadWrite,
+        None => default,
+    }
+}
+
+fn defaults(element: &Element, inherited: Defaults) -> Result<Defaults, String> {
+    Ok(Defaults {
+        size    : number(element, "size")?.unwrap_or(inherited.size),
+        access  : access(element, inherited.access),
+    })
+}
+
+fn field(element: &Element) -> Result<Field, String> {
+    let (offset, width) =
+        if let Some(range) = element.child_text("bitRange") {
+            // [msb:lsb]
+            let range = range.trim_matches(|c| c == '[' || c == ']');
+            let mut parts = range.split(':');
+            let msb = parse_number(parts.next().unwrap_or(""))?;
+            let lsb = parse_number(parts.next().unwrap_or(""))?;
+            (lsb, msb - lsb + 1)
+        } else if let (Some(lsb), Some(msb)) = (number(element, "lsb")?, number(element, "msb")?) {
+            (lsb, msb - lsb + 1)
+        } else {
+            (number(element, "bitOffset")?.unwrap_or(0), number(element, "bitWidth")?.unwrap_or(1))
+        };
+
+    Ok(Field {
+        name        : required(element, "name")?,
+        description : description(element),
+        offset,
+        width,
+    })
+}
+
+fn registers(element: &Element, defaults: Defaults, out: &mut Vec<Register>) -> Result<(), String> {
+    let registers = match element.child("registers") {
+        Some(registers) => registers,
+        None => return Ok(()),
+    };
+
+    for reg in registers.children("register") {
+        let defaults = self::defaults(reg, defaults)?;
+        let mut fields = Vec::new();
+        if let Some(list) = reg.child("fields") {
+            for f in list.children("field") {
+                fields.push(field(f)?);
+            }
+        }
+        fields.sort_by_key(|f| f.offset);
+
+        let register = Register {
+            name        : required(reg, "name")?,
+            description : description(reg),
+            offset      : number(reg, "addressOffset")?
+                              .ok_or("register without <addressOffset>")?,
+            size        : defaults.size,
+            access      : defaults.access,
+            fields,
+        };
+
+        match number(reg, "dim")? {
+            None => {
+                supported(&register)?;
+                out.push(register);
+            },
+            Some(dim) => {
+                let increment = number(reg, "dimIncrement")?.unwrap_or(register.size / 8);
+                let indices: Vec<String> = match reg.child_text("dimIndex") {
+                    Some(list) if list.contains(',') =>
+                        list.split(',').map(|s| s.trim().to_string()).collect(),
+                    _ => (0..dim).map(|i| i.to_string()).collect(),
+                };
+                for (i, index) in indices.iter().enumerate() {
+                    let mut r = register.clone();
+                    r.name = register.name.replace("[%s]", index).replace("%s", index);
+                    r.offset += i as u32 * increment;
+                    supported(&r)?;
+                    out.push(r);
+                }
+            },
+        }
+    }
+
+    out.sort_by_key(|r| r.offset);
+    Ok(())
+}
+
+/// Fails for registers the emitted modules cannot map exactly
+fn supported(register: &Register) -> Result<(), String> {
+    match (register.size, register.access) {
+        (32, _) | (8, Access::ReadWrite) | (16, Access::ReadWrite) => {},
+        (size, access) =>
+            return Err(format!("{}: no register type for {} bit {:?} registers",
+                               register.name, size, access)),
+    }
+    if !register.offset.is_multiple_of(register.size / 8) {
+        return Err(format!("{}: offset 0x{:X} is not aligned to {} bits",
+                           register.name, register.offset, register.size));
+    }
+    Ok(())
+}
+
+/// Reads the device description from the root `<device>` element
+pub fn device(root: &Element) -> Result<Device, String> {
+    let defaults = defaults(root, Defaults { size: 32, access: Access::ReadWrite })?;
+    let list = root.child("peripherals").ok_or("<device> without <peripherals>")?;
+    let mut peripherals: Vec<Peripheral> = Vec::new();
+
+    for p in list.children("peripheral") {
+        let name = required(p, "name")?;
+        let base = match p.attr("derivedFrom") {
+            Some(from) => Some(peripherals.iter().find(|b| b.name == from).cloned()
+                                   .ok_or(format!("{} derived from unknown {}", name, from))?),
+            None => None,
+        };
+
+        let mut regs = Vec::new();
+        registers(p, self::defaults(p, defaults)?, &mut regs)?;
+        if regs.is_empty() {
+            if let Some(ref base) = base {
+                regs = base.registers.clone();
+            }
+        }
+
+        let group = match p.child_text("groupName") {
+            Some(group) => group.to_string(),
+            None => match base {
+                Some(ref base) => base.group.clone(),
+                None => name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string(),
+            },
+        };
+        let description = match (description(p), &base) {
+            (ref d, Some(base)) if d.is_empty() => base.description.clone(),
+            (d, _) => d,
+        };
+
+        peripherals.push(Peripheral {
+            base        : number(p, "baseAddress")?.ok_or(format!("{} without <baseAddress>", name))?,
+            name,
+            group,
+            description,
+            registers   : regs,
+        });
+    }
+
+    Ok(Device {
+        name        : required(root, "name")?,
+        peripherals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xml;
+
+    const SVD: &str = "
+        <device><name>TEST</name><size>32</size><access>read-write</access>
+          <peripherals>
+            <peripheral>
+              <name>USART1</name><groupName>USART</groupName>
+              <description>Universal synchronous asynchronous receiver transmitter</description>
+              <baseAddress>0x40011000</baseAddress>
+              <registers>
+                <register><name>DR</name><addressOffset>0x4</addressOffset>
+                  <fields><field><name>DR</name><bitOffset>0</bitOffset><bitWidth>9</bitWidth></field></fields>
+                </register>
+                <register><name>SR</name><addressOffset>0x0</addressOffset><access>read-only</access>
+                  <fields>
+                    <field><name>TXE</name><bitRange>[7:7]</bitRange></field>
+                    <field><name>PE</name><lsb>0</lsb><msb>0</msb></field>
+                  </fields>
+                </register>
+                <register><name>BUF%s</name><dim>2</dim><dimIncrement>4</dimIncrement>
+                  <addressOffset>0x8</addressOffset></register>
+              </registers>
+            </peripheral>
+            <peripheral derivedFrom=\"USART1\"><name>USART2</name>
+              <baseAddress>0x40004400</baseAddress></peripheral>
+          </peripherals>
+        </device>";
+
+    #[test]
+    fn reads_registers_and_fields() {
+        let device = device(&xml::parse(SVD).unwrap()).unwrap();
+        let usart1 = &device.peripherals[0];
+
+        assert_eq!(usart1.base, 0x40011000);
+        let names: Vec<&str> = usart1.registers.iter().map(|r| &r.name[..]).collect();
+        assert_eq!(names, ["SR", "DR", "BUF0", "BUF1"]);
+        assert_eq!(usart1.registers[3].offset, 0xC);
+        assert_eq!(usart1.registers[0].access, Access::ReadOnly);
+        assert_eq!(usart1.registers[0].fields[0].name, "PE");
+        assert_eq!((usart1.registers[0].fields[1].offset, usart1.registers[0].fields[1].width), (7, 1));
+        assert_eq!(usart1.registers[1].fields[0].width, 9);
+    }
+
+    #[test]
+    fn derived_peripheral_copies_registers() {
+        let device = device(&xml::parse(SVD).unwrap()).unwrap();
+        let usart2 = &device.peripherals[1];
+
+        assert_eq!(usart2.group, "USART");
+        assert_eq!(usart2.base, 0x40004400);
+        assert_eq!(usart2.registers.len(), 4);
+    }
+
+    #[test]
+    fn unsupported_registers_are_rejected() {
+        let svd = |register: &str| format!(
+            "<device><name>TEST</name><peripherals><peripheral><name>CRC</name>
+               <baseAddress>0x40023000</baseAddress><registers>{}</registers>
+             </peripheral></peripherals></device>", register);
+
+        let narrow = svd("<register><name>IDR</name><addressOffset>0x4</addressOffset>
+                            <size>16</size><access>read-only</access></register>");
+        let unaligned = svd("<register><name>DR</name><addressOffset>0x2</addressOffset></register>");
+        let byte = svd("<register><name>IDR</name><addressOffset>0x5</addressOffset>
+                          <size>8</size></register>");
+
+        assert!(device(&xml::parse(&narrow).unwrap()).err().unwrap().contains("16 bit ReadOnly"));
+        assert!(device(&xml::parse(&unaligned).unwrap()).err().unwrap().contains("not aligned"));
+        assert!(device(&xml::parse(&byte).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_number("0x1F"), Ok(31));
+        assert_eq!(parse_number("#101"), Ok(5));
+        assert_eq!(parse_number(" 12 "), Ok(12));
+        assert!(parse_number("x").is_err());
+    }
+}
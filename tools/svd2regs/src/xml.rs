@@ -0,0 +1,207 @@
+//! Minimal XML reader, enough for CMSIS SVD files: elements, attributes,
+//! text, comments and the predefined entities. No DTDs, no namespaces.
+
+pub struct Element {
+    pub name        : String,
+    pub attrs       : Vec<(String, String)>,
+    pub children    : Vec<Element>,
+    pub text        : String,
+}
+
+impl Element {
+    /// First child element called `name`
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// All child elements called `name`
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Trimmed text of the child element called `name`
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim())
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|a| a.0 == name).map(|a| &a.1[..])
+    }
+}
+
+struct Parser<'a> {
+    src : &'a str,
+    pos : usize,
+}
+
+/// Parses a document and returns its root element
+pub fn parse(src: &str) -> Result<Element, String> {
+    let mut p = Parser { src, pos: 0 };
+
+    p.skip_misc()?;
+    let root = p.element()?;
+    p.skip_misc()?;
+    if p.pos != p.src.len() {
+        return Err(p.error("content after the root element"));
+    }
+    Ok(root)
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> String {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        format!("line {}: {}", line, msg)
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<(), String> {
+        match self.rest().find(end) {
+            Some(i) => { self.pos += i + end.len(); Ok(()) },
+            None => Err(self.error(&format!("missing `{}`", end))),
+        }
+    }
+
+    /// Skips whitespace, comments, processing instructions and doctype
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_ws();
+            if self.rest().starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=')
+                      .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn element(&mut self) -> Result<Element, String> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected `<`"));
+        }
+        self.pos += 1;
+
+        let mut element = Element {
+            name        : self.name()?,
+            attrs       : Vec::new(),
+            children    : Vec::new(),
+            text        : String::new(),
+        };
+
+        // Attributes
+        loop {
+            self.skip_ws();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_ws();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected `=` after attribute name"));
+            }
+            self.pos += 1;
+            self.skip_ws();
+            let quote = match self.rest().chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let len = match self.rest().find(quote) {
+                Some(len) => len,
+                None => return Err(self.error("unterminated attribute value")),
+            };
+            let value = unescape(&self.rest()[..len]);
+            self.pos += len + 1;
+            element.attrs.push((name, value));
+        }
+
+        // Content
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!("`</{}>` closes `<{}>`", name, element.name)));
+                }
+                self.skip_until(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let len = match self.rest().find("]]>") {
+                    Some(len) => len,
+                    None => return Err(self.error("unterminated CDATA section")),
+                };
+                element.text.push_str(&self.rest()[..len]);
+                self.pos += len + 3;
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("`<{}>` is not closed", element.name)));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&unescape(&rest[..len]));
+                self.pos += len;
+            }
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_elements() {
+        let root = parse("<?xml version=\"1.0\"?>\n<!-- c -->\
+                          <a x='1'><b>one &amp; two</b><c/><b>3</b></a>").unwrap();
+
+        assert_eq!(root.name, "a");
+        assert_eq!(root.attr("x"), Some("1"));
+        assert_eq!(root.child_text("b"), Some("one & two"));
+        assert_eq!(root.children("b").count(), 2);
+        assert!(root.child("c").is_some());
+    }
+
+    #[test]
+    fn reports_mismatched_tags() {
+        assert!(parse("<a><b></a></b>").is_err());
+    }
+}